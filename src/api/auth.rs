@@ -0,0 +1,319 @@
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use async_trait::async_trait;
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use reqwest::{Client, Request};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use tracing::instrument;
+
+/// Default OAuth2 token endpoint of the XBRL US API
+pub const DEFAULT_TOKEN_URL: &str = "https://api.xbrl.us/oauth2/token";
+
+/// Header carrying the API key
+pub const API_KEY_HEADER: &str = "X-API-KEY";
+
+/// Tokens are refreshed this long before they actually expire
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// Adds credentials to outgoing requests
+#[async_trait]
+pub trait Authenticator: Send + Sync {
+    /// Add credentials to a request just before it is sent
+    async fn authenticate(&self, request: &mut Request) -> XbrlResult<()>;
+
+    /// Called when the server answers 401; return `true` to replay the request
+    /// once with fresh credentials
+    async fn handle_unauthorized(&self) -> XbrlResult<bool> {
+        Ok(false)
+    }
+
+    /// Long-lived credentials to scrub from recorded traffic
+    fn secrets(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Values identifying the account, hashed into response cache keys
+    fn identity(&self) -> Vec<String> {
+        self.secrets()
+    }
+
+    /// Called when a client is built, with the HTTP client it configured, for
+    /// authenticators that send requests of their own
+    fn use_http_client(&self, _client: &Client) {}
+}
+
+/// Authenticates with a static `X-API-KEY` header
+#[derive(Clone)]
+pub struct ApiKeyAuthenticator {
+    api_key: String,
+}
+
+impl ApiKeyAuthenticator {
+    /// Create an authenticator for the given API key
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for ApiKeyAuthenticator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeyAuthenticator")
+            .field("api_key", &"<redacted>")
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Authenticator for ApiKeyAuthenticator {
+    async fn authenticate(&self, request: &mut Request) -> XbrlResult<()> {
+        let mut value = HeaderValue::from_str(&self.api_key)
+            .map_err(|_| XbrlApiError::AuthError("API key is not a valid header value".into()))?;
+        value.set_sensitive(true);
+        request.headers_mut().insert(API_KEY_HEADER, value);
+        Ok(())
+    }
+
+    fn secrets(&self) -> Vec<String> {
+        vec![self.api_key.clone()]
+    }
+}
+
+/// Credentials for the OAuth2 password grant
+#[derive(Clone)]
+pub struct OAuth2Credentials {
+    pub client_id: String,
+    pub client_secret: String,
+    pub username: String,
+    pub password: String,
+    pub platform: Option<String>,
+}
+
+impl fmt::Debug for OAuth2Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuth2Credentials")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"<redacted>")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .field("platform", &self.platform)
+            .finish()
+    }
+}
+
+/// Token response returned by the OAuth2 token endpoint
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<u64>,
+}
+
+/// Form body sent to the OAuth2 token endpoint
+#[derive(Serialize)]
+struct TokenRequest<'a> {
+    grant_type: &'a str,
+    client_id: &'a str,
+    client_secret: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refresh_token: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    platform: Option<&'a str>,
+}
+
+#[derive(Debug)]
+struct TokenState {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: Option<Instant>,
+}
+
+impl TokenState {
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| Instant::now() + EXPIRY_MARGIN >= expires_at)
+    }
+}
+
+/// Authenticates with OAuth2 bearer tokens obtained through the password grant
+///
+/// Access tokens are fetched lazily, refreshed with the refresh token when they
+/// expire or the API answers 401, and re-requested with the password grant if
+/// the refresh itself is rejected. Token requests go through the HTTP client
+/// of the [`XbrlClient`](crate::XbrlClient) the authenticator is built into,
+/// unless one was set with [`with_http_client`](Self::with_http_client).
+pub struct OAuth2Authenticator {
+    http: OnceLock<Client>,
+    token_url: String,
+    credentials: OAuth2Credentials,
+    token: Mutex<Option<TokenState>>,
+}
+
+impl OAuth2Authenticator {
+    /// Create an authenticator using the default XBRL US token endpoint
+    pub fn new(credentials: OAuth2Credentials) -> Self {
+        Self {
+            http: OnceLock::new(),
+            token_url: DEFAULT_TOKEN_URL.to_string(),
+            credentials,
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Use a custom token endpoint
+    pub fn with_token_url(mut self, token_url: &str) -> Self {
+        self.token_url = token_url.to_string();
+        self
+    }
+
+    /// Use a custom HTTP client for token requests
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.http = OnceLock::from(client);
+        self
+    }
+
+    /// Get the token endpoint
+    pub fn token_url(&self) -> &str {
+        &self.token_url
+    }
+
+    /// Return a valid access token, fetching or refreshing it as needed
+    pub async fn access_token(&self) -> XbrlResult<String> {
+        let mut token = self.token.lock().await;
+
+        let state = match token.take() {
+            Some(state) if !state.is_expired() => state,
+            Some(state) => self.refresh_or_login(state.refresh_token.as_deref()).await?,
+            None => self.password_grant().await?,
+        };
+
+        let access_token = state.access_token.clone();
+        *token = Some(state);
+        Ok(access_token)
+    }
+
+    async fn refresh_or_login(&self, refresh_token: Option<&str>) -> XbrlResult<TokenState> {
+        if let Some(refresh_token) = refresh_token {
+            // A rejected refresh token falls back to a full login
+            if let Ok(state) = self.refresh_grant(refresh_token).await {
+                return Ok(state);
+            }
+        }
+        self.password_grant().await
+    }
+
+    async fn password_grant(&self) -> XbrlResult<TokenState> {
+        let credentials = &self.credentials;
+        self.request_token(&TokenRequest {
+            grant_type: "password",
+            client_id: &credentials.client_id,
+            client_secret: &credentials.client_secret,
+            username: Some(&credentials.username),
+            password: Some(&credentials.password),
+            refresh_token: None,
+            platform: credentials.platform.as_deref(),
+        })
+        .await
+    }
+
+    async fn refresh_grant(&self, refresh_token: &str) -> XbrlResult<TokenState> {
+        let credentials = &self.credentials;
+        self.request_token(&TokenRequest {
+            grant_type: "refresh_token",
+            client_id: &credentials.client_id,
+            client_secret: &credentials.client_secret,
+            username: None,
+            password: None,
+            refresh_token: Some(refresh_token),
+            platform: credentials.platform.as_deref(),
+        })
+        .await
+    }
+
+    #[instrument(name = "xbrl.token", skip(self, form), fields(grant_type = form.grant_type))]
+    async fn request_token(&self, form: &TokenRequest<'_>) -> XbrlResult<TokenState> {
+        let response = self
+            .http
+            .get_or_init(Client::new)
+            .post(&self.token_url)
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| XbrlApiError::AuthError(format!("token request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(XbrlApiError::AuthError(format!(
+                "{} grant rejected with status {}: {}",
+                form.grant_type,
+                status.as_u16(),
+                body
+            )));
+        }
+
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| XbrlApiError::AuthError(format!("invalid token response: {}", e)))?;
+
+        Ok(TokenState {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| Instant::now() + Duration::from_secs(seconds)),
+        })
+    }
+}
+
+impl fmt::Debug for OAuth2Authenticator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuth2Authenticator")
+            .field("token_url", &self.token_url)
+            .field("credentials", &self.credentials)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Authenticator for OAuth2Authenticator {
+    async fn authenticate(&self, request: &mut Request) -> XbrlResult<()> {
+        let access_token = self.access_token().await?;
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", access_token))
+            .map_err(|_| XbrlApiError::AuthError("access token is not a valid header value".into()))?;
+        value.set_sensitive(true);
+        request.headers_mut().insert(AUTHORIZATION, value);
+        Ok(())
+    }
+
+    async fn handle_unauthorized(&self) -> XbrlResult<bool> {
+        // Force the next `authenticate` call to refresh the token
+        if let Some(state) = self.token.lock().await.as_mut() {
+            state.expires_at = Some(Instant::now());
+        }
+        Ok(true)
+    }
+
+    fn secrets(&self) -> Vec<String> {
+        vec![self.credentials.client_secret.clone(), self.credentials.password.clone()]
+    }
+
+    fn identity(&self) -> Vec<String> {
+        vec![self.credentials.client_id.clone(), self.credentials.username.clone()]
+    }
+
+    fn use_http_client(&self, client: &Client) {
+        // A client set with `with_http_client` is kept
+        let _ = self.http.set(client.clone());
+    }
+}
@@ -0,0 +1,490 @@
+use crate::api::auth::Authenticator;
+use crate::api::endpoints::Endpoints;
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use url::Url;
+
+/// Lifetime of cached taxonomy, concept, dimension and network responses
+pub const DEFAULT_DEFINITION_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Cached response body with its validators
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub body: Vec<u8>,
+    /// `ETag` header, sent back as `If-None-Match` on revalidation
+    pub etag: Option<String>,
+    /// `Last-Modified` header, sent back as `If-Modified-Since` on revalidation
+    pub last_modified: Option<String>,
+    /// When the response was fetched or last revalidated
+    pub stored_at: SystemTime,
+}
+
+impl CachedResponse {
+    /// Create an entry stored now
+    pub fn new(body: Vec<u8>, etag: Option<String>, last_modified: Option<String>) -> Self {
+        Self {
+            body,
+            etag,
+            last_modified,
+            stored_at: SystemTime::now(),
+        }
+    }
+
+    /// Time since the entry was stored
+    pub fn age(&self) -> Duration {
+        self.stored_at.elapsed().unwrap_or_default()
+    }
+
+    /// Whether the entry can be revalidated with a conditional request
+    pub fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+/// Storage for cached responses, keyed by credential fingerprint and request URL
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Look up an entry
+    async fn get(&self, key: &str) -> XbrlResult<Option<CachedResponse>>;
+
+    /// Store or replace an entry
+    async fn put(&self, key: &str, response: CachedResponse) -> XbrlResult<()>;
+
+    /// Remove an entry
+    async fn remove(&self, key: &str) -> XbrlResult<()>;
+
+    /// Remove all entries
+    async fn clear(&self) -> XbrlResult<()>;
+}
+
+/// In-memory backend evicting the least recently used entries
+#[derive(Debug)]
+pub struct MemoryCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+#[derive(Debug, Default)]
+struct LruState {
+    entries: HashMap<String, (u64, CachedResponse)>,
+    /// Keys by last use
+    order: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl LruState {
+    fn touch(&mut self, key: &str) -> Option<&CachedResponse> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (used, response) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        self.order.insert(tick, key.to_string());
+        *used = tick;
+        Some(response)
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some((used, _)) = self.entries.remove(key) {
+            self.order.remove(&used);
+        }
+    }
+}
+
+impl MemoryCache {
+    /// Create a cache holding at most `capacity` responses
+    pub fn new(capacity: usize) -> XbrlResult<Self> {
+        if capacity == 0 {
+            return Err(XbrlApiError::ValidationError("cache capacity must be greater than zero".into()));
+        }
+        Ok(Self {
+            capacity,
+            state: Mutex::default(),
+        })
+    }
+
+    /// Maximum number of responses held
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of responses held
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the cache holds no responses
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryCache {
+    async fn get(&self, key: &str) -> XbrlResult<Option<CachedResponse>> {
+        Ok(self.lock().touch(key).cloned())
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse) -> XbrlResult<()> {
+        let mut state = self.lock();
+        state.remove(key);
+        state.tick += 1;
+        let tick = state.tick;
+        state.entries.insert(key.to_string(), (tick, response));
+        state.order.insert(tick, key.to_string());
+
+        while state.entries.len() > self.capacity {
+            let Some((_, oldest)) = state.order.pop_first() else { break };
+            state.entries.remove(&oldest);
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> XbrlResult<()> {
+        self.lock().remove(key);
+        Ok(())
+    }
+
+    async fn clear(&self) -> XbrlResult<()> {
+        *self.lock() = LruState::default();
+        Ok(())
+    }
+}
+
+/// On-disk backend keeping one metadata and one body file per response
+///
+/// Entries survive restarts and can be shared by processes using the same
+/// directory. File names are hashes of the key; the key itself is stored in
+/// the metadata so hash collisions read as misses. Both files are written
+/// under a temporary name and renamed into place, the metadata last.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    directory: PathBuf,
+}
+
+/// Metadata file of a [`DiskCache`] entry
+#[derive(Debug, Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    stored_at: SystemTime,
+}
+
+impl DiskCache {
+    /// Use `directory` for cache files, creating it if needed
+    pub fn new(directory: impl AsRef<Path>) -> XbrlResult<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        std::fs::create_dir_all(&directory).map_err(|e| io_error(&directory, e))?;
+        Ok(Self { directory })
+    }
+
+    /// Directory holding the cache files
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn paths(&self, key: &str) -> (PathBuf, PathBuf) {
+        let name = format!("{:016x}", fnv1a(key.as_bytes()));
+        (
+            self.directory.join(format!("{}.json", name)),
+            self.directory.join(format!("{}.body", name)),
+        )
+    }
+}
+
+#[async_trait]
+impl CacheBackend for DiskCache {
+    async fn get(&self, key: &str) -> XbrlResult<Option<CachedResponse>> {
+        let (meta_path, body_path) = self.paths(key);
+        let meta = match tokio::fs::read(&meta_path).await {
+            Ok(meta) => meta,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(&meta_path, e)),
+        };
+        let entry: DiskEntry = serde_json::from_slice(&meta)?;
+        if entry.key != key {
+            return Ok(None);
+        }
+        let body = match tokio::fs::read(&body_path).await {
+            Ok(body) => body,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(io_error(&body_path, e)),
+        };
+        Ok(Some(CachedResponse {
+            body,
+            etag: entry.etag,
+            last_modified: entry.last_modified,
+            stored_at: entry.stored_at,
+        }))
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse) -> XbrlResult<()> {
+        let (meta_path, body_path) = self.paths(key);
+        let meta = serde_json::to_vec(&DiskEntry {
+            key: key.to_string(),
+            etag: response.etag,
+            last_modified: response.last_modified,
+            stored_at: response.stored_at,
+        })?;
+        // The body goes first so a reader never finds metadata without its body
+        write_atomically(&body_path, &response.body).await?;
+        write_atomically(&meta_path, &meta).await
+    }
+
+    async fn remove(&self, key: &str) -> XbrlResult<()> {
+        let (meta_path, body_path) = self.paths(key);
+        for path in [meta_path, body_path] {
+            match tokio::fs::remove_file(&path).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(io_error(&path, e)),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    async fn clear(&self) -> XbrlResult<()> {
+        let mut entries = tokio::fs::read_dir(&self.directory)
+            .await
+            .map_err(|e| io_error(&self.directory, e))?;
+        while let Some(entry) = entries.next_entry().await.map_err(|e| io_error(&self.directory, e))? {
+            let path = entry.path();
+            if matches!(path.extension().and_then(|ext| ext.to_str()), Some("json" | "body" | "tmp")) {
+                tokio::fs::remove_file(&path).await.map_err(|e| io_error(&path, e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write `contents` to a temporary file beside `path`, then rename it into place
+///
+/// Readers see either the previous file or the complete new one, never a
+/// partial write. The temporary name is unique per process and call so
+/// concurrent writers of the same entry do not clobber each other's files.
+async fn write_atomically(path: &Path, contents: &[u8]) -> XbrlResult<()> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temporary = path.with_file_name(name);
+
+    if let Err(e) = tokio::fs::write(&temporary, contents).await {
+        let _ = tokio::fs::remove_file(&temporary).await;
+        return Err(io_error(&temporary, e));
+    }
+    if let Err(e) = tokio::fs::rename(&temporary, path).await {
+        let _ = tokio::fs::remove_file(&temporary).await;
+        return Err(io_error(path, e));
+    }
+    Ok(())
+}
+
+/// API endpoints with their own cache lifetime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CachedEndpoint {
+    Taxonomies,
+    Reports,
+    Facts,
+    Concepts,
+    Dimensions,
+    Networks,
+}
+
+impl CachedEndpoint {
+    /// Endpoint serving `url`, matching concept and network details to their listings
+    pub fn from_url(endpoints: &Endpoints, url: &Url) -> Option<Self> {
+        match endpoints.resource(url)? {
+            "taxonomies" => Some(Self::Taxonomies),
+            "reports" => Some(Self::Reports),
+            "facts" => Some(Self::Facts),
+            "concepts" => Some(Self::Concepts),
+            "dimensions" => Some(Self::Dimensions),
+            "networks" => Some(Self::Networks),
+            _ => None,
+        }
+    }
+}
+
+/// Which endpoints are cached, and for how long
+///
+/// By default taxonomy, concept, dimension and network responses are kept for
+/// [`DEFAULT_DEFINITION_TTL`]; reports and facts are not cached. Search is a
+/// `POST` and is never cached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachePolicy {
+    ttls: HashMap<CachedEndpoint, Duration>,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self::none()
+            .ttl(CachedEndpoint::Taxonomies, DEFAULT_DEFINITION_TTL)
+            .ttl(CachedEndpoint::Concepts, DEFAULT_DEFINITION_TTL)
+            .ttl(CachedEndpoint::Dimensions, DEFAULT_DEFINITION_TTL)
+            .ttl(CachedEndpoint::Networks, DEFAULT_DEFINITION_TTL)
+    }
+}
+
+impl CachePolicy {
+    /// Create the default policy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy caching nothing
+    pub fn none() -> Self {
+        Self { ttls: HashMap::new() }
+    }
+
+    /// Cache responses of `endpoint` for `ttl`
+    ///
+    /// Entries older than the TTL are revalidated with the server when they
+    /// carry an `ETag` or `Last-Modified` header, and refetched otherwise.
+    pub fn ttl(mut self, endpoint: CachedEndpoint, ttl: Duration) -> Self {
+        self.ttls.insert(endpoint, ttl);
+        self
+    }
+
+    /// Stop caching responses of `endpoint`
+    pub fn uncached(mut self, endpoint: CachedEndpoint) -> Self {
+        self.ttls.remove(&endpoint);
+        self
+    }
+
+    /// TTL of `endpoint`, or `None` if it is not cached
+    pub fn ttl_for(&self, endpoint: CachedEndpoint) -> Option<Duration> {
+        self.ttls.get(&endpoint).copied()
+    }
+}
+
+/// How a single call uses the response cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Serve fresh entries and revalidate stale ones
+    #[default]
+    Default,
+    /// Ignore stored entries, fetch from the server and store the response
+    Refresh,
+    /// Neither read nor write the cache
+    Bypass,
+}
+
+/// Response cache shared by a client and its clones
+///
+/// Keys start with a hash of the client's credentials, so clients with
+/// different accounts can share a backend without seeing each other's
+/// responses.
+#[derive(Clone)]
+pub struct ResponseCache {
+    backend: Arc<dyn CacheBackend>,
+    policy: Arc<CachePolicy>,
+    fingerprint: Option<u64>,
+}
+
+impl ResponseCache {
+    /// Create a cache on `backend` with `policy`
+    pub fn new<B: CacheBackend + 'static>(backend: B, policy: CachePolicy) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            policy: Arc::new(policy),
+            fingerprint: None,
+        }
+    }
+
+    /// In-memory cache of at most `capacity` responses with the default policy
+    pub fn memory(capacity: usize) -> XbrlResult<Self> {
+        Ok(Self::new(MemoryCache::new(capacity)?, CachePolicy::default()))
+    }
+
+    /// On-disk cache in `directory` with the default policy
+    pub fn disk(directory: impl AsRef<Path>) -> XbrlResult<Self> {
+        Ok(Self::new(DiskCache::new(directory)?, CachePolicy::default()))
+    }
+
+    /// Replace the policy
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+
+    /// Copy of the cache whose entries are only visible to the same credentials
+    pub fn for_credentials(&self, authenticator: &dyn Authenticator) -> Self {
+        let identity = authenticator.identity().join("\n");
+        Self {
+            fingerprint: Some(fnv1a(identity.as_bytes())),
+            ..self.clone()
+        }
+    }
+
+    /// Get the policy
+    pub fn policy(&self) -> &CachePolicy {
+        &self.policy
+    }
+
+    /// Get the backend
+    pub fn backend(&self) -> &Arc<dyn CacheBackend> {
+        &self.backend
+    }
+
+    /// Look up the response to a `GET` of `url`
+    pub async fn get(&self, url: &Url) -> XbrlResult<Option<CachedResponse>> {
+        self.backend.get(&self.key(url)).await
+    }
+
+    /// Store the response to a `GET` of `url`
+    pub async fn put(&self, url: &Url, response: CachedResponse) -> XbrlResult<()> {
+        self.backend.put(&self.key(url), response).await
+    }
+
+    /// Drop the response to a `GET` of `url`
+    ///
+    /// The URL must match the request exactly, including pagination parameters.
+    pub async fn invalidate(&self, url: &Url) -> XbrlResult<()> {
+        self.backend.remove(&self.key(url)).await
+    }
+
+    /// Drop all responses
+    pub async fn clear(&self) -> XbrlResult<()> {
+        self.backend.clear().await
+    }
+
+    fn key(&self, url: &Url) -> String {
+        match self.fingerprint {
+            Some(fingerprint) => format!("{:016x} {}", fingerprint, url),
+            None => url.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache").field("policy", &self.policy).finish_non_exhaustive()
+    }
+}
+
+/// 64-bit FNV-1a, stable across runs and platforms
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> XbrlApiError {
+    XbrlApiError::Io {
+        path: path.to_path_buf(),
+        source: error,
+    }
+}
@@ -0,0 +1,271 @@
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, Request};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Placeholder written in place of scrubbed secrets
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Response headers never written to a cassette
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "cookie", "set-cookie", "x-api-key"];
+
+/// Start of a cassette file, before its first interaction
+const HEAD: &str = "{\n\"interactions\": [\n";
+
+/// End of a cassette file, overwritten by each appended interaction
+const TAIL: &str = "\n]\n}\n";
+
+/// Whether a cassette records live traffic or replays it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests to the server and save each exchange
+    Record,
+    /// Serve saved exchanges without touching the network
+    Replay,
+}
+
+/// Saved request and response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+/// Request as sent before credentials were added
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Response status, headers and body
+///
+/// JSON bodies are stored parsed in `json` so cassettes stay readable and
+/// diffable; other bodies are kept verbatim in `text`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl RecordedResponse {
+    /// Body bytes as served on replay
+    pub fn body(&self) -> Vec<u8> {
+        match (&self.json, &self.text) {
+            (Some(json), _) => serde_json::to_vec(json).unwrap_or_default(),
+            (None, Some(text)) => text.clone().into_bytes(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// Headers as served on replay
+    pub fn header_map(&self) -> HeaderMap {
+        self.headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((HeaderName::from_bytes(name.as_bytes()).ok()?, HeaderValue::from_str(value).ok()?))
+            })
+            .collect()
+    }
+}
+
+/// Cassette file contents
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    /// Which interactions have been replayed
+    played: Vec<bool>,
+    /// Cassette file being recorded, once the first interaction is saved
+    file: Option<File>,
+}
+
+/// Record/replay store of API exchanges, shared by a client and its clones
+///
+/// In [`CassetteMode::Record`] every response is appended to a JSON file
+/// right away, without rewriting what was saved before. Requests are captured before the authenticator adds
+/// credentials, sensitive response headers are dropped and strings passed to
+/// [`Cassette::scrub`] are replaced with [`REDACTED`], so cassettes can be
+/// committed. In [`CassetteMode::Replay`] requests are matched on method, URL
+/// and body; identical requests get the recorded responses in order, the last
+/// one repeating once they run out.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    secrets: Arc<Vec<String>>,
+    state: Arc<Mutex<CassetteState>>,
+}
+
+impl Cassette {
+    /// Record into `path`, replacing any existing cassette
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Self::with_interactions(path.as_ref(), CassetteMode::Record, Vec::new())
+    }
+
+    /// Replay the cassette at `path`
+    pub fn replay(path: impl AsRef<Path>) -> XbrlResult<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| io_error(path, e))?;
+        let file: CassetteFile = serde_json::from_str(&json)?;
+        Ok(Self::with_interactions(path, CassetteMode::Replay, file.interactions))
+    }
+
+    /// Replay `path` if it exists, and record it otherwise
+    pub fn auto(path: impl AsRef<Path>) -> XbrlResult<Self> {
+        let path = path.as_ref();
+        if path.exists() { Self::replay(path) } else { Ok(Self::record(path)) }
+    }
+
+    /// Replace `secret` wherever it appears in recorded URLs, bodies and headers
+    pub fn scrub(mut self, secret: &str) -> Self {
+        if !secret.is_empty() {
+            Arc::make_mut(&mut self.secrets).push(secret.to_string());
+        }
+        self
+    }
+
+    fn with_interactions(path: &Path, mode: CassetteMode, interactions: Vec<Interaction>) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            mode,
+            secrets: Arc::default(),
+            state: Arc::new(Mutex::new(CassetteState {
+                played: vec![false; interactions.len()],
+                interactions,
+                file: None,
+            })),
+        }
+    }
+
+    /// Cassette file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record or replay
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// Saved interactions, in order
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.lock().interactions.clone()
+    }
+
+    /// Look up the response to `request`
+    pub fn play(&self, request: &Request) -> XbrlResult<RecordedResponse> {
+        let wanted = self.recorded_request(request);
+        let mut state = self.lock();
+        let matching: Vec<usize> = (0..state.interactions.len())
+            .filter(|&index| state.interactions[index].request == wanted)
+            .collect();
+
+        let index = matching
+            .iter()
+            .copied()
+            .find(|&index| !state.played[index])
+            .or_else(|| matching.last().copied())
+            .ok_or_else(|| XbrlApiError::CassetteMiss {
+                method: wanted.method.clone(),
+                url: wanted.url.clone(),
+            })?;
+        state.played[index] = true;
+        Ok(state.interactions[index].response.clone())
+    }
+
+    /// Save the response to `request` and append it to the cassette file
+    pub fn save(&self, request: RecordedRequest, status: u16, headers: &HeaderMap, body: &[u8]) -> XbrlResult<()> {
+        let body = self.redact(&String::from_utf8_lossy(body));
+        let (json, text) = match serde_json::from_str(&body) {
+            Ok(json) => (Some(json), None),
+            Err(_) => (None, Some(body)),
+        };
+        let response = RecordedResponse {
+            status,
+            headers: headers
+                .iter()
+                .filter(|(name, _)| !SENSITIVE_HEADERS.contains(&name.as_str()))
+                .filter_map(|(name, value)| Some((name.to_string(), self.redact(value.to_str().ok()?))))
+                .collect(),
+            json,
+            text,
+        };
+        let interaction = Interaction { request, response };
+        let json = serde_json::to_string_pretty(&interaction)?;
+
+        let mut state = self.lock();
+        self.append(&mut state, &json)?;
+        state.interactions.push(interaction);
+        state.played.push(true);
+        Ok(())
+    }
+
+    /// Write one interaction over the closing brackets of the file
+    ///
+    /// The first interaction creates the file, replacing any earlier one.
+    fn append(&self, state: &mut CassetteState, json: &str) -> XbrlResult<()> {
+        let failed = |e| io_error(&self.path, e);
+        let (file, separator) = match &mut state.file {
+            Some(file) => {
+                file.seek(SeekFrom::End(-(TAIL.len() as i64))).map_err(failed)?;
+                (file, ",\n")
+            }
+            None => {
+                if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+                }
+                let file = File::create(&self.path).map_err(failed)?;
+                (state.file.insert(file), HEAD)
+            }
+        };
+        file.write_all(format!("{}{}{}", separator, json, TAIL).as_bytes())
+            .map_err(failed)
+    }
+
+    /// Scrubbed copy of `request` as it is matched and saved
+    pub fn recorded_request(&self, request: &Request) -> RecordedRequest {
+        RecordedRequest {
+            method: request.method().to_string(),
+            url: self.redact(request.url().as_str()),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .filter(|_| request.method() != Method::GET)
+                .map(|body| self.redact(&String::from_utf8_lossy(body))),
+        }
+    }
+
+    fn redact(&self, text: &str) -> String {
+        self.secrets
+            .iter()
+            .fold(text.to_string(), |text, secret| text.replace(secret.as_str(), REDACTED))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CassetteState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> XbrlApiError {
+    XbrlApiError::Cassette {
+        path: path.to_path_buf(),
+        source: error,
+    }
+}
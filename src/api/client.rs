@@ -1,18 +1,49 @@
+use crate::api::auth::{ApiKeyAuthenticator, Authenticator};
+use crate::api::cache::{CacheMode, CachedEndpoint, CachedResponse, ResponseCache};
+use crate::api::cassette::{Cassette, CassetteMode};
 use crate::api::endpoints::Endpoints;
-use crate::api::models::{ApiResponse, PaginationParams, QueryParams, SearchParams};
+use crate::api::models::{ApiResponse, ErrorResponse, Page, PageInfo, PaginationParams, QueryParams, SearchParams};
+use crate::api::rate_limit::RateLimiter;
+use crate::api::retry::{parse_retry_after, RetryPolicy};
 use crate::data::facts::Fact;
 use crate::data::reports::Report;
 use crate::data::taxonomy::{Concept, Dimension, Network, Taxonomy};
 use crate::utils::errors::{XbrlApiError, XbrlResult};
-use reqwest::{Client, RequestBuilder};
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use reqwest::{Method, StatusCode};
+use reqwest::{Certificate, Client, Proxy, Request, RequestBuilder, Response};
+use futures::stream::{self, Stream, TryStreamExt};
 use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{field, instrument, Instrument, Span};
+use url::Url;
 // HashMap is used in the with_pagination method through QueryParams
 
 /// XBRL API Client
+///
+/// Cloning is cheap: clones share the connection pool, authenticator, retry
+/// policy, rate limiter, response cache and cassette.
+#[derive(Clone)]
 pub struct XbrlClient {
     client: Client,
-    api_key: String,
+    authenticator: Arc<dyn Authenticator>,
     endpoints: Endpoints,
+    retry_policy: Arc<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+    cache_mode: CacheMode,
+    cassette: Option<Cassette>,
+}
+
+/// Status, headers and body of a response read from the network or a cassette
+struct RawResponse {
+    status: u16,
+    headers: HeaderMap,
+    body: Vec<u8>,
+    retries: u32,
 }
 
 #[allow(dead_code)]
@@ -21,23 +52,39 @@ impl XbrlClient {
     pub fn new(api_key: &str) -> Self {
         Self {
             client: Client::new(),
-            api_key: api_key.to_string(),
+            authenticator: Arc::new(ApiKeyAuthenticator::new(api_key)),
             endpoints: Endpoints::default(),
+            retry_policy: Arc::default(),
+            rate_limiter: None,
+            cache: None,
+            cache_mode: CacheMode::Default,
+            cassette: None,
         }
     }
     
     /// Create a new XBRL API client with a custom base URL
-    pub fn with_base_url(api_key: &str, base_url: &str) -> Self {
-        Self {
+    pub fn with_base_url(api_key: &str, base_url: &str) -> XbrlResult<Self> {
+        Ok(Self {
             client: Client::new(),
-            api_key: api_key.to_string(),
-            endpoints: Endpoints::new(base_url),
-        }
+            authenticator: Arc::new(ApiKeyAuthenticator::new(api_key)),
+            endpoints: Endpoints::new(base_url)?,
+            retry_policy: Arc::default(),
+            rate_limiter: None,
+            cache: None,
+            cache_mode: CacheMode::Default,
+            cassette: None,
+        })
+    }
+    
+    /// Create a builder for configuring the underlying HTTP client
+    pub fn builder() -> XbrlClientBuilder {
+        XbrlClientBuilder::new()
     }
     
     /// Set the base URL for the API endpoints
-    pub fn set_base_url(&mut self, base_url: &str) {
-        self.endpoints = Endpoints::new(base_url);
+    pub fn set_base_url(&mut self, base_url: &str) -> XbrlResult<()> {
+        self.endpoints = Endpoints::new(base_url)?;
+        Ok(())
     }
     
     /// Get the current base URL
@@ -45,9 +92,88 @@ impl XbrlClient {
         self.endpoints.base_url()
     }
     
-    /// Add authentication header to request
-    fn auth_request(&self, request: RequestBuilder) -> RequestBuilder {
-        request.header("X-API-KEY", &self.api_key)
+    /// Get the retry policy applied to requests
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+    
+    /// Replace the retry policy applied to requests
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) -> XbrlResult<()> {
+        policy.validate()?;
+        self.retry_policy = Arc::new(policy);
+        Ok(())
+    }
+    
+    /// Get the rate limiter shared by this client and its clones
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+    
+    /// Replace the rate limiter applied to requests
+    pub fn set_rate_limiter(&mut self, rate_limiter: Option<RateLimiter>) {
+        self.rate_limiter = rate_limiter;
+    }
+    
+    /// Get the endpoint URLs, for example to invalidate cached responses
+    pub fn endpoints(&self) -> &Endpoints {
+        &self.endpoints
+    }
+    
+    /// Get the response cache shared by this client and its clones
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+    
+    /// Replace the response cache
+    ///
+    /// Entries are keyed by this client's credentials as well as the URL.
+    pub fn set_cache(&mut self, cache: Option<ResponseCache>) {
+        self.cache = cache.map(|cache| cache.for_credentials(self.authenticator.as_ref()));
+    }
+    
+    /// Get a clone of this client using the cache in `mode`
+    ///
+    /// `client.with_cache_mode(CacheMode::Refresh).get_concepts("us-gaap")`
+    /// refetches the concepts and replaces the cached entry, while
+    /// [`CacheMode::Bypass`] leaves the cache untouched.
+    pub fn with_cache_mode(&self, mode: CacheMode) -> Self {
+        Self {
+            cache_mode: mode,
+            ..self.clone()
+        }
+    }
+    
+    /// Drop the cached response to a `GET` of `url`
+    pub async fn invalidate_cache(&self, url: &Url) -> XbrlResult<()> {
+        match &self.cache {
+            Some(cache) => cache.invalidate(url).await,
+            None => Ok(()),
+        }
+    }
+    
+    /// Drop all cached responses
+    pub async fn clear_cache(&self) -> XbrlResult<()> {
+        match &self.cache {
+            Some(cache) => cache.clear().await,
+            None => Ok(()),
+        }
+    }
+    
+    /// Get the cassette recording or replaying this client's traffic
+    pub fn cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_ref()
+    }
+    
+    /// Record traffic to, or replay it from, a cassette
+    ///
+    /// The authenticator's credentials are scrubbed from anything the cassette records.
+    pub fn set_cassette(&mut self, cassette: Option<Cassette>) {
+        self.cassette = cassette.map(|cassette| scrub_credentials(cassette, self.authenticator.as_ref()));
+    }
+    
+    /// Get the authenticator adding credentials to requests
+    pub fn authenticator(&self) -> &Arc<dyn Authenticator> {
+        &self.authenticator
     }
     
     /// Execute API request and parse response
@@ -55,83 +181,451 @@ impl XbrlClient {
     where
         T: DeserializeOwned,
     {
-        let response = self.auth_request(request).send().await?;
+        self.fetch_response::<T>(request).await.map(|(data, _)| data)
+    }
+    
+    /// Execute a paged API request and return one page with its metadata
+    ///
+    /// The pagination parameters are merged into the request URL with
+    /// [`Endpoints::with_query`], replacing any `page` or `pageSize` already set.
+    pub async fn execute_paged_request<T>(
+        &self,
+        request: RequestBuilder,
+        pagination: PaginationParams,
+    ) -> XbrlResult<Page<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut params = QueryParams::new();
+        Self::with_pagination(&mut params, Some(pagination));
         
-        let status_code = response.status().as_u16();
+        let (client, request) = request.build_split();
+        let mut request = request?;
+        *request.url_mut() = Endpoints::with_query(request.url().clone(), &params);
         
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(XbrlApiError::ApiError {
-                status_code,
-                message: error_text,
-            });
+        let (items, page_info) = self
+            .fetch_response::<Vec<T>>(RequestBuilder::from_parts(client, request))
+            .await?;
+        Ok(Page::new(items, pagination, page_info))
+    }
+    
+    /// Send a request and decode the data and pagination metadata of the API response envelope
+    ///
+    /// Each call runs in an `xbrl.request` span recording the method, endpoint,
+    /// status, latency, retry count, response size and cache outcome.
+    /// Credentials are added by the authenticator and are never recorded.
+    async fn fetch_response<T>(&self, request: RequestBuilder) -> XbrlResult<(T, Option<PageInfo>)>
+    where
+        T: DeserializeOwned,
+    {
+        let (client, request) = request.build_split();
+        let mut request = request?;
+        
+        let span = tracing::info_span!(
+            "xbrl.request",
+            method = %request.method(),
+            endpoint = %request.url().path(),
+            status = field::Empty,
+            latency_ms = field::Empty,
+            retries = field::Empty,
+            response_bytes = field::Empty,
+            cache = field::Empty,
+        );
+        
+        async {
+            let started = Instant::now();
+            let span = Span::current();
+            let cache = self.cache_for(&request);
+            let method = request.method().clone();
+            let url = request.url().clone();
+            
+            let mut stale = None;
+            if let Some((cache, ttl)) = cache
+                && self.cache_mode == CacheMode::Default
+                && let Some(entry) = Self::cache_lookup(cache, request.url()).await
+            {
+                if entry.age() < ttl {
+                    span.record("cache", "hit");
+                    span.record("response_bytes", entry.body.len());
+                    span.record("latency_ms", started.elapsed().as_millis() as u64);
+                    tracing::debug!("request served from cache");
+                    return Self::decode_body(&method, &url, StatusCode::OK.as_u16(), &HeaderMap::new(), &entry.body);
+                }
+                if entry.has_validators() {
+                    Self::add_validators(request.headers_mut(), &entry);
+                    stale = Some(entry);
+                }
+            }
+            
+            let exchanged = self.exchange(&client, request).await;
+            span.record("latency_ms", started.elapsed().as_millis() as u64);
+            
+            let RawResponse {
+                status: status_code,
+                headers,
+                body,
+                retries,
+            } = match exchanged {
+                Ok(raw) => raw,
+                Err(error) => {
+                    if let Some(status_code) = error.status_code() {
+                        span.record("status", status_code);
+                    }
+                    tracing::debug!(error = %error, "request failed");
+                    return Err(error);
+                }
+            };
+            
+            span.record("status", status_code);
+            span.record("retries", retries);
+            span.record("response_bytes", body.len());
+            
+            if let Some((cache, _)) = cache {
+                if status_code == StatusCode::NOT_MODIFIED.as_u16()
+                    && let Some(mut entry) = stale
+                {
+                    span.record("cache", "revalidated");
+                    tracing::debug!("request revalidated");
+                    entry.stored_at = std::time::SystemTime::now();
+                    let decoded = Self::decode_body(&method, &url, StatusCode::OK.as_u16(), &headers, &entry.body);
+                    Self::cache_store(cache, &url, entry).await;
+                    return decoded;
+                }
+                if (200..300).contains(&status_code) {
+                    span.record("cache", "miss");
+                    let etag = Self::header_string(&headers, header::ETAG);
+                    let last_modified = Self::header_string(&headers, header::LAST_MODIFIED);
+                    Self::cache_store(cache, &url, CachedResponse::new(body.clone(), etag, last_modified)).await;
+                }
+            }
+            tracing::debug!("request completed");
+            
+            Self::decode_body(&method, &url, status_code, &headers, &body)
         }
+        .instrument(span)
+        .await
+    }
+    
+    /// Get the response to a request from the cassette or the network
+    ///
+    /// In record mode the exchange is saved to the cassette once the body
+    /// has been read; in replay mode the network is never used.
+    async fn exchange(&self, client: &Client, request: Request) -> XbrlResult<RawResponse> {
+        let recording = match &self.cassette {
+            Some(cassette) if cassette.mode() == CassetteMode::Replay => {
+                let recorded = cassette.play(&request)?;
+                return Ok(RawResponse {
+                    status: recorded.status,
+                    headers: recorded.header_map(),
+                    body: recorded.body(),
+                    retries: 0,
+                });
+            }
+            Some(cassette) => Some((cassette, cassette.recorded_request(&request))),
+            None => None,
+        };
         
-        let api_response: ApiResponse<T> = response.json().await?;
+        let (response, retries) = self.send_with_retry(client, request).await?;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?.to_vec();
         
+        if let Some((cassette, recorded)) = recording {
+            cassette.save(recorded, status, &headers, &body)?;
+        }
+        Ok(RawResponse {
+            status,
+            headers,
+            body,
+            retries,
+        })
+    }
+    
+    /// Decode a response body into its data and pagination metadata
+    ///
+    /// Non-2xx statuses and envelopes without data become errors naming the request.
+    fn decode_body<T>(
+        method: &Method,
+        url: &Url,
+        status_code: u16,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> XbrlResult<(T, Option<PageInfo>)>
+    where
+        T: DeserializeOwned,
+    {
+        if !(200..300).contains(&status_code) {
+            return Err(Self::response_error(method, url, status_code, headers, body));
+        }
+        
+        let api_response: ApiResponse<T> = serde_json::from_slice(body)?;
         match api_response.data {
-            Some(data) => Ok(data),
-            None => Err(XbrlApiError::ApiError {
-                status_code,
-                message: api_response.message.unwrap_or_else(|| "No error message provided".into()),
-            }),
+            Some(data) => Ok((data, api_response.pagination)),
+            None => {
+                let body = ErrorResponse {
+                    message: api_response.message,
+                    errors: api_response.errors,
+                    ..ErrorResponse::default()
+                };
+                Err(XbrlApiError::ApiError {
+                    status_code,
+                    message: body.message().unwrap_or_else(|| "No error message provided".into()),
+                    method: method.to_string(),
+                    url: url.to_string(),
+                })
+            }
         }
     }
     
-    // Get all taxonomies
-    pub async fn get_taxonomies(&self) -> Result<Vec<Taxonomy>, XbrlApiError> {
-        // Use the endpoints struct for consistency with other methods
-        let taxonomies_url = self.endpoints.taxonomies();
-        println!("Requesting taxonomies from URL: {}", taxonomies_url);
+    /// Turn a failed response into the error variant for its status
+    ///
+    /// JSON bodies give their message and field errors; other bodies are used
+    /// verbatim, and empty ones fall back to the status reason.
+    fn response_error(method: &Method, url: &Url, status_code: u16, headers: &HeaderMap, body: &[u8]) -> XbrlApiError {
+        let parsed: Option<ErrorResponse> = serde_json::from_slice(body).ok();
+        let message = parsed.as_ref().and_then(ErrorResponse::message).unwrap_or_else(|| {
+            let text = String::from_utf8_lossy(body).trim().to_string();
+            if !text.is_empty() {
+                return text;
+            }
+            StatusCode::from_u16(status_code)
+                .ok()
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or("No error message provided")
+                .to_string()
+        });
+        let (method, url) = (method.to_string(), url.to_string());
         
-        let request = self.client.get(&taxonomies_url);
+        match status_code {
+            400 | 422 => XbrlApiError::BadRequest {
+                status_code,
+                field_errors: parsed.and_then(|body| body.errors).unwrap_or_default(),
+                message,
+                method,
+                url,
+            },
+            401 => XbrlApiError::Unauthorized { message, method, url },
+            403 => XbrlApiError::Forbidden { message, method, url },
+            404 => XbrlApiError::NotFound { message, method, url },
+            429 => XbrlApiError::RateLimited {
+                retry_after: parse_retry_after(headers),
+                message,
+                method,
+                url,
+            },
+            500..=599 => XbrlApiError::ServerError { status_code, message, method, url },
+            _ => XbrlApiError::ApiError { status_code, message, method, url },
+        }
+    }
+    
+    /// The cache and TTL applying to a request, if it may use the cache
+    fn cache_for(&self, request: &Request) -> Option<(&ResponseCache, Duration)> {
+        let cache = self.cache.as_ref()?;
+        if self.cache_mode == CacheMode::Bypass || request.method() != Method::GET {
+            return None;
+        }
+        let endpoint = CachedEndpoint::from_url(&self.endpoints, request.url())?;
+        cache.policy().ttl_for(endpoint).map(|ttl| (cache, ttl))
+    }
+    
+    /// Read a cache entry; backend failures are logged and read as misses
+    async fn cache_lookup(cache: &ResponseCache, url: &Url) -> Option<CachedResponse> {
+        cache.get(url).await.unwrap_or_else(|error| {
+            tracing::warn!(error = %error, "response cache read failed");
+            None
+        })
+    }
+    
+    /// Write a cache entry; backend failures are logged and otherwise ignored
+    async fn cache_store(cache: &ResponseCache, url: &Url, entry: CachedResponse) {
+        if let Err(error) = cache.put(url, entry).await {
+            tracing::warn!(error = %error, "response cache write failed");
+        }
+    }
+    
+    /// Make a request conditional on the validators of a stale cache entry
+    fn add_validators(headers: &mut HeaderMap, entry: &CachedResponse) {
+        let validators = [
+            (header::IF_NONE_MATCH, &entry.etag),
+            (header::IF_MODIFIED_SINCE, &entry.last_modified),
+        ];
+        for (name, value) in validators {
+            if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(name, value);
+            }
+        }
+    }
+    
+    fn header_string(headers: &HeaderMap, name: header::HeaderName) -> Option<String> {
+        headers.get(name)?.to_str().ok().map(str::to_string)
+    }
+    
+    /// Send a request, retrying transient failures according to the retry policy
+    ///
+    /// Credentials are added to every attempt, and a 401 is replayed once if the
+    /// authenticator was able to refresh its credentials.
+    async fn send_with_retry(&self, client: &Client, mut request: Request) -> XbrlResult<(Response, u32)> {
+        let policy = &self.retry_policy;
+        let retryable = policy.allows_method(request.method());
+        let mut attempt = 1;
+        let mut reauthenticated = false;
         
-        self.execute_request::<Vec<Taxonomy>>(request).await
+        loop {
+            // Requests with streaming bodies cannot be cloned and are sent only once
+            let replay = request.try_clone();
+            
+            let mut outgoing = request;
+            self.authenticator.authenticate(&mut outgoing).await?;
+            
+            if let Some(rate_limiter) = &self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+            
+            let outcome = client.execute(outgoing).await;
+            let retries = attempt - 1;
+            let finish = |outcome: reqwest::Result<Response>| -> XbrlResult<(Response, u32)> {
+                // Recorded here as well so requests that end in a transport error keep the count
+                Span::current().record("retries", retries);
+                Ok((outcome?, retries))
+            };
+            let Some(next_request) = replay else {
+                return finish(outcome);
+            };
+            
+            if let Ok(response) = &outcome
+                && response.status() == StatusCode::UNAUTHORIZED
+                && !reauthenticated
+                && self.authenticator.handle_unauthorized().await?
+            {
+                tracing::debug!("credentials rejected, replaying request with refreshed credentials");
+                reauthenticated = true;
+                request = next_request;
+                continue;
+            }
+            
+            if !retryable || attempt >= policy.attempts() {
+                return finish(outcome);
+            }
+            
+            let delay = match &outcome {
+                Ok(response) if policy.is_retryable_status(response.status().as_u16()) => {
+                    policy.delay_for(attempt, Some(response.headers()))
+                }
+                Err(error) if RetryPolicy::is_transient_error(error) => policy.delay_for(attempt, None),
+                _ => return finish(outcome),
+            };
+            
+            match &outcome {
+                Ok(response) => tracing::warn!(
+                    attempt,
+                    status = response.status().as_u16(),
+                    delay_ms = delay.as_millis() as u64,
+                    "retrying request after retryable status"
+                ),
+                Err(error) => tracing::warn!(
+                    attempt,
+                    error = %error,
+                    delay_ms = delay.as_millis() as u64,
+                    "retrying request after transport error"
+                ),
+            }
+            
+            tokio::time::sleep(delay).await;
+            request = next_request;
+            attempt += 1;
+        }
+    }
+    
+    /// Get all taxonomies
+    #[instrument(skip(self))]
+    pub async fn get_taxonomies(&self) -> XbrlResult<Vec<Taxonomy>> {
+        let request = self.client.get(self.endpoints.taxonomies());
+        self.execute_request(request).await
     }
     
     /// Get reports for a specific taxonomy
+    #[instrument(skip(self))]
     pub async fn get_reports(&self, taxonomy: &str) -> XbrlResult<Vec<Report>> {
         let request = self.client.get(self.endpoints.reports(taxonomy));
         self.execute_request(request).await
     }
     
     /// Get facts for a specific report
+    #[instrument(skip(self))]
     pub async fn get_facts(&self, report_id: &str) -> XbrlResult<Vec<Fact>> {
         let request = self.client.get(self.endpoints.facts(report_id));
         self.execute_request(request).await
     }
     
+    /// Get one page of reports for a specific taxonomy
+    #[instrument(skip(self))]
+    pub async fn get_reports_paged(&self, taxonomy: &str, pagination: PaginationParams) -> XbrlResult<Page<Report>> {
+        let request = self.client.get(self.endpoints.reports(taxonomy));
+        self.execute_paged_request(request, pagination).await
+    }
+    
+    /// Stream every report for a specific taxonomy, fetching pages lazily
+    pub fn stream_reports(&self, taxonomy: &str, page_size: u32) -> impl Stream<Item = XbrlResult<Report>> + Send + 'static {
+        let taxonomy = taxonomy.to_string();
+        self.paginate(page_size, move |client, pagination| {
+            let taxonomy = taxonomy.clone();
+            async move { client.get_reports_paged(&taxonomy, pagination).await }
+        })
+    }
+    
+    /// Get one page of facts for a specific report
+    #[instrument(skip(self))]
+    pub async fn get_facts_paged(&self, report_id: &str, pagination: PaginationParams) -> XbrlResult<Page<Fact>> {
+        let request = self.client.get(self.endpoints.facts(report_id));
+        self.execute_paged_request(request, pagination).await
+    }
+    
+    /// Stream every fact of a specific report, fetching pages lazily
+    pub fn stream_facts(&self, report_id: &str, page_size: u32) -> impl Stream<Item = XbrlResult<Fact>> + Send + 'static {
+        let report_id = report_id.to_string();
+        self.paginate(page_size, move |client, pagination| {
+            let report_id = report_id.clone();
+            async move { client.get_facts_paged(&report_id, pagination).await }
+        })
+    }
+    
     /// Get concepts for a specific taxonomy
+    #[instrument(skip(self))]
     pub async fn get_concepts(&self, taxonomy: &str) -> XbrlResult<Vec<Concept>> {
         let request = self.client.get(self.endpoints.concepts(taxonomy));
         self.execute_request(request).await
     }
     
     /// Get details for a specific concept
+    #[instrument(skip(self))]
     pub async fn get_concept_details(&self, taxonomy: &str, concept_name: &str) -> XbrlResult<Concept> {
         let request = self.client.get(self.endpoints.concept_details(taxonomy, concept_name));
         self.execute_request(request).await
     }
     
     /// Get dimensions for a specific taxonomy
+    #[instrument(skip(self))]
     pub async fn get_dimensions(&self, taxonomy: &str) -> XbrlResult<Vec<Dimension>> {
         let request = self.client.get(self.endpoints.dimensions(taxonomy));
         self.execute_request(request).await
     }
     
     /// Get networks for a specific taxonomy
+    #[instrument(skip(self))]
     pub async fn get_networks(&self, taxonomy: &str) -> XbrlResult<Vec<Network>> {
         let request = self.client.get(self.endpoints.networks(taxonomy));
         self.execute_request(request).await
     }
     
     /// Get details for a specific network
+    #[instrument(skip(self))]
     pub async fn get_network_details(&self, network_id: &str) -> XbrlResult<Network> {
         let request = self.client.get(self.endpoints.network_details(network_id));
         self.execute_request(request).await
     }
     
     /// Search for facts
+    #[instrument(skip(self))]
     pub async fn search(&self, params: SearchParams) -> XbrlResult<Vec<Fact>> {
         let request = self.client
             .post(self.endpoints.search())
@@ -139,6 +633,47 @@ impl XbrlClient {
         self.execute_request(request).await
     }
     
+    /// Get one page of search results
+    #[instrument(skip(self))]
+    pub async fn search_paged(&self, params: &SearchParams, pagination: PaginationParams) -> XbrlResult<Page<Fact>> {
+        let request = self.client
+            .post(self.endpoints.search())
+            .json(params);
+        self.execute_paged_request(request, pagination).await
+    }
+    
+    /// Stream every search result, fetching pages lazily
+    pub fn stream_search(&self, params: SearchParams, page_size: u32) -> impl Stream<Item = XbrlResult<Fact>> + Send + 'static {
+        let params = Arc::new(params);
+        self.paginate(page_size, move |client, pagination| {
+            let params = params.clone();
+            async move { client.search_paged(&params, pagination).await }
+        })
+    }
+    
+    /// Walk pages starting at page 1 until the API reports no next page,
+    /// yielding the items of each page as they arrive
+    fn paginate<T, F, Fut>(&self, page_size: u32, fetch_page: F) -> impl Stream<Item = XbrlResult<T>> + Send + 'static
+    where
+        T: Send + 'static,
+        F: Fn(XbrlClient, PaginationParams) -> Fut + Send + 'static,
+        Fut: Future<Output = XbrlResult<Page<T>>> + Send + 'static,
+    {
+        let client = self.clone();
+        stream::try_unfold(Some(1), move |next_page| {
+            let request = next_page.map(|page| fetch_page(client.clone(), PaginationParams::new(page, page_size)));
+            async move {
+                let Some(request) = request else {
+                    return XbrlResult::Ok(None);
+                };
+                let page = request.await?;
+                let items = stream::iter(page.items.into_iter().map(Ok));
+                Ok(Some((items, page.next_page)))
+            }
+        })
+        .try_flatten()
+    }
+    
     /// Utility method to build queries with pagination
     pub fn with_pagination(params: &mut QueryParams, pagination: Option<PaginationParams>) {
         if let Some(pagination) = pagination {
@@ -151,3 +686,286 @@ impl XbrlClient {
         }
     }
 }
+
+
+/// Builder for [`XbrlClient`] exposing the HTTP transport settings
+///
+/// Either configure the transport through the individual settings or supply a
+/// ready-made `reqwest::Client` with [`XbrlClientBuilder::http_client`]; the two
+/// cannot be combined.
+#[derive(Default)]
+pub struct XbrlClientBuilder {
+    api_key: Option<String>,
+    authenticator: Option<Arc<dyn Authenticator>>,
+    base_url: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<String>,
+    root_certificates: Vec<Certificate>,
+    built_in_root_certs: Option<bool>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    http_client: Option<Client>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limit: Option<(f64, u32)>,
+    rate_limiter: Option<RateLimiter>,
+    cache: Option<ResponseCache>,
+    cassette: Option<Cassette>,
+}
+
+impl XbrlClientBuilder {
+    /// Create a new builder with no settings applied
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Set the API key sent with every request
+    pub fn api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+    
+    /// Authenticate with a custom authenticator, e.g. `OAuth2Authenticator`,
+    /// instead of an API key
+    pub fn authenticator<A: Authenticator + 'static>(mut self, authenticator: A) -> Self {
+        self.authenticator = Some(Arc::new(authenticator));
+        self
+    }
+    
+    /// Set a custom base URL for the API endpoints
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+    
+    /// Set the total timeout applied to each request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    
+    /// Set the timeout for establishing a connection
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+    
+    /// Set the `User-Agent` header sent with every request
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+    
+    /// Route all requests through the given proxy URL
+    pub fn proxy(mut self, proxy_url: &str) -> Self {
+        self.proxy = Some(proxy_url.to_string());
+        self
+    }
+    
+    /// Trust an additional root certificate
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+    
+    /// Enable or disable the platform's built-in root certificates
+    pub fn tls_built_in_root_certs(mut self, enabled: bool) -> Self {
+        self.built_in_root_certs = Some(enabled);
+        self
+    }
+    
+    /// Set the maximum number of idle connections kept per host
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+    
+    /// Set how long idle connections are kept in the pool
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+    
+    /// Use a caller-supplied `reqwest::Client` instead of building one
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+    
+    /// Set the retry policy for transient failures
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+    
+    /// Limit requests to `requests_per_second` on average with bursts of up to `burst`
+    pub fn rate_limit(mut self, requests_per_second: f64, burst: u32) -> Self {
+        self.rate_limit = Some((requests_per_second, burst));
+        self
+    }
+    
+    /// Draw from an existing rate limiter, e.g. one shared with clients built separately
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+    
+    /// Cache responses, sharing `cache` with other clients built from it
+    ///
+    /// Clients with different credentials never see each other's entries.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    
+    /// Record traffic to, or replay it from, `cassette`
+    ///
+    /// The API key, or the credentials of the custom authenticator, are
+    /// scrubbed from anything the cassette records.
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+    
+    /// Validate the settings and build the client
+    pub fn build(self) -> XbrlResult<XbrlClient> {
+        let authenticator: Arc<dyn Authenticator> = match (&self.authenticator, self.api_key.as_deref()) {
+            (Some(_), Some(_)) => {
+                return Err(XbrlApiError::ValidationError(
+                    "api_key and authenticator cannot both be set".into(),
+                ));
+            }
+            (Some(authenticator), None) => authenticator.clone(),
+            (None, Some(key)) if !key.trim().is_empty() => Arc::new(ApiKeyAuthenticator::new(key.trim())),
+            _ => return Err(XbrlApiError::ValidationError("API key must not be empty".into())),
+        };
+        
+        let endpoints = match &self.base_url {
+            Some(base_url) => {
+                Self::validate_url("base URL", base_url)?;
+                Endpoints::new(base_url)?
+            }
+            None => Endpoints::default(),
+        };
+        
+        let client = match &self.http_client {
+            Some(client) => {
+                if self.has_transport_settings() {
+                    return Err(XbrlApiError::ValidationError(
+                        "transport settings cannot be combined with a custom reqwest::Client".into(),
+                    ));
+                }
+                client.clone()
+            }
+            None => self.build_http_client()?,
+        };
+        authenticator.use_http_client(&client);
+        
+        let retry_policy = self.retry_policy.unwrap_or_default();
+        retry_policy.validate()?;
+        
+        let rate_limiter = match (self.rate_limiter, self.rate_limit) {
+            (Some(_), Some(_)) => {
+                return Err(XbrlApiError::ValidationError(
+                    "rate_limit and rate_limiter cannot both be set".into(),
+                ));
+            }
+            (Some(rate_limiter), None) => Some(rate_limiter),
+            (None, Some((requests_per_second, burst))) => Some(RateLimiter::new(requests_per_second, burst)?),
+            (None, None) => None,
+        };
+        
+        let cassette = self
+            .cassette
+            .map(|cassette| scrub_credentials(cassette, authenticator.as_ref()));
+        let cache = self.cache.map(|cache| cache.for_credentials(authenticator.as_ref()));
+        
+        Ok(XbrlClient {
+            client,
+            authenticator,
+            endpoints,
+            retry_policy: Arc::new(retry_policy),
+            rate_limiter,
+            cache,
+            cache_mode: CacheMode::Default,
+            cassette,
+        })
+    }
+    
+    fn has_transport_settings(&self) -> bool {
+        self.timeout.is_some()
+            || self.connect_timeout.is_some()
+            || self.user_agent.is_some()
+            || self.proxy.is_some()
+            || !self.root_certificates.is_empty()
+            || self.built_in_root_certs.is_some()
+            || self.pool_max_idle_per_host.is_some()
+            || self.pool_idle_timeout.is_some()
+    }
+    
+    fn build_http_client(&self) -> XbrlResult<Client> {
+        let mut builder = Client::builder();
+        
+        if let Some(timeout) = self.timeout {
+            Self::validate_duration("timeout", timeout)?;
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            Self::validate_duration("connect timeout", timeout)?;
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            let value = HeaderValue::from_str(user_agent).map_err(|_| {
+                XbrlApiError::ValidationError(format!("invalid user agent: {:?}", user_agent))
+            })?;
+            builder = builder.user_agent(value);
+        }
+        if let Some(proxy_url) = &self.proxy {
+            Self::validate_url("proxy URL", proxy_url)?;
+            let proxy = Proxy::all(proxy_url)
+                .map_err(|e| XbrlApiError::ValidationError(format!("invalid proxy URL: {}", e)))?;
+            builder = builder.proxy(proxy);
+        }
+        for certificate in &self.root_certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        if let Some(enabled) = self.built_in_root_certs {
+            builder = builder.tls_built_in_root_certs(enabled);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        Ok(builder.build()?)
+    }
+    
+    fn validate_duration(name: &str, duration: Duration) -> XbrlResult<()> {
+        if duration.is_zero() {
+            return Err(XbrlApiError::ValidationError(format!("{} must be greater than zero", name)));
+        }
+        Ok(())
+    }
+    
+    fn validate_url(name: &str, value: &str) -> XbrlResult<()> {
+        let url = Url::parse(value)
+            .map_err(|e| XbrlApiError::ValidationError(format!("invalid {}: {}", name, e)))?;
+        match url.scheme() {
+            "http" | "https" => Ok(()),
+            scheme => Err(XbrlApiError::ValidationError(format!(
+                "unsupported scheme '{}' in {}",
+                scheme, name
+            ))),
+        }
+    }
+}
+
+/// Keep the authenticator's credentials out of a cassette
+fn scrub_credentials(cassette: Cassette, authenticator: &dyn Authenticator) -> Cassette {
+    authenticator
+        .secrets()
+        .iter()
+        .fold(cassette, |cassette, secret| cassette.scrub(secret))
+}
@@ -1,75 +1,133 @@
+use crate::api::models::QueryParams;
+use crate::utils::errors::XbrlResult;
+use url::{ParseError, Url};
+
 /// Default base URL for the XBRL US API
 pub const DEFAULT_API_BASE_URL: &str = "https://api.xbrl.us/api/v1";
 
 /// API Endpoints
+///
+/// Path segments and query values are percent-encoded, so taxonomy, concept and
+/// network names may safely contain characters such as `&`, `#`, `/` or spaces.
+#[derive(Debug, Clone)]
 pub struct Endpoints {
-    base_url: String,
+    base_url: Url,
 }
 
 impl Default for Endpoints {
     fn default() -> Self {
-        Self {
-            base_url: DEFAULT_API_BASE_URL.to_string(),
-        }
+        Self::new(DEFAULT_API_BASE_URL).expect("default API base URL is valid")
     }
 }
 
 #[allow(dead_code)]
 impl Endpoints {
     /// Create a new Endpoints instance with a custom base URL
-    pub fn new(base_url: &str) -> Self {
-        Self {
-            base_url: base_url.to_string(),
+    pub fn new(base_url: &str) -> XbrlResult<Self> {
+        let base_url = Url::parse(base_url)?;
+        if base_url.cannot_be_a_base() {
+            return Err(ParseError::RelativeUrlWithCannotBeABaseBase.into());
         }
+        Ok(Self { base_url })
     }
-    
+
     /// Get the base URL
     pub fn base_url(&self) -> &str {
-        &self.base_url
+        self.base_url.as_str().trim_end_matches('/')
     }
-    
+
     /// Taxonomies endpoint
-    pub fn taxonomies(&self) -> String {
-        format!("{}/taxonomies", self.base_url)
+    pub fn taxonomies(&self) -> Url {
+        self.url(&["taxonomies"], &[])
     }
-    
+
     /// Reports endpoint for a specific taxonomy
-    pub fn reports(&self, taxonomy: &str) -> String {
-        format!("{}/reports?taxonomy={}", self.base_url, taxonomy)
+    pub fn reports(&self, taxonomy: &str) -> Url {
+        self.url(&["reports"], &[("taxonomy", taxonomy)])
     }
-    
+
     /// Facts endpoint for a specific report
-    pub fn facts(&self, report_id: &str) -> String {
-        format!("{}/facts?reportId={}", self.base_url, report_id)
+    pub fn facts(&self, report_id: &str) -> Url {
+        self.url(&["facts"], &[("reportId", report_id)])
     }
-    
+
     /// Concepts endpoint for a specific taxonomy
-    pub fn concepts(&self, taxonomy: &str) -> String {
-        format!("{}/concepts?taxonomy={}", self.base_url, taxonomy)
+    pub fn concepts(&self, taxonomy: &str) -> Url {
+        self.url(&["concepts"], &[("taxonomy", taxonomy)])
     }
-    
+
     /// Concept details endpoint
-    pub fn concept_details(&self, taxonomy: &str, concept_name: &str) -> String {
-        format!("{}/concepts/{}?taxonomy={}", self.base_url, concept_name, taxonomy)
+    pub fn concept_details(&self, taxonomy: &str, concept_name: &str) -> Url {
+        self.url(&["concepts", concept_name], &[("taxonomy", taxonomy)])
     }
-    
+
     /// Dimensions endpoint for a specific taxonomy
-    pub fn dimensions(&self, taxonomy: &str) -> String {
-        format!("{}/dimensions?taxonomy={}", self.base_url, taxonomy)
+    pub fn dimensions(&self, taxonomy: &str) -> Url {
+        self.url(&["dimensions"], &[("taxonomy", taxonomy)])
     }
-    
+
     /// Networks endpoint for a specific taxonomy
-    pub fn networks(&self, taxonomy: &str) -> String {
-        format!("{}/networks?taxonomy={}", self.base_url, taxonomy)
+    pub fn networks(&self, taxonomy: &str) -> Url {
+        self.url(&["networks"], &[("taxonomy", taxonomy)])
     }
-    
+
     /// Network details endpoint
-    pub fn network_details(&self, network_id: &str) -> String {
-        format!("{}/networks/{}", self.base_url, network_id)
+    pub fn network_details(&self, network_id: &str) -> Url {
+        self.url(&["networks", network_id], &[])
     }
-    
+
     /// Search endpoint
-    pub fn search(&self) -> String {
-        format!("{}/search", self.base_url)
+    pub fn search(&self) -> Url {
+        self.url(&["search"], &[])
     }
-}
\ No newline at end of file
+
+    /// First path segment below the base URL, such as `concepts` for the
+    /// concept details endpoint, or `None` for URLs outside the API
+    pub fn resource<'a>(&self, url: &'a Url) -> Option<&'a str> {
+        if url.origin() != self.base_url.origin() {
+            return None;
+        }
+        let base_path = self.base_url.path().trim_end_matches('/');
+        let rest = url.path().strip_prefix(base_path)?.strip_prefix('/')?;
+        rest.split('/').next().filter(|segment| !segment.is_empty())
+    }
+
+    /// Merge extra query parameters into an endpoint URL
+    ///
+    /// Parameters already present on the URL are replaced by entries with the
+    /// same name. Extra parameters are appended in key order so the resulting
+    /// URL is deterministic.
+    pub fn with_query(mut url: Url, params: &QueryParams) -> Url {
+        if params.is_empty() {
+            return url;
+        }
+
+        let existing: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| !params.contains_key(key.as_ref()))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+
+        let mut extra: Vec<(&String, &String)> = params.iter().collect();
+        extra.sort();
+
+        url.query_pairs_mut()
+            .clear()
+            .extend_pairs(existing)
+            .extend_pairs(extra);
+        url
+    }
+
+    /// Build an endpoint URL from encoded path segments and query pairs
+    fn url(&self, segments: &[&str], query: &[(&str, &str)]) -> Url {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .expect("base URL was checked to be a valid base")
+            .pop_if_empty()
+            .extend(segments);
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        url
+    }
+}
@@ -1,3 +1,9 @@
+pub mod auth;
+pub mod cache;
+pub mod cassette;
 pub mod client;
 pub mod endpoints;
-pub mod models;
\ No newline at end of file
+pub mod models;
+pub mod rate_limit;
+pub mod retry;
+pub mod traits;
\ No newline at end of file
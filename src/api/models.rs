@@ -1,13 +1,24 @@
+use crate::utils::errors::FieldError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Basic pagination parameters
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize)]
 pub struct PaginationParams {
     pub page: Option<u32>,
     pub page_size: Option<u32>,
 }
 
+impl PaginationParams {
+    /// Parameters requesting a specific page of the given size
+    pub fn new(page: u32, page_size: u32) -> Self {
+        Self {
+            page: Some(page),
+            page_size: Some(page_size),
+        }
+    }
+}
+
 /// Parameter for filtering by entity
 #[derive(Debug, Serialize)]
 pub struct EntityFilterParams {
@@ -17,7 +28,7 @@ pub struct EntityFilterParams {
 }
 
 /// Search request parameters
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SearchParams {
     pub taxonomy: String,
     pub concept_name: Option<String>,
@@ -32,14 +43,99 @@ pub struct SearchParams {
 }
 
 /// API Response wrapper
-/// status and errors fields are required by the API but not currently used directly in our code
+/// status is required by the API but not currently used directly in our code
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 pub struct ApiResponse<T> {
     pub status: String,
     pub message: Option<String>,
     pub data: Option<T>,
-    pub errors: Option<Vec<String>>,
+    pub errors: Option<Vec<FieldError>>,
+    #[serde(default)]
+    pub pagination: Option<PageInfo>,
+}
+
+/// JSON body of a failed response
+///
+/// Error responses use the [`ApiResponse`] envelope, except those from the
+/// OAuth2 layer, which use `error` and `error_description`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ErrorResponse {
+    pub message: Option<String>,
+    pub errors: Option<Vec<FieldError>>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+impl ErrorResponse {
+    /// Most specific message in the body
+    pub fn message(&self) -> Option<String> {
+        let joined = || {
+            let errors = self.errors.as_ref().filter(|errors| !errors.is_empty())?;
+            Some(errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))
+        };
+        [&self.message, &self.error_description, &self.error]
+            .into_iter()
+            .flatten()
+            .find(|message| !message.is_empty())
+            .cloned()
+            .or_else(joined)
+    }
+}
+
+/// Pagination metadata returned alongside paged results
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PageInfo {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    pub total_count: Option<u64>,
+    pub total_pages: Option<u32>,
+}
+
+/// A single page of results together with its pagination metadata
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub page_size: Option<u32>,
+    pub total_count: Option<u64>,
+    pub next_page: Option<u32>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from the requested pagination and the metadata the API returned
+    pub fn new(items: Vec<T>, requested: PaginationParams, info: Option<PageInfo>) -> Self {
+        let info = info.unwrap_or_default();
+        let page = info.page.or(requested.page).unwrap_or(1);
+        let page_size = info.page_size.or(requested.page_size);
+        let total_count = info.total_count;
+        
+        let has_more = if items.is_empty() {
+            false
+        } else if let Some(total_pages) = info.total_pages {
+            page < total_pages
+        } else if let (Some(total), Some(size)) = (total_count, page_size) {
+            u64::from(page) * u64::from(size) < total
+        } else if let Some(size) = page_size {
+            // Without totals, a full page means there may be more to fetch
+            items.len() as u64 >= u64::from(size)
+        } else {
+            false
+        };
+        
+        Self {
+            items,
+            page,
+            page_size,
+            total_count,
+            next_page: has_more.then(|| page + 1),
+        }
+    }
+    
+    /// Whether there are more pages after this one
+    pub fn has_next(&self) -> bool {
+        self.next_page.is_some()
+    }
 }
 
 /// Authentication header
@@ -0,0 +1,79 @@
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Token-bucket rate limiter
+///
+/// Cloning a `RateLimiter` shares the underlying bucket, so every clone draws
+/// from the same budget. The bucket starts full, allowing an initial burst.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+    requests_per_second: f64,
+    burst: u32,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `requests_per_second` on average and bursts of up to `burst` requests
+    pub fn new(requests_per_second: f64, burst: u32) -> XbrlResult<Self> {
+        if !requests_per_second.is_finite() || requests_per_second <= 0.0 {
+            return Err(XbrlApiError::ValidationError(
+                "rate limit must be a positive number of requests per second".into(),
+            ));
+        }
+        if burst == 0 {
+            return Err(XbrlApiError::ValidationError("rate limit burst must be at least 1".into()));
+        }
+
+        Ok(Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: burst as f64,
+                last_refill: Instant::now(),
+            })),
+            requests_per_second,
+            burst,
+        })
+    }
+
+    /// Get the sustained request rate
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    /// Get the maximum burst size
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Wait until a request may be sent and consume one token
+    pub async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Consume a token if one is available, otherwise fail with how long until the next one
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.requests_per_second).min(self.burst as f64);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Err(Duration::from_secs_f64(missing / self.requests_per_second))
+        }
+    }
+}
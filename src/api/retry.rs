@@ -0,0 +1,175 @@
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::Method;
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime};
+
+/// Status codes retried by the default policy
+pub const DEFAULT_RETRYABLE_STATUS_CODES: [u16; 4] = [429, 502, 503, 504];
+
+/// Retry policy applied by `XbrlClient::execute_request`
+///
+/// Delays grow exponentially from `initial_backoff` by `multiplier` per attempt
+/// and are capped at `max_backoff`. A `Retry-After` header on a retryable
+/// response takes precedence over the computed delay (still capped).
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    retryable_status_codes: HashSet<u16>,
+    respect_retry_after: bool,
+    retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            retryable_status_codes: DEFAULT_RETRYABLE_STATUS_CODES.into_iter().collect(),
+            respect_retry_after: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create the default retry policy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Set the total number of attempts, including the first one
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Set the delay before the first retry
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the upper bound for any single delay
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Set the factor the delay grows by after each attempt
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Enable or disable random jitter on computed delays
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Replace the set of status codes that trigger a retry
+    pub fn retryable_status_codes<I: IntoIterator<Item = u16>>(mut self, codes: I) -> Self {
+        self.retryable_status_codes = codes.into_iter().collect();
+        self
+    }
+
+    /// Honour the `Retry-After` header on retryable responses
+    pub fn respect_retry_after(mut self, respect: bool) -> Self {
+        self.respect_retry_after = respect;
+        self
+    }
+
+    /// Opt in to retrying non-idempotent requests such as `search`
+    pub fn retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    /// Get the total number of attempts
+    pub fn attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Check the policy for settings that can never work
+    pub fn validate(&self) -> XbrlResult<()> {
+        let invalid = |message: &str| Err(XbrlApiError::ValidationError(message.into()));
+        if self.max_attempts == 0 {
+            return invalid("retry policy max attempts must be at least 1");
+        }
+        if !self.multiplier.is_finite() || self.multiplier < 1.0 {
+            return invalid("retry policy multiplier must be a finite number >= 1.0");
+        }
+        if self.initial_backoff > self.max_backoff {
+            return invalid("retry policy initial backoff must not exceed max backoff");
+        }
+        Ok(())
+    }
+
+    /// Whether requests with this method may be retried
+    pub fn allows_method(&self, method: &Method) -> bool {
+        self.retry_non_idempotent || is_idempotent(method)
+    }
+
+    /// Whether a response with this status code should be retried
+    pub fn is_retryable_status(&self, status_code: u16) -> bool {
+        self.retryable_status_codes.contains(&status_code)
+    }
+
+    /// Whether a transport error is transient and worth retrying
+    pub fn is_transient_error(error: &reqwest::Error) -> bool {
+        error.is_connect() || error.is_timeout() || error.is_request()
+    }
+
+    /// Delay before the retry following the given (1-based) attempt
+    pub fn delay_for(&self, attempt: u32, headers: Option<&HeaderMap>) -> Duration {
+        if self.respect_retry_after
+            && let Some(delay) = headers.and_then(parse_retry_after)
+        {
+            return delay.min(self.max_backoff);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        let capped = backoff.min(self.max_backoff.as_secs_f64());
+
+        // Equal jitter: keep half of the delay and randomise the other half
+        let seconds = if self.jitter {
+            capped / 2.0 + fastrand::f64() * capped / 2.0
+        } else {
+            capped
+        };
+        Duration::from_secs_f64(seconds)
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS | Method::TRACE
+    )
+}
+
+/// Parse a `Retry-After` header given either in seconds or as an HTTP date
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+}
@@ -0,0 +1,87 @@
+use crate::api::client::XbrlClient;
+use crate::api::models::SearchParams;
+use crate::data::facts::Fact;
+use crate::data::reports::Report;
+use crate::data::taxonomy::{Concept, Dimension, Network, Taxonomy};
+use crate::utils::errors::XbrlResult;
+use async_trait::async_trait;
+#[cfg(any(test, feature = "testing"))]
+use mockall::automock;
+
+/// Source of XBRL API data
+///
+/// [`XbrlClient`] implements this against the REST API,
+/// `store::StoreApi` against a local `Store` (`store` feature)
+/// and [`OfflineApi`](crate::parser::offline::OfflineApi) against parsed files.
+/// Code written against `&dyn XbrlApi` or `impl XbrlApi` works with any of
+/// them, and with the generated `MockXbrlApi` (`testing` feature) in unit tests. Paging and
+/// streaming stay on [`XbrlClient`].
+#[cfg_attr(any(test, feature = "testing"), automock)]
+#[async_trait]
+pub trait XbrlApi: Send + Sync {
+    /// Get all taxonomies
+    async fn get_taxonomies(&self) -> XbrlResult<Vec<Taxonomy>>;
+
+    /// Get reports for a specific taxonomy
+    async fn get_reports(&self, taxonomy: &str) -> XbrlResult<Vec<Report>>;
+
+    /// Get facts for a specific report
+    async fn get_facts(&self, report_id: &str) -> XbrlResult<Vec<Fact>>;
+
+    /// Get concepts for a specific taxonomy
+    async fn get_concepts(&self, taxonomy: &str) -> XbrlResult<Vec<Concept>>;
+
+    /// Get details for a specific concept
+    async fn get_concept_details(&self, taxonomy: &str, concept_name: &str) -> XbrlResult<Concept>;
+
+    /// Get dimensions for a specific taxonomy
+    async fn get_dimensions(&self, taxonomy: &str) -> XbrlResult<Vec<Dimension>>;
+
+    /// Get networks for a specific taxonomy
+    async fn get_networks(&self, taxonomy: &str) -> XbrlResult<Vec<Network>>;
+
+    /// Get details for a specific network
+    async fn get_network_details(&self, network_id: &str) -> XbrlResult<Network>;
+
+    /// Search for facts
+    async fn search(&self, params: SearchParams) -> XbrlResult<Vec<Fact>>;
+}
+
+#[async_trait]
+impl XbrlApi for XbrlClient {
+    async fn get_taxonomies(&self) -> XbrlResult<Vec<Taxonomy>> {
+        XbrlClient::get_taxonomies(self).await
+    }
+
+    async fn get_reports(&self, taxonomy: &str) -> XbrlResult<Vec<Report>> {
+        XbrlClient::get_reports(self, taxonomy).await
+    }
+
+    async fn get_facts(&self, report_id: &str) -> XbrlResult<Vec<Fact>> {
+        XbrlClient::get_facts(self, report_id).await
+    }
+
+    async fn get_concepts(&self, taxonomy: &str) -> XbrlResult<Vec<Concept>> {
+        XbrlClient::get_concepts(self, taxonomy).await
+    }
+
+    async fn get_concept_details(&self, taxonomy: &str, concept_name: &str) -> XbrlResult<Concept> {
+        XbrlClient::get_concept_details(self, taxonomy, concept_name).await
+    }
+
+    async fn get_dimensions(&self, taxonomy: &str) -> XbrlResult<Vec<Dimension>> {
+        XbrlClient::get_dimensions(self, taxonomy).await
+    }
+
+    async fn get_networks(&self, taxonomy: &str) -> XbrlResult<Vec<Network>> {
+        XbrlClient::get_networks(self, taxonomy).await
+    }
+
+    async fn get_network_details(&self, network_id: &str) -> XbrlResult<Network> {
+        XbrlClient::get_network_details(self, network_id).await
+    }
+
+    async fn search(&self, params: SearchParams) -> XbrlResult<Vec<Fact>> {
+        XbrlClient::search(self, params).await
+    }
+}
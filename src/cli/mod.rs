@@ -0,0 +1,155 @@
+//! Command-line interface of the `xbrl_api_client` binary
+
+pub mod output;
+
+use crate::api::cache::ResponseCache;
+use crate::api::client::XbrlClient;
+use crate::api::models::SearchParams;
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use clap::{Args, Parser, Subcommand};
+use output::{write_record, write_records, NetworkRow, OutputFormat};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Query the XBRL US API
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// API key
+    #[arg(long, env = "XBRL_API_KEY", hide_env_values = true)]
+    pub api_key: Option<String>,
+
+    /// API base URL
+    #[arg(long, env = "XBRL_API_BASE_URL")]
+    pub base_url: Option<String>,
+
+    /// Cache taxonomy, concept, dimension and network responses in this directory
+    #[arg(long, env = "XBRL_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, short, value_enum, default_value_t = OutputFormat::Table, global = true)]
+    pub format: OutputFormat,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// Subcommands, one per API endpoint
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List taxonomies
+    Taxonomies,
+    /// List the reports of a taxonomy
+    Reports { taxonomy: String },
+    /// List the facts of a report
+    Facts { report_id: String },
+    /// List the concepts of a taxonomy
+    Concepts { taxonomy: String },
+    /// Show one concept
+    Concept { taxonomy: String, name: String },
+    /// List the dimensions of a taxonomy
+    Dimensions { taxonomy: String },
+    /// List the networks of a taxonomy
+    Networks { taxonomy: String },
+    /// Show the nodes of one network
+    Network { id: String },
+    /// Search for facts
+    Search(SearchArgs),
+}
+
+/// Flags of the `search` subcommand, one per [`SearchParams`] field
+#[derive(Debug, Args)]
+pub struct SearchArgs {
+    #[arg(long)]
+    pub taxonomy: String,
+    #[arg(long)]
+    pub concept_name: Option<String>,
+    #[arg(long)]
+    pub entity_id: Option<String>,
+    #[arg(long)]
+    pub fiscal_year: Option<u32>,
+    /// For example `FY` or `Q1`
+    #[arg(long)]
+    pub fiscal_period: Option<String>,
+    #[arg(long)]
+    pub dimension_name: Option<String>,
+    #[arg(long)]
+    pub member_name: Option<String>,
+    #[arg(long)]
+    pub text_search: Option<String>,
+    #[arg(long)]
+    pub value_greater_than: Option<f64>,
+    #[arg(long)]
+    pub value_less_than: Option<f64>,
+}
+
+impl From<SearchArgs> for SearchParams {
+    fn from(args: SearchArgs) -> Self {
+        SearchParams {
+            taxonomy: args.taxonomy,
+            concept_name: args.concept_name,
+            entity_id: args.entity_id,
+            fiscal_year: args.fiscal_year,
+            fiscal_period: args.fiscal_period,
+            dimension_name: args.dimension_name,
+            member_name: args.member_name,
+            text_search: args.text_search,
+            value_greater_than: args.value_greater_than,
+            value_less_than: args.value_less_than,
+        }
+    }
+}
+
+impl Cli {
+    /// Build the client configured by the global flags
+    pub fn client(&self) -> XbrlResult<XbrlClient> {
+        let api_key = self.api_key.as_deref().ok_or_else(|| {
+            XbrlApiError::ValidationError("API key missing: set XBRL_API_KEY or pass --api-key".into())
+        })?;
+
+        let mut builder = XbrlClient::builder().api_key(api_key);
+        if let Some(base_url) = &self.base_url {
+            builder = builder.base_url(base_url);
+        }
+        if let Some(cache_dir) = &self.cache_dir {
+            builder = builder.cache(ResponseCache::disk(cache_dir)?);
+        }
+        builder.build()
+    }
+}
+
+/// Run the command with the configured client, printing to `out`
+pub async fn run<W: Write + ?Sized>(cli: Cli, out: &mut W) -> XbrlResult<()> {
+    let client = cli.client()?;
+    execute(&client, cli.command, cli.format, out).await
+}
+
+/// Run a command with `client`, printing to `out`
+pub async fn execute<W: Write + ?Sized>(
+    client: &XbrlClient,
+    command: Command,
+    format: OutputFormat,
+    out: &mut W,
+) -> XbrlResult<()> {
+    match command {
+        Command::Taxonomies => write_records(out, &client.get_taxonomies().await?, format),
+        Command::Reports { taxonomy } => write_records(out, &client.get_reports(&taxonomy).await?, format),
+        Command::Facts { report_id } => write_records(out, &client.get_facts(&report_id).await?, format),
+        Command::Concepts { taxonomy } => write_records(out, &client.get_concepts(&taxonomy).await?, format),
+        Command::Concept { taxonomy, name } => {
+            write_record(out, &client.get_concept_details(&taxonomy, &name).await?, format)
+        }
+        Command::Dimensions { taxonomy } => write_records(out, &client.get_dimensions(&taxonomy).await?, format),
+        Command::Networks { taxonomy } => write_records(out, &client.get_networks(&taxonomy).await?, format),
+        Command::Network { id } => {
+            let network = client.get_network_details(&id).await?;
+            match format {
+                // JSON keeps the node tree; tables and CSV list one node per row
+                OutputFormat::Json | OutputFormat::Ndjson => write_record(out, &network, format),
+                OutputFormat::Table | OutputFormat::Csv => write_records(out, &NetworkRow::flatten(&network), format),
+            }
+        }
+        Command::Search(args) => write_records(out, &client.search(args.into()).await?, format),
+    }
+}
@@ -0,0 +1,290 @@
+use crate::data::facts::Fact;
+use crate::data::reports::Report;
+use crate::data::taxonomy::{Concept, Dimension, Network, NetworkNode, Taxonomy};
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::Write;
+
+/// Widest table cell before it is cut off
+const MAX_CELL_WIDTH: usize = 60;
+
+/// How results are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns
+    #[default]
+    Table,
+    /// Pretty-printed JSON array or object
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+/// Record that can be printed as a table or CSV row
+pub trait Tabular {
+    /// Column names
+    fn headers() -> &'static [&'static str];
+
+    /// Cells, one per column
+    fn row(&self) -> Vec<String>;
+
+    /// Column names of table output, when they differ from [`headers`](Self::headers)
+    fn table_headers() -> &'static [&'static str] {
+        Self::headers()
+    }
+
+    /// Cells of table output, one per [`table_headers`](Self::table_headers) column
+    fn table_row(&self) -> Vec<String> {
+        self.row()
+    }
+}
+
+/// Print records in `format`
+pub fn write_records<T, W>(out: &mut W, records: &[T], format: OutputFormat) -> XbrlResult<()>
+where
+    T: Tabular + Serialize,
+    W: Write + ?Sized,
+{
+    match format {
+        OutputFormat::Table => write_table(out, T::table_headers(), records.iter().map(T::table_row)),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, records)?;
+            writeln!(out).map_err(output_error)
+        }
+        OutputFormat::Ndjson => {
+            for record in records {
+                serde_json::to_writer(&mut *out, record)?;
+                writeln!(out).map_err(output_error)?;
+            }
+            Ok(())
+        }
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(&mut *out);
+            writer.write_record(T::headers()).map_err(csv_error)?;
+            for record in records {
+                writer.write_record(record.row()).map_err(csv_error)?;
+            }
+            writer.flush().map_err(output_error)
+        }
+    }
+}
+
+/// Print a single record; JSON output is an object rather than an array
+pub fn write_record<T, W>(out: &mut W, record: &T, format: OutputFormat) -> XbrlResult<()>
+where
+    T: Tabular + Serialize,
+    W: Write + ?Sized,
+{
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, record)?;
+            writeln!(out).map_err(output_error)
+        }
+        _ => write_records(out, std::slice::from_ref(record), format),
+    }
+}
+
+fn write_table<W, I>(out: &mut W, headers: &[&str], rows: I) -> XbrlResult<()>
+where
+    W: Write + ?Sized,
+    I: Iterator<Item = Vec<String>>,
+{
+    let rows: Vec<Vec<String>> = rows.map(|row| row.into_iter().map(|cell| truncate(&cell)).collect()).collect();
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header: Vec<String> = headers.iter().map(|header| header.to_uppercase()).collect();
+    write_row(out, &header, &widths)?;
+    for row in &rows {
+        write_row(out, row, &widths)?;
+    }
+    Ok(())
+}
+
+fn write_row<W: Write + ?Sized>(out: &mut W, cells: &[String], widths: &[usize]) -> XbrlResult<()> {
+    let line: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+        .collect();
+    writeln!(out, "{}", line.join("  ").trim_end()).map_err(output_error)
+}
+
+/// Cut a cell to [`MAX_CELL_WIDTH`] characters and flatten line breaks
+fn truncate(cell: &str) -> String {
+    let cell = cell.replace(['\r', '\n'], " ");
+    if cell.chars().count() <= MAX_CELL_WIDTH {
+        return cell;
+    }
+    let mut truncated: String = cell.chars().take(MAX_CELL_WIDTH - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn output_error(error: std::io::Error) -> XbrlApiError {
+    XbrlApiError::Output(Box::new(error))
+}
+
+fn csv_error(error: csv::Error) -> XbrlApiError {
+    XbrlApiError::Output(Box::new(error))
+}
+
+fn optional<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(ToString::to_string).unwrap_or_default()
+}
+
+impl Tabular for Taxonomy {
+    fn headers() -> &'static [&'static str] {
+        &["name", "version", "description"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.name.clone(), self.version.clone(), self.description.clone()]
+    }
+}
+
+impl Tabular for Report {
+    fn headers() -> &'static [&'static str] {
+        &["id", "entity_name", "fiscal_year", "fiscal_period", "filing_date", "report_type"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.entity_name.clone(),
+            self.fiscal_year.to_string(),
+            self.fiscal_period.clone(),
+            self.filing_date.clone(),
+            optional(&self.report_type),
+        ]
+    }
+}
+
+impl Tabular for Fact {
+    fn headers() -> &'static [&'static str] {
+        &["concept_name", "entity_id", "period", "value", "unit", "decimals", "dimensions"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let mut dimensions: Vec<String> = self
+            .dimensions
+            .iter()
+            .flatten()
+            .map(|(axis, member)| format!("{}={}", axis, member))
+            .collect();
+        dimensions.sort();
+        vec![
+            self.concept_name.clone(),
+            self.entity_id.clone(),
+            self.period.to_string(),
+            self.value.to_string(),
+            optional(&self.unit),
+            optional(&self.decimals),
+            dimensions.join(";"),
+        ]
+    }
+}
+
+impl Tabular for Concept {
+    fn headers() -> &'static [&'static str] {
+        &["name", "label", "type_name", "period_type", "balance"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.label.clone(),
+            self.type_name.clone(),
+            optional(&self.period_type),
+            optional(&self.balance),
+        ]
+    }
+}
+
+impl Tabular for Dimension {
+    fn headers() -> &'static [&'static str] {
+        &["name", "label", "members"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let members: Vec<&str> = self.members.iter().flatten().map(|member| member.name.as_str()).collect();
+        vec![self.name.clone(), self.label.clone(), members.join(";")]
+    }
+}
+
+impl Tabular for Network {
+    fn headers() -> &'static [&'static str] {
+        &["id", "name", "role"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.id.clone(), self.name.clone(), self.role.clone()]
+    }
+}
+
+/// Node of a network with its depth in the tree, for printing the tree as rows
+#[derive(Debug, Serialize)]
+pub struct NetworkRow<'a> {
+    pub depth: usize,
+    pub concept_name: &'a str,
+    pub concept_label: &'a str,
+    pub parent: Option<&'a str>,
+    pub order: Option<f64>,
+}
+
+impl<'a> NetworkRow<'a> {
+    /// Rows of all nodes of `network`, parents before their children
+    pub fn flatten(network: &'a Network) -> Vec<Self> {
+        fn visit<'a>(nodes: &'a [NetworkNode], depth: usize, rows: &mut Vec<NetworkRow<'a>>) {
+            for node in nodes {
+                rows.push(NetworkRow {
+                    depth,
+                    concept_name: &node.concept_name,
+                    concept_label: &node.concept_label,
+                    parent: node.parent.as_deref(),
+                    order: node.order,
+                });
+                visit(node.children.as_deref().unwrap_or_default(), depth + 1, rows);
+            }
+        }
+
+        let mut rows = Vec::new();
+        visit(network.nodes.as_deref().unwrap_or_default(), 0, &mut rows);
+        rows
+    }
+}
+
+impl Tabular for NetworkRow<'_> {
+    fn headers() -> &'static [&'static str] {
+        &["depth", "concept_name", "concept_label", "parent", "order"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.depth.to_string(),
+            self.concept_name.to_string(),
+            self.concept_label.to_string(),
+            optional(&self.parent),
+            optional(&self.order),
+        ]
+    }
+
+    // Tables show the tree by indenting names instead of a depth column
+    fn table_headers() -> &'static [&'static str] {
+        &["concept_name", "concept_label", "parent", "order"]
+    }
+
+    fn table_row(&self) -> Vec<String> {
+        let mut cells = self.row();
+        cells.remove(0);
+        cells[0] = format!("{}{}", "  ".repeat(self.depth), self.concept_name);
+        cells
+    }
+}
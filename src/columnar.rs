@@ -0,0 +1,393 @@
+//! Apache Arrow and Parquet export of facts and reports
+//!
+//! Enabled with the `arrow` feature. Schemas are fixed, so batches built from
+//! different reports can be appended to the same table.
+
+use crate::data::facts::{Decimals, Fact, FactValue, Precision};
+use crate::data::period::parse_date;
+use crate::data::reports::Report;
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use arrow::array::{
+    ArrayRef, BooleanBuilder, Date32Builder, Decimal128Builder, Int32Builder, MapBuilder, MapFieldNames, StringBuilder,
+    UInt32Builder,
+};
+use arrow::datatypes::{DataType, Date32Type, Field, Fields, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use futures::{Stream, StreamExt};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use rust_decimal::Decimal;
+use std::io::Write;
+use std::sync::{Arc, LazyLock};
+
+/// Precision of the `value` column
+pub const VALUE_PRECISION: u8 = 38;
+
+/// Scale of the `value` column; values with more decimal places are rounded
+pub const VALUE_SCALE: i8 = 10;
+
+/// Records per batch when writing Parquet streams
+pub const DEFAULT_BATCH_SIZE: usize = 10_000;
+
+static FACT_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("report_id", DataType::Utf8, false),
+        Field::new("concept_name", DataType::Utf8, false),
+        Field::new("concept_label", DataType::Utf8, true),
+        Field::new("entity_id", DataType::Utf8, false),
+        Field::new("entity_name", DataType::Utf8, true),
+        Field::new("period_start", DataType::Date32, true),
+        Field::new("period_end", DataType::Date32, true),
+        Field::new("value", DataType::Decimal128(VALUE_PRECISION, VALUE_SCALE), true),
+        Field::new("text_value", DataType::Utf8, true),
+        Field::new("is_nil", DataType::Boolean, false),
+        Field::new("unit", DataType::Utf8, true),
+        Field::new("decimals", DataType::Int32, true),
+        Field::new("precision", DataType::Int32, true),
+        Field::new("dimensions", dimensions_type(), true),
+        Field::new("filing_url", DataType::Utf8, true),
+    ]))
+});
+
+static REPORT_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("accession_number", DataType::Utf8, true),
+        Field::new("filing_date", DataType::Date32, true),
+        Field::new("fiscal_period", DataType::Utf8, false),
+        Field::new("fiscal_year", DataType::UInt32, false),
+        Field::new("entity_id", DataType::Utf8, false),
+        Field::new("entity_name", DataType::Utf8, false),
+        Field::new("cik", DataType::Utf8, true),
+        Field::new("taxonomy", DataType::Utf8, false),
+        Field::new("report_type", DataType::Utf8, true),
+        Field::new("filing_url", DataType::Utf8, true),
+    ]))
+});
+
+/// Field names of the `dimensions` map
+fn dimension_field_names() -> MapFieldNames {
+    MapFieldNames {
+        entry: "entries".to_string(),
+        key: "key".to_string(),
+        value: "value".to_string(),
+    }
+}
+
+/// `map<utf8, utf8>` of dimension names to members
+fn dimensions_type() -> DataType {
+    let names = dimension_field_names();
+    let entries = Fields::from(vec![
+        Field::new(names.key, DataType::Utf8, false),
+        Field::new(names.value, DataType::Utf8, true),
+    ]);
+    DataType::Map(Arc::new(Field::new(names.entry, DataType::Struct(entries), false)), false)
+}
+
+/// Types with a fixed Arrow schema
+pub trait ArrowRecord: Sized {
+    /// Schema of every batch built from this type
+    fn schema() -> SchemaRef;
+
+    /// Convert records to a batch with [`ArrowRecord::schema`]
+    fn to_record_batch(records: &[Self]) -> XbrlResult<RecordBatch>;
+}
+
+impl ArrowRecord for Fact {
+    /// Fact schema
+    ///
+    /// Periods use `period_start` (durations only) and `period_end` (instants
+    /// and durations). Numeric values go to `value`, everything else to
+    /// `text_value`. `decimals` and `precision` are null when absent or `INF`.
+    fn schema() -> SchemaRef {
+        FACT_SCHEMA.clone()
+    }
+
+    fn to_record_batch(facts: &[Self]) -> XbrlResult<RecordBatch> {
+        let mut id = StringBuilder::new();
+        let mut report_id = StringBuilder::new();
+        let mut concept_name = StringBuilder::new();
+        let mut concept_label = StringBuilder::new();
+        let mut entity_id = StringBuilder::new();
+        let mut entity_name = StringBuilder::new();
+        let mut period_start = Date32Builder::new();
+        let mut period_end = Date32Builder::new();
+        let mut value = Decimal128Builder::new()
+            .with_precision_and_scale(VALUE_PRECISION, VALUE_SCALE)
+            .map_err(arrow_error)?;
+        let mut text_value = StringBuilder::new();
+        let mut is_nil = BooleanBuilder::new();
+        let mut unit = StringBuilder::new();
+        let mut decimals = Int32Builder::new();
+        let mut precision = Int32Builder::new();
+        let mut dimensions = MapBuilder::new(Some(dimension_field_names()), StringBuilder::new(), StringBuilder::new());
+        let mut filing_url = StringBuilder::new();
+
+        for fact in facts {
+            id.append_value(&fact.id);
+            report_id.append_value(&fact.report_id);
+            concept_name.append_value(&fact.concept_name);
+            concept_label.append_option(fact.concept_label.as_deref());
+            entity_id.append_value(&fact.entity_id);
+            entity_name.append_option(fact.entity_name.as_deref());
+            period_start.append_option(fact.period.start_date().map(Date32Type::from_naive_date));
+            period_end.append_option(fact.period.end_date().map(Date32Type::from_naive_date));
+
+            let number = match &fact.value {
+                FactValue::Decimal(_) | FactValue::Number(_) => fact.value.as_decimal(),
+                FactValue::String(_) if fact.unit.is_some() => fact.value.as_decimal(),
+                _ => None,
+            };
+            value.append_option(number.map(|n| to_decimal128(n, &fact.id)).transpose()?);
+            text_value.append_option(match (&fact.value, number) {
+                (FactValue::Nil, _) | (_, Some(_)) => None,
+                (other, None) => Some(other.to_string()),
+            });
+            is_nil.append_value(fact.value.is_nil());
+
+            unit.append_option(fact.unit.as_ref().map(ToString::to_string));
+            decimals.append_option(match fact.decimals {
+                Some(Decimals::Finite(d)) => Some(d),
+                _ => None,
+            });
+            precision.append_option(match fact.precision {
+                Some(Precision::Finite(p)) => i32::try_from(p).ok(),
+                _ => None,
+            });
+
+            match &fact.dimensions {
+                Some(members) => {
+                    // Sorted so equal facts produce equal rows
+                    let mut members: Vec<_> = members.iter().collect();
+                    members.sort();
+                    for (dimension, member) in members {
+                        dimensions.keys().append_value(dimension);
+                        dimensions.values().append_value(member);
+                    }
+                    dimensions.append(true).map_err(arrow_error)?;
+                }
+                None => dimensions.append(false).map_err(arrow_error)?,
+            }
+            filing_url.append_option(fact.filing_url.as_deref());
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(id.finish()),
+            Arc::new(report_id.finish()),
+            Arc::new(concept_name.finish()),
+            Arc::new(concept_label.finish()),
+            Arc::new(entity_id.finish()),
+            Arc::new(entity_name.finish()),
+            Arc::new(period_start.finish()),
+            Arc::new(period_end.finish()),
+            Arc::new(value.finish()),
+            Arc::new(text_value.finish()),
+            Arc::new(is_nil.finish()),
+            Arc::new(unit.finish()),
+            Arc::new(decimals.finish()),
+            Arc::new(precision.finish()),
+            Arc::new(dimensions.finish()),
+            Arc::new(filing_url.finish()),
+        ];
+        RecordBatch::try_new(Self::schema(), columns).map_err(arrow_error)
+    }
+}
+
+impl ArrowRecord for Report {
+    /// Report schema; `filing_date` is null when it is not a valid date
+    fn schema() -> SchemaRef {
+        REPORT_SCHEMA.clone()
+    }
+
+    fn to_record_batch(reports: &[Self]) -> XbrlResult<RecordBatch> {
+        let mut id = StringBuilder::new();
+        let mut accession_number = StringBuilder::new();
+        let mut filing_date = Date32Builder::new();
+        let mut fiscal_period = StringBuilder::new();
+        let mut fiscal_year = UInt32Builder::new();
+        let mut entity_id = StringBuilder::new();
+        let mut entity_name = StringBuilder::new();
+        let mut cik = StringBuilder::new();
+        let mut taxonomy = StringBuilder::new();
+        let mut report_type = StringBuilder::new();
+        let mut filing_url = StringBuilder::new();
+
+        for report in reports {
+            id.append_value(&report.id);
+            accession_number.append_option(report.accession_number.as_deref());
+            filing_date.append_option(parse_date(&report.filing_date).ok().map(Date32Type::from_naive_date));
+            fiscal_period.append_value(&report.fiscal_period);
+            fiscal_year.append_value(report.fiscal_year);
+            entity_id.append_value(&report.entity_id);
+            entity_name.append_value(&report.entity_name);
+            cik.append_option(report.cik.as_deref());
+            taxonomy.append_value(&report.taxonomy);
+            report_type.append_option(report.report_type.as_deref());
+            filing_url.append_option(report.filing_url.as_deref());
+        }
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(id.finish()),
+            Arc::new(accession_number.finish()),
+            Arc::new(filing_date.finish()),
+            Arc::new(fiscal_period.finish()),
+            Arc::new(fiscal_year.finish()),
+            Arc::new(entity_id.finish()),
+            Arc::new(entity_name.finish()),
+            Arc::new(cik.finish()),
+            Arc::new(taxonomy.finish()),
+            Arc::new(report_type.finish()),
+            Arc::new(filing_url.finish()),
+        ];
+        RecordBatch::try_new(Self::schema(), columns).map_err(arrow_error)
+    }
+}
+
+/// Convert facts to a record batch
+pub fn facts_to_record_batch(facts: &[Fact]) -> XbrlResult<RecordBatch> {
+    Fact::to_record_batch(facts)
+}
+
+/// Convert reports to a record batch
+pub fn reports_to_record_batch(reports: &[Report]) -> XbrlResult<RecordBatch> {
+    Report::to_record_batch(reports)
+}
+
+/// Parquet writer fed one record at a time
+///
+/// Records are converted and written as a row group every `batch_size`
+/// records, so memory use does not grow with the number of records.
+pub struct ParquetStreamWriter<T: ArrowRecord, W: Write + Send> {
+    writer: ArrowWriter<W>,
+    buffer: Vec<T>,
+    batch_size: usize,
+    rows: usize,
+}
+
+impl<T: ArrowRecord, W: Write + Send> ParquetStreamWriter<T, W> {
+    /// Create a Snappy-compressed Parquet writer with [`DEFAULT_BATCH_SIZE`]
+    pub fn new(writer: W) -> XbrlResult<Self> {
+        Self::with_batch_size(writer, DEFAULT_BATCH_SIZE)
+    }
+
+    /// Create a writer with a custom batch and row group size
+    pub fn with_batch_size(writer: W, batch_size: usize) -> XbrlResult<Self> {
+        let batch_size = batch_size.max(1);
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .set_max_row_group_row_count(Some(batch_size))
+            .build();
+        Ok(Self {
+            writer: ArrowWriter::try_new(writer, T::schema(), Some(properties)).map_err(parquet_error)?,
+            buffer: Vec::with_capacity(batch_size),
+            batch_size,
+            rows: 0,
+        })
+    }
+
+    /// Add a record, writing a batch once `batch_size` records are buffered
+    pub fn push(&mut self, record: T) -> XbrlResult<()> {
+        self.buffer.push(record);
+        if self.buffer.len() >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write the buffered records
+    pub fn flush(&mut self) -> XbrlResult<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let batch = T::to_record_batch(&self.buffer)?;
+        self.writer.write(&batch).map_err(parquet_error)?;
+        self.writer.flush().map_err(parquet_error)?;
+        self.rows += self.buffer.len();
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Write the remaining records and the file footer, returning the row count
+    pub fn finish(mut self) -> XbrlResult<usize> {
+        self.flush()?;
+        self.writer.close().map_err(parquet_error)?;
+        Ok(self.rows)
+    }
+}
+
+/// Write a stream of records, such as [`XbrlClient::stream_facts`](crate::XbrlClient::stream_facts),
+/// to Parquet and return the number of rows written
+///
+/// Encoding and writing run on Tokio's blocking thread pool, fed through a
+/// bounded channel, so the calling task never blocks on I/O. The first error
+/// in the stream stops the export and is returned.
+pub async fn write_parquet_stream<T, S, W>(stream: S, writer: W, batch_size: usize) -> XbrlResult<usize>
+where
+    T: ArrowRecord + Send + 'static,
+    S: Stream<Item = XbrlResult<T>>,
+    W: Write + Send + 'static,
+{
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(batch_size.max(1));
+    let encoder = tokio::task::spawn_blocking(move || {
+        let mut parquet = ParquetStreamWriter::with_batch_size(writer, batch_size)?;
+        while let Some(record) = receiver.blocking_recv() {
+            parquet.push(record)?;
+        }
+        parquet.finish()
+    });
+
+    let mut stream = std::pin::pin!(stream);
+    let mut failure = None;
+    while let Some(record) = stream.next().await {
+        match record {
+            Ok(record) => {
+                // A closed channel means the encoder failed; its error is returned below
+                if sender.send(record).await.is_err() {
+                    break;
+                }
+            }
+            Err(error) => {
+                failure = Some(error);
+                break;
+            }
+        }
+    }
+    drop(sender);
+
+    let written = encoder.await.map_err(|e| XbrlApiError::Storage {
+        backend: "Parquet",
+        source: Box::new(e),
+    })?;
+    match failure {
+        Some(error) => Err(error),
+        None => written,
+    }
+}
+
+/// Decimal scaled to [`VALUE_SCALE`], failing when it needs more than [`VALUE_PRECISION`] digits
+fn to_decimal128(value: Decimal, id: &str) -> XbrlResult<i128> {
+    // Scale in i128, since `Decimal` itself cannot hold 28 digits plus the scale
+    let value = value.round_dp(VALUE_SCALE as u32);
+    let factor = 10i128.pow(VALUE_SCALE as u32 - value.scale());
+    value
+        .mantissa()
+        .checked_mul(factor)
+        .filter(|scaled| scaled.unsigned_abs() < 10u128.pow(VALUE_PRECISION as u32))
+        .ok_or_else(|| XbrlApiError::ValidationError(format!("value of fact {} does not fit decimal(38, 10)", id)))
+}
+
+fn arrow_error(error: arrow::error::ArrowError) -> XbrlApiError {
+    XbrlApiError::Storage {
+        backend: "Arrow",
+        source: Box::new(error),
+    }
+}
+
+fn parquet_error(error: parquet::errors::ParquetError) -> XbrlApiError {
+    XbrlApiError::Storage {
+        backend: "Parquet",
+        source: Box::new(error),
+    }
+}
@@ -0,0 +1,170 @@
+use crate::data::facts::{round_to_decimals, Decimals, Fact};
+use crate::data::period::Period;
+use crate::data::taxonomy::{CalculationArc, CalculationNetwork};
+use crate::data::units::Unit;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+/// Summation item whose reported value differs from the sum of its contributing items
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalculationInconsistency {
+    pub role: String,
+    pub parent: String,
+    pub entity_id: String,
+    pub period: Period,
+    pub dimensions: BTreeMap<String, String>,
+    pub unit: Option<Unit>,
+    /// Weighted sum of the contributing items, rounded to the parent's decimals
+    pub expected: Decimal,
+    /// Reported total, rounded to its decimals
+    pub reported: Decimal,
+    /// `reported - expected`
+    pub difference: Decimal,
+    /// Decimals both sides were rounded to
+    pub decimals: Decimals,
+    /// Contributing items that were present in the context
+    pub contributors: Vec<String>,
+}
+
+/// Facts compared against each other: same entity, period, dimensions and unit
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ContextKey<'a> {
+    entity_id: &'a str,
+    period: Period,
+    dimensions: BTreeMap<&'a str, &'a str>,
+    unit: Option<&'a Unit>,
+}
+
+/// Check a report's facts against a calculation network using the XBRL 2.1 rounding rules
+///
+/// Facts are grouped by entity, period, dimensions and unit. A summation item
+/// is only checked in a context where it and at least one contributing item
+/// are reported. Each contributing value is rounded to its own decimals,
+/// weighted and summed; the sum and the reported total are then both rounded
+/// to the total's decimals and compared. Nil facts are ignored, and
+/// relationships touching inconsistent duplicate facts or carrying a
+/// non-finite weight are skipped.
+pub fn check_calculations(network: &CalculationNetwork, facts: &[Fact]) -> Vec<CalculationInconsistency> {
+    let mut contexts: HashMap<ContextKey, HashMap<&str, Vec<&Fact>>> = HashMap::new();
+    for fact in facts {
+        if fact.value.as_decimal().is_some() {
+            contexts
+                .entry(context_key(fact))
+                .or_default()
+                .entry(fact.concept_name.as_str())
+                .or_default()
+                .push(fact);
+        }
+    }
+
+    // Summation items in first-appearance order, and their arcs
+    let mut parents: Vec<&str> = Vec::new();
+    let mut children: HashMap<&str, Vec<&CalculationArc>> = HashMap::new();
+    for arc in &network.arcs {
+        children
+            .entry(arc.parent.as_str())
+            .or_insert_with(|| {
+                parents.push(&arc.parent);
+                Vec::new()
+            })
+            .push(arc);
+    }
+
+    let mut inconsistencies = Vec::new();
+    for (key, concepts) in &contexts {
+        for &parent in &parents {
+            let Some(Resolved::Value(total)) = concepts.get(parent).map(|facts| resolve(facts)) else {
+                continue;
+            };
+
+            let mut sum = Decimal::ZERO;
+            let mut contributors = Vec::new();
+            let mut bound = true;
+            for arc in &children[parent] {
+                match concepts.get(arc.child.as_str()).map(|facts| resolve(facts)) {
+                    None => {}
+                    Some(Resolved::Value(item)) => match Decimal::try_from(arc.weight) {
+                        Ok(weight) => {
+                            sum += item.rounded_value().unwrap_or_default() * weight;
+                            contributors.push(arc.child.clone());
+                        }
+                        Err(_) => {
+                            tracing::warn!(
+                                role = %network.role,
+                                parent,
+                                child = %arc.child,
+                                weight = arc.weight,
+                                "skipping summation with an unusable calculation weight"
+                            );
+                            bound = false;
+                        }
+                    },
+                    Some(Resolved::Inconsistent) => bound = false,
+                }
+            }
+            if !bound || contributors.is_empty() {
+                continue;
+            }
+
+            let decimals = total.inferred_decimals().unwrap_or(Decimals::Infinite);
+            let expected = round_to_decimals(sum, decimals);
+            let reported = round_to_decimals(total.value.as_decimal().unwrap_or_default(), decimals);
+            if expected != reported {
+                inconsistencies.push(CalculationInconsistency {
+                    role: network.role.clone(),
+                    parent: parent.to_string(),
+                    entity_id: key.entity_id.to_string(),
+                    period: key.period,
+                    dimensions: key
+                        .dimensions
+                        .iter()
+                        .map(|(axis, member)| (axis.to_string(), member.to_string()))
+                        .collect(),
+                    unit: key.unit.cloned(),
+                    expected,
+                    reported,
+                    difference: reported - expected,
+                    decimals,
+                    contributors,
+                });
+            }
+        }
+    }
+
+    inconsistencies.sort_by(|a, b| {
+        (&a.parent, a.period.end_date(), &a.dimensions).cmp(&(&b.parent, b.period.end_date(), &b.dimensions))
+    });
+    inconsistencies
+}
+
+enum Resolved<'a> {
+    Value(&'a Fact),
+    Inconsistent,
+}
+
+/// Pick the most accurate of a set of duplicate facts, if they agree
+fn resolve<'a>(facts: &[&'a Fact]) -> Resolved<'a> {
+    let best = facts
+        .iter()
+        .max_by_key(|fact| fact.inferred_decimals().unwrap_or(Decimals::Infinite))
+        .expect("contexts only hold non-empty fact lists");
+    if facts.iter().all(|fact| fact.value_equals(best)) {
+        Resolved::Value(best)
+    } else {
+        Resolved::Inconsistent
+    }
+}
+
+fn context_key(fact: &Fact) -> ContextKey<'_> {
+    ContextKey {
+        entity_id: &fact.entity_id,
+        period: fact.period,
+        dimensions: fact
+            .dimensions
+            .iter()
+            .flatten()
+            .map(|(axis, member)| (axis.as_str(), member.as_str()))
+            .collect(),
+        unit: fact.unit.as_ref(),
+    }
+}
@@ -1,30 +1,71 @@
-use serde::{Deserialize, Serialize};
+use crate::data::period::Period;
+use crate::data::units::Unit;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 /// Fact data structure
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// A string value is read as a number only when the fact has a unit, since
+/// only numeric facts do; text such as `"00123"` is kept as it was written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+// Derived as inherent functions, wrapped by the trait impls below
+#[serde(remote = "Self")]
 pub struct Fact {
     pub id: String,
     pub concept_name: String,
     pub concept_label: Option<String>,
     pub entity_id: String,
     pub entity_name: Option<String>,
-    pub period_start: Option<String>,
-    pub period_end: String,
+    #[serde(flatten)]
+    pub period: Period,
+    #[serde(default)]
     pub value: FactValue,
-    pub unit: Option<String>,
+    pub unit: Option<Unit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<Decimals>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<Precision>,
     pub dimensions: Option<HashMap<String, String>>,
     pub report_id: String,
     pub filing_url: Option<String>,
 }
 
-/// Fact value can be a string, number, or boolean
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(untagged)]
+/// Fact value can be a string, number, boolean, exact decimal or nil
+///
+/// JSON numbers are read from their text as exact `Decimal`s, so
+/// `12345678901234567.89` keeps every digit. `Number` is left for values a
+/// `Decimal` cannot hold, such as `1e40`, and for formats that only hand over
+/// an `f64`. Strings stay `String`s: whether they are numbers depends on the
+/// fact, see [`Fact`].
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum FactValue {
     String(String),
     Number(f64),
     Boolean(bool),
+    Decimal(Decimal),
+    /// Fact reported with `xsi:nil="true"`
+    #[default]
+    Nil,
+}
+
+/// Value of the XBRL `decimals` attribute
+///
+/// `Infinite` sorts above every finite value, so `min` picks the less accurate side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Decimals {
+    Finite(i32),
+    Infinite,
+}
+
+/// Value of the XBRL `precision` attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Precision {
+    Finite(u32),
+    Infinite,
 }
 
 /// Fact context
@@ -32,7 +73,308 @@ pub enum FactValue {
 pub struct FactContext {
     pub entity_id: String,
     pub entity_name: Option<String>,
-    pub period_start: Option<String>,
-    pub period_end: String,
+    #[serde(flatten)]
+    pub period: Period,
     pub dimensions: HashMap<String, String>,
-}
\ No newline at end of file
+}
+
+impl Fact {
+    /// Decimals the value is accurate to, inferred from `precision` when `decimals` is absent
+    pub fn inferred_decimals(&self) -> Option<Decimals> {
+        if let Some(decimals) = self.decimals {
+            return Some(decimals);
+        }
+        match self.precision? {
+            Precision::Infinite => Some(Decimals::Infinite),
+            Precision::Finite(0) => None,
+            Precision::Finite(precision) => {
+                let value = self.value.as_decimal()?;
+                Some(decimals_from_precision(value, precision))
+            }
+        }
+    }
+
+    /// Numeric value rounded to the accuracy it was reported at
+    pub fn rounded_value(&self) -> Option<Decimal> {
+        let value = self.value.as_decimal()?;
+        Some(match self.inferred_decimals() {
+            Some(decimals) => round_to_decimals(value, decimals),
+            None => value,
+        })
+    }
+
+    /// Compare two facts the way XBRL 2.1 compares numeric values (v-equality)
+    ///
+    /// Numeric values are rounded to the lower of the two accuracies before
+    /// being compared; non-numeric values must match exactly.
+    pub fn value_equals(&self, other: &Fact) -> bool {
+        match (self.value.as_decimal(), other.value.as_decimal()) {
+            (Some(left), Some(right)) => {
+                let decimals = match (self.inferred_decimals(), other.inferred_decimals()) {
+                    (Some(a), Some(b)) => a.min(b),
+                    (Some(d), None) | (None, Some(d)) => d,
+                    (None, None) => Decimals::Infinite,
+                };
+                round_to_decimals(left, decimals) == round_to_decimals(right, decimals)
+            }
+            (None, None) => self.value == other.value,
+            _ => false,
+        }
+    }
+}
+
+impl FactValue {
+    /// Exact numeric view of the value, parsing numeric strings
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            FactValue::Decimal(value) => Some(*value),
+            // The shortest round-trip representation is what the JSON actually said
+            FactValue::Number(value) => parse_decimal(&value.to_string()),
+            FactValue::String(value) => parse_decimal(value),
+            FactValue::Boolean(_) | FactValue::Nil => None,
+        }
+    }
+
+    /// Whether the fact was reported as nil
+    pub fn is_nil(&self) -> bool {
+        matches!(self, FactValue::Nil)
+    }
+
+    /// Read a numeric string as an exact `Decimal`, for the value of a numeric fact
+    pub fn into_numeric(self) -> FactValue {
+        match self {
+            FactValue::String(value) => match parse_decimal(&value) {
+                Some(decimal) => FactValue::Decimal(decimal),
+                None => FactValue::String(value),
+            },
+            value => value,
+        }
+    }
+}
+
+impl fmt::Display for FactValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FactValue::String(value) => write!(f, "{}", value),
+            FactValue::Number(value) => write!(f, "{}", value),
+            FactValue::Boolean(value) => write!(f, "{}", value),
+            FactValue::Decimal(value) => write!(f, "{}", value),
+            FactValue::Nil => write!(f, "nil"),
+        }
+    }
+}
+
+/// Round a value to the given number of decimals using round-half-to-even
+///
+/// Negative decimals round to tens, hundreds and so on, as in `decimals="-3"`
+/// for values reported in thousands.
+pub fn round_to_decimals(value: Decimal, decimals: Decimals) -> Decimal {
+    match decimals {
+        Decimals::Infinite => value,
+        Decimals::Finite(places) if places >= 0 => {
+            value.round_dp_with_strategy(places as u32, RoundingStrategy::MidpointNearestEven)
+        }
+        Decimals::Finite(places) => {
+            let mut factor = Decimal::ONE;
+            for _ in 0..places.unsigned_abs() {
+                match factor.checked_mul(Decimal::TEN) {
+                    Some(next) => factor = next,
+                    // Rounding to more digits than a Decimal can hold leaves nothing
+                    None => return Decimal::ZERO,
+                }
+            }
+            (value / factor).round_dp_with_strategy(0, RoundingStrategy::MidpointNearestEven) * factor
+        }
+    }
+}
+
+/// Infer `decimals` from `precision` as described in XBRL 2.1 section 4.6.6
+fn decimals_from_precision(value: Decimal, precision: u32) -> Decimals {
+    if value.is_zero() {
+        return Decimals::Infinite;
+    }
+    // Digits before the decimal point minus one, i.e. floor(log10(|value|))
+    let integer_digits = value.abs().trunc().to_string().trim_start_matches('0').len() as i32;
+    let magnitude = if integer_digits > 0 {
+        integer_digits - 1
+    } else {
+        let fraction = value.abs().fract().to_string();
+        let leading_zeros = fraction[2..].chars().take_while(|c| *c == '0').count() as i32;
+        -(leading_zeros + 1)
+    };
+    Decimals::Finite(precision as i32 - magnitude - 1)
+}
+
+/// Parse a plain decimal literal, rejecting exponents and non-numeric text
+fn parse_decimal(value: &str) -> Option<Decimal> {
+    let value = value.trim();
+    let digits = value.strip_prefix(['-', '+']).unwrap_or(value);
+    let is_numeric = !digits.is_empty()
+        && digits.chars().any(|c| c.is_ascii_digit())
+        && digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && digits.matches('.').count() <= 1;
+    if is_numeric {
+        Decimal::from_str(value).ok()
+    } else {
+        None
+    }
+}
+
+impl Serialize for Fact {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Fact::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fact {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fact = Fact::deserialize(deserializer)?;
+        if fact.unit.is_some() {
+            fact.value = fact.value.into_numeric();
+        }
+        Ok(fact)
+    }
+}
+
+impl Serialize for FactValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            FactValue::String(value) => serializer.serialize_str(value),
+            FactValue::Number(value) => serializer.serialize_f64(*value),
+            FactValue::Boolean(value) => serializer.serialize_bool(*value),
+            // Serialized as a string so no precision is lost on the way out
+            FactValue::Decimal(value) => serializer.serialize_str(&value.to_string()),
+            FactValue::Nil => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FactValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FactValueVisitor;
+
+        impl<'de> Visitor<'de> for FactValueVisitor {
+            type Value = FactValue;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string, number, boolean or null")
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<FactValue, E> {
+                Ok(FactValue::Boolean(value))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<FactValue, E> {
+                Ok(FactValue::Decimal(Decimal::from(value)))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<FactValue, E> {
+                Ok(FactValue::Decimal(Decimal::from(value)))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<FactValue, E> {
+                Ok(FactValue::Number(value))
+            }
+
+            // serde_json's `arbitrary_precision` hands numbers over as a map holding their text
+            fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<FactValue, A::Error> {
+                let number = serde_json::Number::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                let text = number.to_string();
+                match parse_decimal(&text).or_else(|| Decimal::from_scientific(&text).ok()) {
+                    Some(value) => Ok(FactValue::Decimal(value)),
+                    None => text
+                        .parse()
+                        .map(FactValue::Number)
+                        .map_err(|_| de::Error::custom(format!("invalid number {}", text))),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<FactValue, E> {
+                Ok(FactValue::String(value.to_string()))
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<FactValue, E> {
+                Ok(FactValue::Nil)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<FactValue, E> {
+                Ok(FactValue::Nil)
+            }
+        }
+
+        deserializer.deserialize_any(FactValueVisitor)
+    }
+}
+
+/// Accepts an integer, a numeric string or `"INF"`
+fn parse_accuracy<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        // Not `i64`: buffered numbers arrive as maps under `arbitrary_precision`
+        Integer(serde_json::Number),
+        Text(String),
+    }
+
+    match Raw::deserialize(deserializer)? {
+        Raw::Integer(value) => value
+            .as_i64()
+            .map(Some)
+            .ok_or_else(|| de::Error::custom(format!("invalid accuracy attribute: {}", value))),
+        Raw::Text(text) if text.trim().eq_ignore_ascii_case("INF") => Ok(None),
+        Raw::Text(text) => text
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| de::Error::custom(format!("invalid accuracy attribute: {}", text))),
+    }
+}
+
+impl fmt::Display for Decimals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decimals::Finite(value) => write!(f, "{}", value),
+            Decimals::Infinite => write!(f, "INF"),
+        }
+    }
+}
+
+impl Serialize for Decimals {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Decimals::Finite(value) => serializer.serialize_i32(*value),
+            Decimals::Infinite => serializer.serialize_str("INF"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimals {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match parse_accuracy(deserializer)? {
+            None => Ok(Decimals::Infinite),
+            Some(value) => i32::try_from(value)
+                .map(Decimals::Finite)
+                .map_err(|_| de::Error::custom(format!("decimals out of range: {}", value))),
+        }
+    }
+}
+
+impl Serialize for Precision {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Precision::Finite(value) => serializer.serialize_u32(*value),
+            Precision::Infinite => serializer.serialize_str("INF"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Precision {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match parse_accuracy(deserializer)? {
+            None => Ok(Precision::Infinite),
+            Some(value) => u32::try_from(value)
+                .map(Precision::Finite)
+                .map_err(|_| de::Error::custom(format!("precision out of range: {}", value))),
+        }
+    }
+}
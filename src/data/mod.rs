@@ -1,3 +1,7 @@
+pub mod calculation;
 pub mod facts;
+pub mod period;
 pub mod reports;
-pub mod taxonomy;
\ No newline at end of file
+pub mod statements;
+pub mod taxonomy;
+pub mod units;
\ No newline at end of file
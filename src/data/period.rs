@@ -0,0 +1,271 @@
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Days a 52/53-week fiscal year may end after its nominal year-end date
+const FISCAL_YEAR_END_TOLERANCE_DAYS: i64 = 7;
+
+/// Reporting period of a fact
+///
+/// Dates are inclusive calendar dates as reported by the API, so a calendar
+/// year is `Duration { start: 2022-01-01, end: 2022-12-31 }`.
+///
+/// Serialized as the API's flat `period_start` / `period_end` fields: an
+/// instant only has `period_end`, a duration has both and a forever period
+/// has neither (or `period_end` set to `"forever"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Period {
+    Instant(NaiveDate),
+    Duration { start: NaiveDate, end: NaiveDate },
+    Forever,
+}
+
+/// Coarse classification of a period by its length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PeriodLength {
+    Instant,
+    Quarter,
+    HalfYear,
+    NineMonths,
+    Annual,
+    Other,
+    Forever,
+}
+
+/// Month and day on which an entity's fiscal year ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FiscalYearEnd {
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Period {
+    /// Create a duration period, validating that it does not end before it starts
+    pub fn duration(start: NaiveDate, end: NaiveDate) -> XbrlResult<Self> {
+        if end < start {
+            return Err(invalid(format!("period end {} is before period start {}", end, start)));
+        }
+        Ok(Period::Duration { start, end })
+    }
+
+    /// Build a period from the API's raw `period_start` / `period_end` strings
+    pub fn from_raw(start: Option<&str>, end: Option<&str>) -> XbrlResult<Self> {
+        let start = start.map(str::trim).filter(|s| !s.is_empty());
+        let end = end.map(str::trim).filter(|s| !s.is_empty());
+
+        match (start, end) {
+            (_, Some(end)) if end.eq_ignore_ascii_case("forever") => Ok(Period::Forever),
+            (None, None) => Ok(Period::Forever),
+            (None, Some(end)) => Ok(Period::Instant(parse_end_date(end)?)),
+            (Some(start), Some(end)) => Period::duration(parse_date(start)?, parse_end_date(end)?),
+            (Some(start), None) => Err(invalid(format!("period starting {} has no end date", start))),
+        }
+    }
+
+    /// Whether this is an instant period
+    pub fn is_instant(&self) -> bool {
+        matches!(self, Period::Instant(_))
+    }
+
+    /// Whether this is a duration period
+    pub fn is_duration(&self) -> bool {
+        matches!(self, Period::Duration { .. })
+    }
+
+    /// Whether this is a forever period
+    pub fn is_forever(&self) -> bool {
+        matches!(self, Period::Forever)
+    }
+
+    /// First day of a duration period
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        match self {
+            Period::Duration { start, .. } => Some(*start),
+            _ => None,
+        }
+    }
+
+    /// Date of an instant, or last day of a duration
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        match self {
+            Period::Instant(date) => Some(*date),
+            Period::Duration { end, .. } => Some(*end),
+            Period::Forever => None,
+        }
+    }
+
+    /// Length of a duration period in days, counting both the first and last day
+    pub fn duration_days(&self) -> Option<i64> {
+        match self {
+            Period::Duration { start, end } => Some((*end - *start).num_days() + 1),
+            _ => None,
+        }
+    }
+
+    /// Classify the period by its length, allowing for 52/53-week calendars
+    pub fn length(&self) -> PeriodLength {
+        match self {
+            Period::Instant(_) => PeriodLength::Instant,
+            Period::Forever => PeriodLength::Forever,
+            Period::Duration { .. } => match self.duration_days().unwrap_or_default() {
+                80..=100 => PeriodLength::Quarter,
+                170..=195 => PeriodLength::HalfYear,
+                260..=285 => PeriodLength::NineMonths,
+                350..=380 => PeriodLength::Annual,
+                _ => PeriodLength::Other,
+            },
+        }
+    }
+
+    /// Whether this is a (roughly) three-month duration
+    pub fn is_quarter(&self) -> bool {
+        self.length() == PeriodLength::Quarter
+    }
+
+    /// Whether this is a (roughly) twelve-month duration
+    pub fn is_annual(&self) -> bool {
+        self.length() == PeriodLength::Annual
+    }
+
+    /// Fiscal year the period ends in, labelled by the calendar year in which that fiscal year ends
+    pub fn fiscal_year(&self, fiscal_year_end: FiscalYearEnd) -> Option<i32> {
+        self.end_date().map(|date| fiscal_year_end.fiscal_year_of(date))
+    }
+
+    /// Fiscal quarter (1-4) of a quarterly duration
+    pub fn fiscal_quarter(&self, fiscal_year_end: FiscalYearEnd) -> Option<u32> {
+        if !self.is_quarter() {
+            return None;
+        }
+        let end = self.end_date()?;
+        let year_end = fiscal_year_end.date_in(fiscal_year_end.fiscal_year_of(end));
+        let days_to_year_end = (year_end - end).num_days();
+        let quarters_remaining = ((days_to_year_end + 45) / 91).clamp(0, 3) as u32;
+        Some(4 - quarters_remaining)
+    }
+
+    /// Whether the period is a full fiscal year ending on the given year-end
+    pub fn is_fiscal_year(&self, fiscal_year_end: FiscalYearEnd) -> bool {
+        match (self.is_annual(), self.end_date()) {
+            (true, Some(end)) => {
+                let year_end = fiscal_year_end.date_in(fiscal_year_end.fiscal_year_of(end));
+                (year_end - end).num_days().abs() <= FISCAL_YEAR_END_TOLERANCE_DAYS
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Period::Instant(date) => write!(f, "{}", date),
+            Period::Duration { start, end } => write!(f, "{}/{}", start, end),
+            Period::Forever => write!(f, "forever"),
+        }
+    }
+}
+
+impl FiscalYearEnd {
+    /// Create a fiscal year end, validating the month and day
+    pub fn new(month: u32, day: u32) -> XbrlResult<Self> {
+        // 2000 is a leap year, so February 29 is accepted
+        NaiveDate::from_ymd_opt(2000, month, day)
+            .ok_or_else(|| invalid(format!("invalid fiscal year end --{:02}-{:02}", month, day)))?;
+        Ok(Self { month, day })
+    }
+
+    /// Calendar year end (December 31)
+    pub fn calendar() -> Self {
+        Self { month: 12, day: 31 }
+    }
+
+    /// Parse the `--MM-DD` form used by `dei:CurrentFiscalYearEndDate`
+    pub fn parse(value: &str) -> XbrlResult<Self> {
+        let trimmed = value.trim().trim_start_matches("--");
+        let error = || invalid(format!("invalid fiscal year end: {}", value));
+        let (month, day) = trimmed.split_once('-').ok_or_else(error)?;
+        let month = month.parse().map_err(|_| error())?;
+        let day = day.parse().map_err(|_| error())?;
+        Self::new(month, day)
+    }
+
+    /// Nominal year-end date in the given calendar year
+    pub fn date_in(&self, year: i32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, self.month, self.day)
+            // February 29 in a non-leap year falls back to February 28
+            .or_else(|| NaiveDate::from_ymd_opt(year, self.month, self.day - 1))
+            .expect("fiscal year end was validated")
+    }
+
+    /// Fiscal year containing the date, allowing 52/53-week years to end a few days late
+    pub fn fiscal_year_of(&self, date: NaiveDate) -> i32 {
+        let year = date.year();
+        let tolerance = chrono::Duration::days(FISCAL_YEAR_END_TOLERANCE_DAYS);
+        if date <= self.date_in(year - 1) + tolerance {
+            year - 1
+        } else if date <= self.date_in(year) + tolerance {
+            year
+        } else {
+            year + 1
+        }
+    }
+}
+
+/// Parse an XBRL date, accepting a trailing time component
+pub fn parse_date(value: &str) -> XbrlResult<NaiveDate> {
+    let value = value.trim();
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").map(|dt| dt.date()))
+        .or_else(|_| NaiveDate::parse_from_str(value.get(..10).unwrap_or(value), "%Y-%m-%d"))
+        .map_err(|_| invalid(format!("invalid date: {}", value)))
+}
+
+/// Parse the end or instant date of a period
+///
+/// A datetime at midnight is the end of the previous day, so
+/// `2023-01-01T00:00:00` ends on `2022-12-31`.
+pub fn parse_end_date(value: &str) -> XbrlResult<NaiveDate> {
+    let date = parse_date(value)?;
+    let midnight = value
+        .trim()
+        .split_once('T')
+        .is_some_and(|(_, time)| time.starts_with("00:00:00"));
+    if midnight {
+        Ok(date.pred_opt().unwrap_or(date))
+    } else {
+        Ok(date)
+    }
+}
+
+fn invalid(message: String) -> XbrlApiError {
+    XbrlApiError::ValidationError(message)
+}
+
+/// Flat wire representation shared with the API
+#[derive(Serialize, Deserialize)]
+struct RawPeriod {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    period_start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    period_end: Option<String>,
+}
+
+impl Serialize for Period {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let raw = RawPeriod {
+            period_start: self.start_date().map(|d| d.to_string()),
+            period_end: self.end_date().map(|d| d.to_string()),
+        };
+        raw.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Period {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawPeriod::deserialize(deserializer)?;
+        Period::from_raw(raw.period_start.as_deref(), raw.period_end.as_deref())
+            .map_err(serde::de::Error::custom)
+    }
+}
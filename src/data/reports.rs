@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 // use std::collections::HashMap;
 
 /// Report data structure
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Report {
     pub id: String,
     pub accession_number: Option<String>,
@@ -0,0 +1,235 @@
+use crate::data::facts::{Fact, FactValue};
+use crate::data::period::Period;
+use crate::data::taxonomy::{Network, NetworkNode};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+/// Financial statement rebuilt from a presentation network and a report's facts
+#[derive(Debug, Clone)]
+pub struct Statement {
+    pub title: String,
+    pub role: String,
+    /// Periods of the value columns, most recent first
+    pub columns: Vec<Period>,
+    pub rows: Vec<StatementRow>,
+}
+
+/// One line of a statement
+#[derive(Debug, Clone)]
+pub struct StatementRow {
+    pub concept_name: String,
+    pub label: String,
+    /// Indentation level, 0 for top-level lines
+    pub depth: u32,
+    /// Header line without values of its own
+    pub is_abstract: bool,
+    /// Line rendered with a total label
+    pub is_total: bool,
+    /// Values are shown with their sign flipped by a negated label
+    pub is_negated: bool,
+    pub preferred_label: Option<String>,
+    /// One value per column; negated labels are already applied
+    pub values: Vec<Option<FactValue>>,
+}
+
+impl Statement {
+    /// Rebuild the statement described by `network` from a report's facts
+    ///
+    /// Only facts without dimensions are used. Columns are the duration
+    /// periods found for the network's concepts, or the instants when there are
+    /// no durations (as on a balance sheet). Instant facts on a duration
+    /// statement are matched to the column's end date, or its start date for
+    /// period start labels, as on a cash flow statement. Lines with children
+    /// or an `Abstract` concept name are kept as headers; other lines without
+    /// any value are dropped.
+    pub fn build(network: &Network, facts: &[Fact]) -> Self {
+        let nodes = network.nodes.as_deref().unwrap_or_default();
+        let tree = Tree::new(nodes);
+
+        let concepts: HashSet<&str> = tree.concept_names();
+        let mut by_concept: HashMap<&str, Vec<&Fact>> = HashMap::new();
+        for fact in facts {
+            let has_dimensions = fact.dimensions.as_ref().is_some_and(|d| !d.is_empty());
+            if !has_dimensions && concepts.contains(fact.concept_name.as_str()) {
+                by_concept.entry(fact.concept_name.as_str()).or_default().push(fact);
+            }
+        }
+
+        let columns = columns_for(by_concept.values().flatten().copied());
+
+        let mut rows = Vec::new();
+        for (node, depth) in tree.walk() {
+            // Label roles are URIs such as http://www.xbrl.org/2009/role/negatedTotalLabel
+            let label_role = node
+                .preferred_label
+                .as_deref()
+                .and_then(|role| role.rsplit('/').next())
+                .unwrap_or_default();
+            let is_negated = label_role.starts_with("negated");
+            let is_total = matches!(label_role, "totalLabel" | "negatedTotalLabel");
+            let period_start = matches!(label_role, "periodStartLabel" | "negatedPeriodStartLabel");
+
+            let concept_facts = by_concept.get(node.concept_name.as_str());
+            let values: Vec<Option<FactValue>> = columns
+                .iter()
+                .map(|column| {
+                    concept_facts
+                        .and_then(|facts| find_value(facts, column, period_start))
+                        .map(|value| if is_negated { negate(value) } else { value.clone() })
+                })
+                .collect();
+
+            let has_values = values.iter().any(Option::is_some);
+            let is_header = tree.has_children(node) || node.concept_name.ends_with("Abstract");
+            if !has_values && !is_header {
+                continue;
+            }
+
+            rows.push(StatementRow {
+                concept_name: node.concept_name.clone(),
+                label: node.concept_label.clone(),
+                depth: node.level.map(|level| level.saturating_sub(1)).unwrap_or(depth),
+                is_abstract: !has_values,
+                is_total,
+                is_negated,
+                preferred_label: node.preferred_label.clone(),
+                values,
+            });
+        }
+
+        Statement {
+            title: network.short_name.clone().unwrap_or_else(|| network.name.clone()),
+            role: network.role.clone(),
+            columns,
+            rows,
+        }
+    }
+
+    /// Find a row by concept name
+    pub fn row(&self, concept_name: &str) -> Option<&StatementRow> {
+        self.rows.iter().find(|row| row.concept_name == concept_name)
+    }
+}
+
+/// Presentation tree accepting both nested `children` and flat `parent` links
+struct Tree<'a> {
+    roots: Vec<&'a NetworkNode>,
+    flat_children: HashMap<&'a str, Vec<&'a NetworkNode>>,
+}
+
+impl<'a> Tree<'a> {
+    fn new(nodes: &'a [NetworkNode]) -> Self {
+        let mut known = HashSet::new();
+        collect_names(nodes, &mut known);
+
+        let mut roots = Vec::new();
+        let mut flat_children: HashMap<&str, Vec<&NetworkNode>> = HashMap::new();
+        for node in nodes {
+            match node.parent.as_deref() {
+                Some(parent) if known.contains(parent) && parent != node.concept_name => {
+                    flat_children.entry(parent).or_default().push(node)
+                }
+                _ => roots.push(node),
+            }
+        }
+        sort_by_order(&mut roots);
+        flat_children.values_mut().for_each(sort_by_order);
+
+        Self { roots, flat_children }
+    }
+
+    fn children(&self, node: &'a NetworkNode) -> Vec<&'a NetworkNode> {
+        let mut children: Vec<&NetworkNode> = node.children.iter().flatten().collect();
+        if let Some(flat) = self.flat_children.get(node.concept_name.as_str()) {
+            children.extend(flat);
+        }
+        sort_by_order(&mut children);
+        children
+    }
+
+    fn has_children(&self, node: &NetworkNode) -> bool {
+        node.children.as_ref().is_some_and(|c| !c.is_empty())
+            || self.flat_children.contains_key(node.concept_name.as_str())
+    }
+
+    fn concept_names(&self) -> HashSet<&'a str> {
+        self.walk().into_iter().map(|(node, _)| node.concept_name.as_str()).collect()
+    }
+
+    /// Depth-first walk in presentation order, skipping cycles
+    fn walk(&self) -> Vec<(&'a NetworkNode, u32)> {
+        let mut out = Vec::new();
+        let mut path = Vec::new();
+        for root in &self.roots {
+            self.visit(root, 0, &mut path, &mut out);
+        }
+        out
+    }
+
+    fn visit(
+        &self,
+        node: &'a NetworkNode,
+        depth: u32,
+        path: &mut Vec<&'a str>,
+        out: &mut Vec<(&'a NetworkNode, u32)>,
+    ) {
+        if path.contains(&node.concept_name.as_str()) {
+            return;
+        }
+        out.push((node, depth));
+        path.push(&node.concept_name);
+        for child in self.children(node) {
+            self.visit(child, depth + 1, path, out);
+        }
+        path.pop();
+    }
+}
+
+fn collect_names<'a>(nodes: &'a [NetworkNode], names: &mut HashSet<&'a str>) {
+    for node in nodes {
+        names.insert(&node.concept_name);
+        collect_names(node.children.as_deref().unwrap_or_default(), names);
+    }
+}
+
+fn sort_by_order(nodes: &mut Vec<&NetworkNode>) {
+    // Nodes without an order keep their relative position after ordered ones
+    nodes.sort_by(|a, b| {
+        let a = a.order.unwrap_or(f64::INFINITY);
+        let b = b.order.unwrap_or(f64::INFINITY);
+        a.total_cmp(&b)
+    });
+}
+
+fn columns_for<'a>(facts: impl Iterator<Item = &'a Fact>) -> Vec<Period> {
+    let periods: HashSet<Period> = facts.map(|fact| fact.period).collect();
+    let durations: Vec<Period> = periods.iter().copied().filter(Period::is_duration).collect();
+    let mut columns = if durations.is_empty() {
+        periods.into_iter().filter(Period::is_instant).collect()
+    } else {
+        durations
+    };
+    columns.sort_by_key(|period| Reverse((period.end_date(), period.start_date())));
+    columns
+}
+
+fn find_value<'a>(facts: &[&'a Fact], column: &Period, period_start: bool) -> Option<&'a FactValue> {
+    let instant = match column {
+        Period::Duration { start, .. } if period_start => start.pred_opt().map(Period::Instant),
+        Period::Duration { end, .. } => Some(Period::Instant(*end)),
+        _ => None,
+    };
+    facts
+        .iter()
+        .find(|fact| fact.period == *column)
+        .or_else(|| facts.iter().find(|fact| Some(fact.period) == instant))
+        .map(|fact| &fact.value)
+}
+
+fn negate(value: &FactValue) -> FactValue {
+    match value {
+        FactValue::Decimal(value) => FactValue::Decimal(-*value),
+        FactValue::Number(value) => FactValue::Number(-*value),
+        other => other.clone(),
+    }
+}
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 // use std::collections::HashMap;
+use std::collections::HashSet;
 
 /// Taxonomy data structure
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Taxonomy {
     pub name: String,
     pub description: String,
@@ -11,7 +12,7 @@ pub struct Taxonomy {
 }
 
 /// Concept data structure
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Concept {
     pub name: String,
     pub label: String,
@@ -29,21 +30,21 @@ pub struct Concept {
 }
 
 /// Concept reference
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reference {
     pub name: String,
     pub value: String,
 }
 
 /// Concept dimension
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConceptDimension {
     pub dimension_name: String,
     pub members: Vec<String>,
 }
 
 /// Dimension data structure
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dimension {
     pub name: String,
     pub label: String,
@@ -53,7 +54,7 @@ pub struct Dimension {
 }
 
 /// Dimension member
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DimensionMember {
     pub name: String,
     pub label: String,
@@ -61,7 +62,7 @@ pub struct DimensionMember {
 }
 
 /// Network data structure
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Network {
     pub id: String,
     pub name: String,
@@ -73,7 +74,7 @@ pub struct Network {
 }
 
 /// Network node
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkNode {
     pub concept_name: String,
     pub concept_label: String,
@@ -82,4 +83,37 @@ pub struct NetworkNode {
     pub level: Option<u32>,
     pub preferred_label: Option<String>,
     pub children: Option<Vec<NetworkNode>>,
+}
+
+/// Calculation network: summation-item relationships within one role
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalculationNetwork {
+    pub role: String,
+    pub arcs: Vec<CalculationArc>,
+}
+
+/// Calculation relationship: `parent` sums `child` multiplied by `weight`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalculationArc {
+    pub parent: String,
+    pub child: String,
+    pub weight: f64,
+    pub order: Option<f64>,
+}
+
+impl CalculationNetwork {
+    /// Names of the summation items, in first-appearance order
+    pub fn parents(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.arcs
+            .iter()
+            .map(|arc| arc.parent.as_str())
+            .filter(|parent| seen.insert(*parent))
+            .collect()
+    }
+
+    /// Contributing items of a summation item
+    pub fn children_of<'a>(&'a self, parent: &'a str) -> impl Iterator<Item = &'a CalculationArc> + 'a {
+        self.arcs.iter().filter(move |arc| arc.parent == parent)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,270 @@
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Prefix of ISO 4217 currency measures
+pub const ISO4217: &str = "iso4217";
+
+/// Prefix of the measures defined by the XBRL specification (`shares`, `pure`)
+pub const XBRLI: &str = "xbrli";
+
+/// ISO 4217 currency codes, sorted
+///
+/// The active codes plus those withdrawn since 2008, which still appear in
+/// older filings.
+pub const CURRENCY_CODES: [&str; 193] = [
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT",
+    "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BOV", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD",
+    "CAD", "CDF", "CHE", "CHF", "CHW", "CLF", "CLP", "CNY", "COP", "COU", "CRC", "CUC", "CUP",
+    "CVE", "CYP", "CZK", "DJF", "DKK", "DOP", "DZD", "EEK", "EGP", "ERN", "ETB", "EUR", "FJD",
+    "FKP", "GBP", "GEL", "GHS", "GIP", "GMD", "GNF", "GTQ", "GYD", "HKD", "HNL", "HRK", "HTG",
+    "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD", "JOD", "JPY", "KES", "KGS", "KHR",
+    "KMF", "KPW", "KRW", "KWD", "KYD", "KZT", "LAK", "LBP", "LKR", "LRD", "LSL", "LTL", "LVL",
+    "LYD", "MAD", "MDL", "MGA", "MKD", "MMK", "MNT", "MOP", "MRO", "MRU", "MTL", "MUR", "MVR",
+    "MWK", "MXN", "MXV", "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", "NPR", "NZD", "OMR", "PAB",
+    "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB", "RWF", "SAR", "SBD",
+    "SCR", "SDG", "SEK", "SGD", "SHP", "SKK", "SLE", "SLL", "SOS", "SRD", "SSP", "STD", "STN",
+    "SVC", "SYP", "SZL", "THB", "TJS", "TMT", "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH",
+    "UGX", "USD", "USN", "UYI", "UYU", "UYW", "UZS", "VED", "VEF", "VES", "VND", "VUV", "WST",
+    "XAF", "XAG", "XAU", "XBA", "XBB", "XBC", "XBD", "XCD", "XCG", "XDR", "XOF", "XPD", "XPF",
+    "XPT", "XSU", "XTS", "XUA", "XXX", "YER", "ZAR", "ZMK", "ZMW", "ZWG", "ZWL",
+];
+
+/// A single namespace-qualified measure such as `iso4217:USD`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Measure {
+    pub prefix: Option<String>,
+    pub name: String,
+}
+
+/// Unit of a numeric fact
+///
+/// Parsed from the API's string form: `USD`, `iso4217:USD`, `shares`,
+/// `USD/shares`, `iso4217:USD/xbrli:shares` or products like `USD*Y`.
+/// Unprefixed currency codes and the `shares` / `pure` measures are qualified
+/// with their standard prefixes, and measures are kept sorted so units that
+/// only differ in measure order compare equal. Units that cannot be parsed
+/// are deserialized as [`Unit::Unknown`] rather than failing the whole fact.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// Product of one or more measures, such as `iso4217:USD`
+    Measures(Vec<Measure>),
+    /// Ratio of two measure products, such as `iso4217:USD/xbrli:shares`
+    Divide {
+        numerators: Vec<Measure>,
+        denominators: Vec<Measure>,
+    },
+    /// Text of a unit that could not be parsed
+    Unknown(String),
+}
+
+impl Measure {
+    /// Create a measure with an explicit prefix
+    pub fn new(prefix: &str, name: &str) -> Self {
+        Self {
+            prefix: Some(prefix.to_string()),
+            name: name.to_string(),
+        }
+    }
+
+    /// Parse `prefix:name` or a bare name, qualifying well-known bare names
+    pub fn parse(value: &str) -> XbrlResult<Self> {
+        let value = value.trim();
+        let (prefix, name) = match value.split_once(':') {
+            Some((prefix, name)) => (Some(prefix.trim()), name.trim()),
+            None => (None, value),
+        };
+        if name.is_empty() || prefix.is_some_and(str::is_empty) || name.contains(char::is_whitespace) {
+            return Err(XbrlApiError::ValidationError(format!("invalid measure: {}", value)));
+        }
+
+        let prefix = match prefix {
+            Some(prefix) => Some(prefix.to_string()),
+            None if CURRENCY_CODES.binary_search(&name).is_ok() => Some(ISO4217.to_string()),
+            None if name == "shares" || name == "pure" => Some(XBRLI.to_string()),
+            None => None,
+        };
+        Ok(Self {
+            prefix,
+            name: name.to_string(),
+        })
+    }
+
+    /// Whether this is an ISO 4217 currency
+    pub fn is_currency(&self) -> bool {
+        self.prefix.as_deref() == Some(ISO4217)
+    }
+
+    /// Whether this is `xbrli:shares`
+    pub fn is_shares(&self) -> bool {
+        self.prefix.as_deref() == Some(XBRLI) && self.name == "shares"
+    }
+
+    /// Whether this is `xbrli:pure`
+    pub fn is_pure(&self) -> bool {
+        self.prefix.as_deref() == Some(XBRLI) && self.name == "pure"
+    }
+}
+
+impl fmt::Display for Measure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.prefix {
+            Some(prefix) => write!(f, "{}:{}", prefix, self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl Unit {
+    /// Unit made of a single measure
+    pub fn simple(measure: Measure) -> Self {
+        Self::Measures(vec![measure])
+    }
+
+    /// Unit kept as the text it was given, without measures
+    pub fn unknown(text: &str) -> Self {
+        Self::Unknown(text.to_string())
+    }
+
+    /// Parse `text`, keeping it as an unknown unit if it is not valid
+    pub fn parse_lenient(text: &str) -> Self {
+        text.parse().unwrap_or_else(|_| Self::unknown(text))
+    }
+
+    /// Whether the unit could not be parsed
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown(_))
+    }
+
+    /// Measures multiplied in the numerator, or every measure of a product
+    pub fn numerators(&self) -> &[Measure] {
+        match self {
+            Self::Measures(measures) => measures,
+            Self::Divide { numerators, .. } => numerators,
+            Self::Unknown(_) => &[],
+        }
+    }
+
+    /// Measures multiplied in the denominator, empty unless the unit divides
+    pub fn denominators(&self) -> &[Measure] {
+        match self {
+            Self::Divide { denominators, .. } => denominators,
+            _ => &[],
+        }
+    }
+
+    /// Monetary unit for an ISO 4217 currency code
+    pub fn monetary(code: &str) -> Self {
+        Self::simple(Measure::new(ISO4217, code))
+    }
+
+    /// Number of shares
+    pub fn shares() -> Self {
+        Self::simple(Measure::new(XBRLI, "shares"))
+    }
+
+    /// Dimensionless value such as a ratio or percentage
+    pub fn pure() -> Self {
+        Self::simple(Measure::new(XBRLI, "pure"))
+    }
+
+    /// Whether the unit has a denominator
+    pub fn is_divide(&self) -> bool {
+        matches!(self, Self::Divide { .. })
+    }
+
+    /// Whether the unit is a single currency, e.g. `iso4217:USD`
+    pub fn is_monetary(&self) -> bool {
+        matches!(self, Self::Measures(measures) if matches!(measures.as_slice(), [measure] if measure.is_currency()))
+    }
+
+    /// Whether the unit is currency per share, e.g. `iso4217:USD/xbrli:shares`
+    pub fn is_per_share(&self) -> bool {
+        matches!(
+            (self.numerators(), self.denominators()),
+            ([numerator], [denominator]) if numerator.is_currency() && denominator.is_shares()
+        )
+    }
+
+    /// Whether the unit is `xbrli:shares`
+    pub fn is_shares(&self) -> bool {
+        matches!(self, Self::Measures(measures) if matches!(measures.as_slice(), [measure] if measure.is_shares()))
+    }
+
+    /// Whether the unit is `xbrli:pure`
+    pub fn is_pure(&self) -> bool {
+        matches!(self, Self::Measures(measures) if matches!(measures.as_slice(), [measure] if measure.is_pure()))
+    }
+
+    /// Currency code of a monetary or per-share unit
+    pub fn currency(&self) -> Option<&str> {
+        match self.numerators() {
+            [measure] if measure.is_currency() && (self.is_monetary() || self.is_per_share()) => {
+                Some(&measure.name)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = XbrlApiError;
+
+    fn from_str(value: &str) -> XbrlResult<Self> {
+        let (numerator, denominator) = match value.split_once('/') {
+            Some((numerator, denominator)) => (numerator, Some(denominator)),
+            None => (value, None),
+        };
+
+        let parse_product = |product: &str| -> XbrlResult<Vec<Measure>> {
+            let mut measures = product
+                .split('*')
+                .map(Measure::parse)
+                .collect::<XbrlResult<Vec<_>>>()?;
+            measures.sort();
+            Ok(measures)
+        };
+
+        Ok(match denominator {
+            Some(denominator) => Unit::Divide {
+                numerators: parse_product(numerator)?,
+                denominators: parse_product(denominator)?,
+            },
+            None => Unit::Measures(parse_product(numerator)?),
+        })
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |measures: &[Measure]| {
+            measures
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("*")
+        };
+        match self {
+            Unit::Measures(measures) => write!(f, "{}", join(measures)),
+            Unit::Divide {
+                numerators,
+                denominators,
+            } => write!(f, "{}/{}", join(numerators), join(denominators)),
+            Unit::Unknown(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl Serialize for Unit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Unit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(Unit::parse_lenient(&value))
+    }
+}
@@ -6,14 +6,28 @@
 
 // Declare modules that are part of the public API
 pub mod api;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "arrow")]
+pub mod columnar;
 pub mod data;
+pub mod oim;
+pub mod parser;
+#[cfg(feature = "store")]
+pub mod store;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod utils;
 
 // Re-export commonly used types for convenience
 pub use api::client::XbrlClient;
 pub use api::models::SearchParams;
+pub use api::traits::XbrlApi;
 pub use data::facts::Fact;
 pub use data::facts::FactValue;  // Add this line to explicitly re-export FactValue
+pub use data::period::Period;
+pub use rust_decimal::Decimal;
 pub use data::reports::Report;
 pub use data::taxonomy::Taxonomy;
+pub use data::units::Unit;
 pub use utils::errors::{XbrlApiError, XbrlResult};
\ No newline at end of file
@@ -0,0 +1,350 @@
+use super::json::DocumentInfo;
+use super::{fact_dimensions, fact_from_dimensions, format_value, FactIds, Namespaces, OimOptions};
+use crate::data::facts::{Decimals, Fact, FactValue};
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// `documentType` of xBRL-CSV 1.0 metadata files
+pub const XBRL_CSV_DOCUMENT_TYPE: &str = "https://xbrl.org/2021/xbrl-csv";
+
+/// Columns every exported table starts with, in order
+const CORE_COLUMNS: [&str; 6] = ["id", "concept", "entity", "period", "unit", "decimals"];
+
+/// Fact column of every exported table
+const VALUE_COLUMN: &str = "value";
+
+/// Metadata file of an xBRL-CSV report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsvMetadata {
+    pub document_info: DocumentInfo,
+    #[serde(default)]
+    pub table_templates: BTreeMap<String, TableTemplate>,
+    #[serde(default)]
+    pub tables: BTreeMap<String, TableDefinition>,
+}
+
+/// Layout shared by the CSV tables built from it
+///
+/// Dimension values starting with `$` name the column holding the value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableTemplate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_id_column: Option<String>,
+    pub columns: BTreeMap<String, ColumnDefinition>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dimensions: BTreeMap<String, String>,
+}
+
+/// Column of a table template
+///
+/// Columns with `dimensions` hold fact values; the others hold values that
+/// fact columns refer to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColumnDefinition {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<BTreeMap<String, String>>,
+    /// An integer, `"INF"` or a `$column` reference
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<Value>,
+}
+
+/// CSV table of a report and the template it follows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableDefinition {
+    pub url: String,
+    /// Template id, defaulting to the table id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+/// xBRL-CSV report: the metadata and the contents of its tables by URL
+#[derive(Debug, Clone)]
+pub struct XbrlCsvReport {
+    pub metadata: CsvMetadata,
+    pub tables: BTreeMap<String, String>,
+}
+
+impl XbrlCsvReport {
+    /// Build a report from facts
+    ///
+    /// Facts are grouped by the taxonomy dimensions they use, and each group
+    /// gets a template and table with one column per dimension next to the
+    /// `id`, `concept`, `entity`, `period`, `unit`, `decimals` and `value`
+    /// columns. Naming follows [`XbrlJsonDocument::from_facts`](super::json::XbrlJsonDocument::from_facts).
+    pub fn from_facts(facts: &[Fact], options: &OimOptions) -> XbrlResult<Self> {
+        let mut namespaces = Namespaces::new(options);
+        let mut ids = FactIds::default();
+        // Rows by the sorted taxonomy dimensions they carry
+        let mut groups: BTreeMap<Vec<String>, Vec<BTreeMap<String, String>>> = BTreeMap::new();
+
+        for fact in facts {
+            let mut cells = fact_dimensions(fact, &mut namespaces)?;
+            let axes: Vec<String> = cells.keys().filter(|name| name.contains(':')).cloned().collect();
+            for axis in &axes {
+                if let Some(member) = cells.remove(axis) {
+                    cells.insert(column_name(axis), member);
+                }
+            }
+            cells.insert("id".to_string(), ids.unique(&fact.id));
+            if let Some(Decimals::Finite(decimals)) = fact.decimals {
+                cells.insert("decimals".to_string(), decimals.to_string());
+            }
+            cells.insert(VALUE_COLUMN.to_string(), format_csv_value(&fact.value));
+            groups.entry(axes).or_default().push(cells);
+        }
+
+        let mut metadata = CsvMetadata {
+            document_info: DocumentInfo {
+                document_type: XBRL_CSV_DOCUMENT_TYPE.to_string(),
+                namespaces: namespaces.used,
+                taxonomy: options.taxonomy.clone(),
+            },
+            table_templates: BTreeMap::new(),
+            tables: BTreeMap::new(),
+        };
+        let mut tables = BTreeMap::new();
+
+        for (axes, rows) in groups {
+            let name = template_name(&axes);
+            let header: Vec<String> = CORE_COLUMNS
+                .iter()
+                .map(|c| c.to_string())
+                .chain(axes.iter().map(|axis| column_name(axis)))
+                .chain([VALUE_COLUMN.to_string()])
+                .collect();
+
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record(&header).map_err(csv_error)?;
+            for row in &rows {
+                writer
+                    .write_record(header.iter().map(|column| row.get(column).map_or("", String::as_str)))
+                    .map_err(csv_error)?;
+            }
+            let bytes = writer.into_inner().map_err(|e| csv_error(e.into_error()))?;
+            let url = format!("{}.csv", name);
+            tables.insert(url.clone(), String::from_utf8_lossy(&bytes).into_owned());
+
+            metadata.table_templates.insert(name.clone(), template(&axes));
+            metadata.tables.insert(name, TableDefinition { url, template: None });
+        }
+
+        Ok(Self { metadata, tables })
+    }
+
+    /// Parse a metadata file, reading each table through `read_table`
+    pub fn parse(metadata_json: &str, mut read_table: impl FnMut(&str) -> XbrlResult<String>) -> XbrlResult<Self> {
+        let metadata: CsvMetadata = serde_json::from_str(metadata_json)?;
+        if !metadata.document_info.document_type.ends_with("/xbrl-csv") {
+            return Err(XbrlApiError::ParseError(format!(
+                "unsupported documentType {}",
+                metadata.document_info.document_type
+            )));
+        }
+        let mut tables = BTreeMap::new();
+        for table in metadata.tables.values() {
+            tables.insert(table.url.clone(), read_table(&table.url)?);
+        }
+        Ok(Self { metadata, tables })
+    }
+
+    /// Convert to facts of report `report_id`
+    ///
+    /// Fact ids come from the row id column, followed by `.column` when a
+    /// template has more than one fact column. Empty cells hold no fact.
+    pub fn into_facts(self, report_id: &str) -> XbrlResult<Vec<Fact>> {
+        let namespaces = &self.metadata.document_info.namespaces;
+        let mut facts = Vec::new();
+
+        for (table_id, table) in &self.metadata.tables {
+            let template_id = table.template.as_deref().unwrap_or(table_id);
+            let template = self.metadata.table_templates.get(template_id).ok_or_else(|| {
+                XbrlApiError::ParseError(format!("table {} uses unknown template {}", table_id, template_id))
+            })?;
+            let contents = self
+                .tables
+                .get(&table.url)
+                .ok_or_else(|| XbrlApiError::ParseError(format!("table {} has not been read", table.url)))?;
+
+            let mut reader = csv::Reader::from_reader(contents.as_bytes());
+            let header: Vec<String> = reader.headers().map_err(csv_error)?.iter().map(str::to_string).collect();
+            let fact_columns: Vec<(&str, &ColumnDefinition)> = header
+                .iter()
+                .filter_map(|name| Some((name.as_str(), template.columns.get(name)?)))
+                .filter(|(_, column)| column.dimensions.is_some())
+                .collect();
+
+            for (index, record) in reader.records().enumerate() {
+                let record = record.map_err(csv_error)?;
+                let row: BTreeMap<&str, &str> = header.iter().map(String::as_str).zip(record.iter()).collect();
+                let row_id = match &template.row_id_column {
+                    Some(column) => row.get(column.as_str()).copied().unwrap_or_default().to_string(),
+                    None => format!("{}.{}", table_id, index + 1),
+                };
+
+                for (name, column) in &fact_columns {
+                    let cell = row.get(name).copied().unwrap_or_default();
+                    if cell.is_empty() {
+                        continue;
+                    }
+                    let id = if fact_columns.len() == 1 {
+                        row_id.clone()
+                    } else {
+                        format!("{}.{}", row_id, name)
+                    };
+
+                    let mut dimensions = BTreeMap::new();
+                    for (dimension, value) in template.dimensions.iter().chain(column.dimensions.iter().flatten()) {
+                        match resolve(value, &row, &id)? {
+                            Some(value) => dimensions.insert(dimension.clone(), value.to_string()),
+                            None => dimensions.remove(dimension),
+                        };
+                    }
+                    let decimals = match &column.decimals {
+                        Some(Value::String(value)) => resolve(value, &row, &id)?.map(|d| parse_decimals(d, &id)).transpose()?,
+                        Some(Value::Number(value)) => Some(parse_decimals(&value.to_string(), &id)?),
+                        _ => None,
+                    };
+                    facts.push(fact_from_dimensions(
+                        &id,
+                        dimensions,
+                        parse_csv_value(cell).as_deref(),
+                        decimals,
+                        namespaces,
+                        report_id,
+                    )?);
+                }
+            }
+        }
+        Ok(facts)
+    }
+
+    /// Pretty-printed metadata JSON
+    pub fn metadata_json(&self) -> XbrlResult<String> {
+        Ok(serde_json::to_string_pretty(&self.metadata)?)
+    }
+
+    /// Write `<name>.json` and the tables to a directory, returning the metadata path
+    pub fn write_to_directory(&self, directory: impl AsRef<Path>, name: &str) -> XbrlResult<PathBuf> {
+        let directory = directory.as_ref();
+        let write = |path: PathBuf, contents: &str| {
+            std::fs::write(&path, contents).map_err(|source| XbrlApiError::Io { path, source })
+        };
+        for (url, contents) in &self.tables {
+            write(directory.join(url), contents)?;
+        }
+        let metadata_path = directory.join(format!("{}.json", name));
+        write(metadata_path.clone(), &self.metadata_json()?)?;
+        Ok(metadata_path)
+    }
+}
+
+/// Convert facts to an xBRL-CSV report
+pub fn to_xbrl_csv(facts: &[Fact], options: &OimOptions) -> XbrlResult<XbrlCsvReport> {
+    XbrlCsvReport::from_facts(facts, options)
+}
+
+/// Read and parse an xBRL-CSV report from its metadata file
+///
+/// Tables are read relative to the metadata file, whose stem is used as the
+/// report id.
+pub fn parse_xbrl_csv_file(path: impl AsRef<Path>) -> XbrlResult<Vec<Fact>> {
+    let path = path.as_ref();
+    let read = |path: &Path| {
+        std::fs::read_to_string(path)
+            .map_err(|e| XbrlApiError::ParseError(format!("cannot read {}: {}", path.display(), e)))
+    };
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+    let report = XbrlCsvReport::parse(&read(path)?, |url| read(&directory.join(url)))?;
+    let report_id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    report.into_facts(report_id)
+}
+
+/// Template with the core columns, one column per taxonomy dimension and a value column
+fn template(axes: &[String]) -> TableTemplate {
+    let mut columns: BTreeMap<String, ColumnDefinition> =
+        CORE_COLUMNS.iter().map(|c| (c.to_string(), ColumnDefinition::default())).collect();
+    let mut dimensions: BTreeMap<String, String> = ["concept", "entity", "period", "unit"]
+        .iter()
+        .map(|c| (c.to_string(), format!("${}", c)))
+        .collect();
+    for axis in axes {
+        let column = column_name(axis);
+        dimensions.insert(axis.clone(), format!("${}", column));
+        columns.insert(column, ColumnDefinition::default());
+    }
+    columns.insert(
+        VALUE_COLUMN.to_string(),
+        ColumnDefinition {
+            dimensions: Some(BTreeMap::new()),
+            decimals: Some(Value::String("$decimals".to_string())),
+        },
+    );
+    TableTemplate {
+        row_id_column: Some("id".to_string()),
+        columns,
+        dimensions,
+    }
+}
+
+/// Template and table id for facts with the given taxonomy dimensions
+fn template_name(axes: &[String]) -> String {
+    std::iter::once("facts".to_string())
+        .chain(axes.iter().map(|axis| column_name(axis)))
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Column identifiers cannot contain `:`, so `srt:SegmentAxis` becomes `srt_SegmentAxis`
+fn column_name(qname: &str) -> String {
+    qname.replace(':', "_")
+}
+
+/// Value of a `$column` reference, or the literal; `None` for an absent value
+fn resolve<'a>(value: &'a str, row: &BTreeMap<&str, &'a str>, id: &str) -> XbrlResult<Option<&'a str>> {
+    let value = match value.strip_prefix('$') {
+        Some(column) => row
+            .get(column)
+            .copied()
+            .ok_or_else(|| XbrlApiError::ParseError(format!("fact {} refers to missing column {}", id, column)))?,
+        None => value,
+    };
+    Ok((!value.is_empty() && value != "#none").then_some(value))
+}
+
+fn parse_decimals(value: &str, id: &str) -> XbrlResult<Decimals> {
+    match value.trim() {
+        "INF" => Ok(Decimals::Infinite),
+        value => value
+            .parse()
+            .map(Decimals::Finite)
+            .map_err(|_| XbrlApiError::ParseError(format!("fact {} has invalid decimals {:?}", id, value))),
+    }
+}
+
+/// Cell text of a value: `#nil` for nil, `#empty` for an empty string, with a
+/// leading `#` escaped as `##`
+fn format_csv_value(value: &FactValue) -> String {
+    match format_value(value) {
+        None => "#nil".to_string(),
+        Some(text) if text.is_empty() => "#empty".to_string(),
+        Some(text) if text.starts_with('#') => format!("#{}", text),
+        Some(text) => text,
+    }
+}
+
+fn parse_csv_value(cell: &str) -> Option<String> {
+    match cell {
+        "#nil" => None,
+        "#empty" => Some(String::new()),
+        cell => Some(cell.strip_prefix("##").map_or(cell.to_string(), |rest| format!("#{}", rest))),
+    }
+}
+
+fn csv_error(error: impl std::fmt::Display) -> XbrlApiError {
+    XbrlApiError::ParseError(format!("invalid CSV: {}", error))
+}
@@ -0,0 +1,169 @@
+use super::{fact_dimensions, fact_from_dimensions, format_value, FactIds, Namespaces, OimOptions};
+use crate::data::facts::{Decimals, Fact};
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// `documentType` of xBRL-JSON 1.0 documents
+pub const XBRL_JSON_DOCUMENT_TYPE: &str = "https://xbrl.org/2021/xbrl-json";
+
+/// xBRL-JSON document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XbrlJsonDocument {
+    pub document_info: DocumentInfo,
+    /// Facts keyed by id, in document order
+    #[serde(default, with = "ordered_facts")]
+    pub facts: Vec<(String, JsonFact)>,
+}
+
+/// `documentInfo` object of an xBRL-JSON document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentInfo {
+    pub document_type: String,
+    #[serde(default)]
+    pub namespaces: BTreeMap<String, String>,
+    #[serde(default)]
+    pub taxonomy: Vec<String>,
+}
+
+/// Fact of an xBRL-JSON document
+///
+/// `dimensions` holds the core `concept`, `entity`, `period` and `unit`
+/// dimensions next to the taxonomy-defined ones.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonFact {
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<i32>,
+    pub dimensions: BTreeMap<String, String>,
+}
+
+impl XbrlJsonDocument {
+    /// Build a document from facts
+    ///
+    /// Concept, dimension and entity names without a prefix get the prefixes
+    /// set in `options`. Only finite `decimals` are written, since xBRL-JSON
+    /// leaves them out for exact values and has no `precision`. Duplicate fact
+    /// ids get a numeric suffix.
+    pub fn from_facts(facts: &[Fact], options: &OimOptions) -> XbrlResult<Self> {
+        let mut namespaces = Namespaces::new(options);
+        let mut ids = FactIds::default();
+        let mut json_facts = Vec::with_capacity(facts.len());
+
+        for fact in facts {
+            json_facts.push((
+                ids.unique(&fact.id),
+                JsonFact {
+                    value: format_value(&fact.value),
+                    decimals: match fact.decimals {
+                        Some(Decimals::Finite(decimals)) => Some(decimals),
+                        _ => None,
+                    },
+                    dimensions: fact_dimensions(fact, &mut namespaces)?,
+                },
+            ));
+        }
+
+        Ok(Self {
+            document_info: DocumentInfo {
+                document_type: XBRL_JSON_DOCUMENT_TYPE.to_string(),
+                namespaces: namespaces.used,
+                taxonomy: options.taxonomy.clone(),
+            },
+            facts: json_facts,
+        })
+    }
+
+    /// Parse an xBRL-JSON document
+    pub fn parse(json: &str) -> XbrlResult<Self> {
+        let document: Self = serde_json::from_str(json)?;
+        if !document.document_info.document_type.ends_with("/xbrl-json") {
+            return Err(XbrlApiError::ParseError(format!(
+                "unsupported documentType {}",
+                document.document_info.document_type
+            )));
+        }
+        Ok(document)
+    }
+
+    /// Convert to facts of report `report_id`
+    ///
+    /// Concept names lose their prefix and the entity scheme is dropped. Facts
+    /// with a unit get `Decimal` values; all others are kept as strings.
+    pub fn into_facts(self, report_id: &str) -> XbrlResult<Vec<Fact>> {
+        let namespaces = &self.document_info.namespaces;
+        self.facts
+            .into_iter()
+            .map(|(id, fact)| {
+                let decimals = fact.decimals.map(Decimals::Finite);
+                fact_from_dimensions(&id, fact.dimensions, fact.value.as_deref(), decimals, namespaces, report_id)
+            })
+            .collect()
+    }
+
+    /// Pretty-printed JSON text
+    pub fn to_json_string(&self) -> XbrlResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Serialize facts as an xBRL-JSON document
+pub fn to_xbrl_json(facts: &[Fact], options: &OimOptions) -> XbrlResult<String> {
+    XbrlJsonDocument::from_facts(facts, options)?.to_json_string()
+}
+
+/// Parse the facts of an xBRL-JSON document
+pub fn parse_xbrl_json(json: &str, report_id: &str) -> XbrlResult<Vec<Fact>> {
+    XbrlJsonDocument::parse(json)?.into_facts(report_id)
+}
+
+/// Read and parse an xBRL-JSON file, using the file stem as the report id
+pub fn parse_xbrl_json_file(path: impl AsRef<Path>) -> XbrlResult<Vec<Fact>> {
+    let path = path.as_ref();
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| XbrlApiError::ParseError(format!("cannot read {}: {}", path.display(), e)))?;
+    let report_id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    parse_xbrl_json(&json, report_id)
+}
+
+/// The `facts` object, keeping its members in document order
+mod ordered_facts {
+    use super::JsonFact;
+    use serde::de::{MapAccess, Visitor};
+    use serde::ser::SerializeMap;
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(facts: &[(String, JsonFact)], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(facts.len()))?;
+        for (id, fact) in facts {
+            map.serialize_entry(id, fact)?;
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(String, JsonFact)>, D::Error> {
+        struct FactsVisitor;
+
+        impl<'de> Visitor<'de> for FactsVisitor {
+            type Value = Vec<(String, JsonFact)>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an object of facts keyed by id")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut facts = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some(entry) = map.next_entry()? {
+                    facts.push(entry);
+                }
+                Ok(facts)
+            }
+        }
+
+        deserializer.deserialize_map(FactsVisitor)
+    }
+}
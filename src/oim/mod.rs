@@ -0,0 +1,336 @@
+//! XBRL Open Information Model (OIM) formats
+//!
+//! OIM documents carry fully qualified names, so exporting needs a namespace
+//! for every prefix in use. [`OimOptions`] supplies them, along with the
+//! prefix for concept names that have none. Taxonomy namespaces change with
+//! every release, so they are never assumed: bind them with
+//! [`OimOptions::us_gaap`] or [`OimOptions::namespace`].
+
+pub mod csv;
+pub mod json;
+
+use crate::data::facts::{Decimals, Fact, FactValue};
+use crate::data::period::{parse_date, parse_end_date, Period};
+use crate::data::units::Unit;
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use chrono::{Days, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+/// Namespaces bound by default, overridable with [`OimOptions::namespace`]
+///
+/// Only namespaces that do not change between taxonomy releases.
+const DEFAULT_NAMESPACES: [(&str, &str); 3] = [
+    ("xbrli", "http://www.xbrl.org/2003/instance"),
+    ("iso4217", "http://www.xbrl.org/2003/iso4217"),
+    ("cik", "http://www.sec.gov/CIK"),
+];
+
+/// Settings for exporting facts to OIM formats
+#[derive(Debug, Clone)]
+pub struct OimOptions {
+    namespaces: BTreeMap<String, String>,
+    concept_prefix: String,
+    entity_scheme: String,
+    taxonomy: Vec<String>,
+}
+
+impl Default for OimOptions {
+    fn default() -> Self {
+        Self {
+            namespaces: DEFAULT_NAMESPACES
+                .iter()
+                .map(|(prefix, uri)| (prefix.to_string(), uri.to_string()))
+                .collect(),
+            concept_prefix: "us-gaap".to_string(),
+            entity_scheme: "cik".to_string(),
+            taxonomy: Vec::new(),
+        }
+    }
+}
+
+impl OimOptions {
+    /// Options binding `xbrli`, `iso4217` and `cik`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `us-gaap`, `dei` and `srt` to the namespaces of a US GAAP taxonomy release
+    ///
+    /// `year` is the release the report was filed against, such as `2024` for
+    /// `http://fasb.org/us-gaap/2024`. Bind a differing `dei` or `srt` release
+    /// with [`namespace`](Self::namespace) afterwards.
+    pub fn us_gaap(self, year: u16) -> Self {
+        self.namespace("us-gaap", &format!("http://fasb.org/us-gaap/{}", year))
+            .namespace("dei", &format!("http://xbrl.sec.gov/dei/{}", year))
+            .namespace("srt", &format!("http://fasb.org/srt/{}", year))
+    }
+
+    /// Bind a prefix to a namespace URI
+    pub fn namespace(mut self, prefix: &str, uri: &str) -> Self {
+        self.namespaces.insert(prefix.to_string(), uri.to_string());
+        self
+    }
+
+    /// Prefix for concept and dimension names without one (default `us-gaap`)
+    pub fn concept_prefix(mut self, prefix: &str) -> Self {
+        self.concept_prefix = prefix.to_string();
+        self
+    }
+
+    /// Scheme prefix for entity identifiers without one (default `cik`)
+    pub fn entity_scheme(mut self, prefix: &str, uri: &str) -> Self {
+        self.entity_scheme = prefix.to_string();
+        self.namespace(prefix, uri)
+    }
+
+    /// Add a taxonomy entry point URL to the document info
+    pub fn taxonomy(mut self, url: &str) -> Self {
+        self.taxonomy.push(url.to_string());
+        self
+    }
+}
+
+/// Collects the namespaces actually used by an export
+struct Namespaces<'a> {
+    options: &'a OimOptions,
+    used: BTreeMap<String, String>,
+}
+
+impl<'a> Namespaces<'a> {
+    fn new(options: &'a OimOptions) -> Self {
+        Self {
+            options,
+            used: BTreeMap::new(),
+        }
+    }
+
+    /// Mark the prefix of a QName as used, failing when it is not bound
+    fn require(&mut self, qname: &str) -> XbrlResult<()> {
+        let Some((prefix, _)) = qname.split_once(':') else {
+            return Ok(());
+        };
+        let uri = self.options.namespaces.get(prefix).ok_or_else(|| {
+            XbrlApiError::ValidationError(format!(
+                "no namespace bound to prefix {}; add one with OimOptions::namespace or OimOptions::us_gaap",
+                prefix
+            ))
+        })?;
+        self.used.insert(prefix.to_string(), uri.clone());
+        Ok(())
+    }
+
+    /// Mark the prefix of a value as used if it looks like a bound QName
+    fn note(&mut self, value: &str) {
+        if let Some((prefix, _)) = value.split_once(':')
+            && let Some(uri) = self.options.namespaces.get(prefix)
+        {
+            self.used.insert(prefix.to_string(), uri.clone());
+        }
+    }
+
+    fn qualify(&mut self, name: &str) -> XbrlResult<String> {
+        let qname = if name.contains(':') {
+            name.to_string()
+        } else {
+            format!("{}:{}", self.options.concept_prefix, name)
+        };
+        self.require(&qname)?;
+        Ok(qname)
+    }
+
+    fn entity(&mut self, entity_id: &str) -> XbrlResult<String> {
+        let qname = if entity_id.contains(':') {
+            entity_id.to_string()
+        } else {
+            format!("{}:{}", self.options.entity_scheme, entity_id)
+        };
+        self.require(&qname)?;
+        Ok(qname)
+    }
+
+    fn unit(&mut self, unit: &Unit) -> XbrlResult<String> {
+        if let Unit::Unknown(text) = unit {
+            return Err(XbrlApiError::ValidationError(format!("unit {} has no valid measures", text)));
+        }
+        for measure in unit.numerators().iter().chain(unit.denominators()) {
+            self.require(&measure.to_string())?;
+        }
+        Ok(format_unit(unit))
+    }
+}
+
+/// Fact ids made unique by suffixing repeats with `_2`, `_3`, ...
+#[derive(Default)]
+struct FactIds {
+    seen: HashMap<String, usize>,
+}
+
+impl FactIds {
+    fn unique(&mut self, id: &str) -> String {
+        let count = self.seen.entry(id.to_string()).or_default();
+        *count += 1;
+        if *count == 1 {
+            id.to_string()
+        } else {
+            format!("{}_{}", id, count)
+        }
+    }
+}
+
+/// Core and taxonomy-defined OIM dimensions of a fact
+fn fact_dimensions(fact: &Fact, namespaces: &mut Namespaces) -> XbrlResult<BTreeMap<String, String>> {
+    let mut dimensions = BTreeMap::new();
+    dimensions.insert("concept".to_string(), namespaces.qualify(&fact.concept_name)?);
+    if !fact.entity_id.is_empty() {
+        dimensions.insert("entity".to_string(), namespaces.entity(&fact.entity_id)?);
+    }
+    if let Some(period) = format_period(&fact.period) {
+        dimensions.insert("period".to_string(), period);
+    }
+    if let Some(unit) = &fact.unit {
+        dimensions.insert("unit".to_string(), namespaces.unit(unit)?);
+    }
+    for (dimension, member) in fact.dimensions.iter().flatten() {
+        namespaces.note(member);
+        dimensions.insert(namespaces.qualify(dimension)?, member.clone());
+    }
+    Ok(dimensions)
+}
+
+/// Build a fact from its OIM dimensions
+///
+/// Concept names lose their prefix, as they do in the XML parsers, and the
+/// entity scheme is dropped. Facts with a unit get `Decimal` values; all
+/// others are kept as strings.
+fn fact_from_dimensions(
+    id: &str,
+    mut dimensions: BTreeMap<String, String>,
+    value: Option<&str>,
+    decimals: Option<Decimals>,
+    namespaces: &BTreeMap<String, String>,
+    report_id: &str,
+) -> XbrlResult<Fact> {
+    let concept = dimensions
+        .remove("concept")
+        .ok_or_else(|| XbrlApiError::ParseError(format!("fact {} has no concept", id)))?;
+    check_prefix(namespaces, &concept, id)?;
+
+    let mut fact = new_fact(id, &concept, report_id);
+    if let Some(entity) = dimensions.remove("entity") {
+        fact.entity_id = local_name(&entity).to_string();
+    }
+    fact.period = parse_period(dimensions.remove("period").as_deref())?;
+    if let Some(unit) = dimensions.remove("unit") {
+        fact.unit = Some(parse_unit(&unit)?);
+    }
+    fact.value = parse_value(value, fact.unit.is_some(), id)?;
+    fact.decimals = decimals;
+
+    // Core dimensions not modelled by `Fact`, such as `language`, are dropped
+    dimensions.retain(|name, _| name.contains(':'));
+    for dimension in dimensions.keys() {
+        check_prefix(namespaces, dimension, id)?;
+    }
+    if !dimensions.is_empty() {
+        fact.dimensions = Some(dimensions.into_iter().collect());
+    }
+    Ok(fact)
+}
+
+/// OIM unit string: `a*b/c`, with products in parentheses when divided
+fn format_unit(unit: &Unit) -> String {
+    let product = |measures: &[crate::data::units::Measure], grouped: bool| {
+        let joined = measures.iter().map(ToString::to_string).collect::<Vec<_>>().join("*");
+        if grouped && measures.len() > 1 {
+            format!("({})", joined)
+        } else {
+            joined
+        }
+    };
+    if unit.is_divide() {
+        format!("{}/{}", product(unit.numerators(), true), product(unit.denominators(), true))
+    } else {
+        product(unit.numerators(), false)
+    }
+}
+
+fn parse_unit(value: &str) -> XbrlResult<Unit> {
+    value.replace(['(', ')'], "").parse()
+}
+
+/// Fail when a QName of fact `id` uses a prefix the document does not declare
+fn check_prefix(namespaces: &BTreeMap<String, String>, qname: &str, id: &str) -> XbrlResult<()> {
+    match qname.split_once(':') {
+        Some((prefix, _)) if !namespaces.contains_key(prefix) => Err(XbrlApiError::ParseError(format!(
+            "fact {} uses undeclared prefix {}",
+            id, prefix
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Local name of a QName
+fn local_name(qname: &str) -> &str {
+    qname.split_once(':').map_or(qname, |(_, name)| name)
+}
+
+/// OIM period: dateTimes at midnight, with XBRL end dates moved to the next day
+fn format_period(period: &Period) -> Option<String> {
+    let end_of = |date: &NaiveDate| format!("{}T00:00:00", date.checked_add_days(Days::new(1)).unwrap_or(*date));
+    match period {
+        Period::Instant(date) => Some(end_of(date)),
+        Period::Duration { start, end } => Some(format!("{}T00:00:00/{}", start, end_of(end))),
+        Period::Forever => None,
+    }
+}
+
+fn parse_period(value: Option<&str>) -> XbrlResult<Period> {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(Period::Forever);
+    };
+    match value.split_once('/') {
+        Some((start, end)) => Period::duration(parse_date(start)?, parse_end_date(end)?),
+        None => Ok(Period::Instant(parse_end_date(value)?)),
+    }
+}
+
+/// Lexical value of a fact, `None` for nil
+fn format_value(value: &FactValue) -> Option<String> {
+    match value {
+        FactValue::Nil => None,
+        other => Some(other.to_string()),
+    }
+}
+
+/// Facts with a unit are numeric; everything else stays a string
+fn parse_value(value: Option<&str>, numeric: bool, id: &str) -> XbrlResult<FactValue> {
+    match value {
+        None => Ok(FactValue::Nil),
+        Some(text) if numeric => Decimal::from_str(text.trim())
+            .or_else(|_| Decimal::from_scientific(text.trim()))
+            .map(FactValue::Decimal)
+            .map_err(|_| XbrlApiError::ParseError(format!("fact {} has a non-numeric value {:?}", id, text))),
+        Some(text) => Ok(FactValue::String(text.to_string())),
+    }
+}
+
+/// Fact skeleton shared by the importers
+fn new_fact(id: &str, concept: &str, report_id: &str) -> Fact {
+    Fact {
+        id: id.to_string(),
+        concept_name: local_name(concept).to_string(),
+        concept_label: None,
+        entity_id: String::new(),
+        entity_name: None,
+        period: Period::Forever,
+        value: FactValue::Nil,
+        unit: None,
+        decimals: None,
+        precision: None,
+        dimensions: None,
+        report_id: report_id.to_string(),
+        filing_url: None,
+    }
+}
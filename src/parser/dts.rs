@@ -0,0 +1,745 @@
+use super::package::TaxonomyPackage;
+use super::{is, parse_error, text_of, LINK_NS, XBRLI_NS, XLINK_NS};
+use crate::data::taxonomy::{
+    CalculationArc, CalculationNetwork, Concept, ConceptDimension, Dimension, DimensionMember, Network, NetworkNode,
+    Reference, Taxonomy,
+};
+use crate::utils::errors::XbrlResult;
+use roxmltree::{Document, Node};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// XML Schema namespace
+pub const XS_NS: &str = "http://www.w3.org/2001/XMLSchema";
+
+/// XBRL Dimensions taxonomy namespace (for `xbrldt:targetRole`)
+pub const XBRLDT_NS: &str = "http://xbrl.org/2005/xbrldt";
+
+const XML_NS: &str = "http://www.w3.org/XML/1998/namespace";
+
+const STANDARD_LABEL: &str = "http://www.xbrl.org/2003/role/label";
+const DOCUMENTATION_LABEL: &str = "http://www.xbrl.org/2003/role/documentation";
+
+const PARENT_CHILD: &str = "http://www.xbrl.org/2003/arcrole/parent-child";
+const SUMMATION_ITEM: &str = "http://www.xbrl.org/2003/arcrole/summation-item";
+const SUMMATION_ITEM_1_1: &str = "https://xbrl.org/2023/arcrole/summation-item";
+const ALL: &str = "http://xbrl.org/int/dim/arcrole/all";
+const HYPERCUBE_DIMENSION: &str = "http://xbrl.org/int/dim/arcrole/hypercube-dimension";
+const DIMENSION_DOMAIN: &str = "http://xbrl.org/int/dim/arcrole/dimension-domain";
+const DOMAIN_MEMBER: &str = "http://xbrl.org/int/dim/arcrole/domain-member";
+
+/// Discoverable taxonomy set loaded from local files
+#[derive(Debug)]
+pub struct Dts {
+    pub taxonomy: Taxonomy,
+    pub concepts: Vec<Concept>,
+    /// Presentation networks, one per extended link role
+    pub networks: Vec<Network>,
+    pub calculation_networks: Vec<CalculationNetwork>,
+    pub dimensions: Vec<Dimension>,
+    /// Referenced documents that are not in the package, such as remote base taxonomies
+    pub missing: Vec<String>,
+    /// Calculation arcs left out of their network because of an unusable weight
+    pub skipped_arcs: Vec<SkippedArc>,
+    labels: HashMap<(String, String), String>,
+    /// Position of each concept in `concepts`, by name
+    concept_index: HashMap<String, usize>,
+}
+
+/// Calculation arc left out of a [`CalculationNetwork`], and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedArc {
+    pub role: String,
+    pub parent: String,
+    pub child: String,
+    pub reason: String,
+}
+
+impl Dts {
+    /// Concept by name
+    pub fn concept(&self, name: &str) -> Option<&Concept> {
+        // `concepts` is public, so an index gone stale falls back to a scan
+        match self.concept_index.get(name).and_then(|&i| self.concepts.get(i)) {
+            Some(concept) if concept.name == name => Some(concept),
+            _ => self.concepts.iter().find(|c| c.name == name),
+        }
+    }
+
+    /// Presentation network by role URI
+    pub fn network(&self, role: &str) -> Option<&Network> {
+        self.networks.iter().find(|n| n.role == role)
+    }
+
+    /// Calculation network by role URI
+    pub fn calculation_network(&self, role: &str) -> Option<&CalculationNetwork> {
+        self.calculation_networks.iter().find(|n| n.role == role)
+    }
+
+    /// Dimension by name
+    pub fn dimension(&self, name: &str) -> Option<&Dimension> {
+        self.dimensions.iter().find(|d| d.name == name)
+    }
+
+    /// Label of a concept in a label role, such as a presentation arc's preferred label
+    pub fn label(&self, concept_name: &str, role: &str) -> Option<&str> {
+        self.labels
+            .get(&(concept_name.to_string(), role.to_string()))
+            .map(String::as_str)
+    }
+}
+
+/// Load the DTS of a taxonomy package from its entry points
+///
+/// See [`TaxonomyPackage::entry_points`] for how the entry points are chosen.
+pub fn load_dts(package: &TaxonomyPackage) -> XbrlResult<Dts> {
+    load_dts_from(package, &package.entry_points()?)
+}
+
+/// Load the DTS discovered from the given entry points
+///
+/// Entry points are package paths or URLs mapped by the package catalog.
+/// Discovery follows `xs:import`, `xs:include`, `link:linkbaseRef` and locator
+/// hrefs. Targets outside the package are listed in [`Dts::missing`] rather
+/// than failing the load; concepts they define keep the name encoded in the
+/// locator's fragment (`us-gaap_Revenues` becomes `Revenues`).
+pub fn load_dts_from(package: &TaxonomyPackage, entry_points: &[String]) -> XbrlResult<Dts> {
+    if entry_points.is_empty() {
+        return Err(parse_error("taxonomy has no entry point schemas"));
+    }
+
+    let mut loader = Loader::default();
+    for entry_point in entry_points {
+        let path = package
+            .resolve("", entry_point)
+            .ok_or_else(|| parse_error(format!("entry point {} is not in the package", entry_point)))?;
+        if loader.visited.insert(path.clone()) {
+            loader.queue.push_back(path);
+        }
+    }
+    while let Some(path) = loader.queue.pop_front() {
+        let xml = package.read(&path)?;
+        let document = Document::parse(&xml).map_err(|e| parse_error(format!("invalid document {}: {}", path, e)))?;
+        loader.visit(package, &path, document.root_element());
+    }
+
+    let metadata = package.metadata().cloned().unwrap_or_default();
+    let taxonomy = Taxonomy {
+        name: metadata
+            .name
+            .or_else(|| loader.first_prefix.clone())
+            .unwrap_or_else(|| file_stem(&entry_points[0])),
+        description: metadata.description.unwrap_or_default(),
+        version: metadata.version.unwrap_or_default(),
+        documentation_url: metadata.publisher_url,
+    };
+    Ok(loader.finish(taxonomy))
+}
+
+/// Load the DTS of a taxonomy stored as plain files
+pub fn load_taxonomy_directory(path: impl AsRef<Path>) -> XbrlResult<Dts> {
+    load_dts(&TaxonomyPackage::open_directory(path)?)
+}
+
+/// Load the DTS of a taxonomy package ZIP
+pub fn load_taxonomy_package(path: impl AsRef<Path>) -> XbrlResult<Dts> {
+    load_dts(&TaxonomyPackage::open_zip(path)?)
+}
+
+fn file_stem(path: &str) -> String {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.split('.').next().unwrap_or(name).to_string()
+}
+
+/// Element declaration of an item or tuple
+struct RawConcept {
+    name: String,
+    prefix: Option<String>,
+    type_name: String,
+    period_type: Option<String>,
+    balance: Option<String>,
+    is_abstract: bool,
+    is_nillable: bool,
+}
+
+/// Arc between two concepts, keyed by `path#id` until every schema is read
+struct Relationship {
+    role: String,
+    arcrole: String,
+    from: String,
+    to: String,
+    order: Option<f64>,
+    weight: Option<String>,
+    preferred_label: Option<String>,
+    target_role: Option<String>,
+    priority: i32,
+    prohibited: bool,
+}
+
+struct Label {
+    concept: String,
+    role: String,
+    lang: Option<String>,
+    text: String,
+}
+
+#[derive(Default)]
+struct Loader {
+    queue: VecDeque<String>,
+    visited: HashSet<String>,
+    missing: BTreeSet<String>,
+    first_prefix: Option<String>,
+    concepts: Vec<RawConcept>,
+    /// `path#id` of each element declaration to its name
+    concept_ids: HashMap<String, String>,
+    /// Role URI to its `roleType` id and definition
+    roles: HashMap<String, (Option<String>, Option<String>)>,
+    labels: Vec<Label>,
+    references: Vec<(String, Vec<Reference>)>,
+    relationships: Vec<Relationship>,
+}
+
+impl Loader {
+    fn visit(&mut self, package: &TaxonomyPackage, path: &str, root: Node) {
+        if is(root, XS_NS, "schema") {
+            self.read_schema(package, path, root);
+        }
+        for node in root.descendants().filter(Node::is_element) {
+            if is(node, LINK_NS, "linkbaseRef") {
+                if let Some(href) = node.attribute((XLINK_NS, "href")) {
+                    self.follow(package, path, href);
+                }
+            } else if is(node, LINK_NS, "roleType") {
+                if let Some(uri) = node.attribute("roleURI") {
+                    let definition = node
+                        .children()
+                        .find(|n| is(*n, LINK_NS, "definition"))
+                        .map(text_of);
+                    self.roles
+                        .entry(uri.to_string())
+                        .or_insert((node.attribute("id").map(str::to_string), definition));
+                }
+            } else if node.tag_name().namespace() == Some(LINK_NS) && xlink_type(node) == Some("extended") {
+                self.read_link(package, path, node);
+            }
+        }
+    }
+
+    fn read_schema(&mut self, package: &TaxonomyPackage, path: &str, schema: Node) {
+        let prefix = schema
+            .attribute("targetNamespace")
+            .and_then(|namespace| schema.lookup_prefix(namespace))
+            .map(str::to_string);
+        if self.first_prefix.is_none() {
+            self.first_prefix = prefix.clone();
+        }
+
+        for node in schema.children().filter(Node::is_element) {
+            if is(node, XS_NS, "import") || is(node, XS_NS, "include") {
+                if let Some(location) = node.attribute("schemaLocation") {
+                    self.follow(package, path, location);
+                }
+            } else if is(node, XS_NS, "element")
+                && node.has_attribute("substitutionGroup")
+                && let Some(name) = node.attribute("name")
+            {
+                if let Some(id) = node.attribute("id") {
+                    self.concept_ids.insert(format!("{}#{}", path, id), name.to_string());
+                }
+                self.concepts.push(RawConcept {
+                    name: name.to_string(),
+                    prefix: prefix.clone(),
+                    type_name: node.attribute("type").unwrap_or_default().to_string(),
+                    period_type: node.attribute((XBRLI_NS, "periodType")).map(str::to_string),
+                    balance: node.attribute((XBRLI_NS, "balance")).map(str::to_string),
+                    is_abstract: is_true(node.attribute("abstract")),
+                    is_nillable: is_true(node.attribute("nillable")),
+                });
+            }
+        }
+    }
+
+    fn read_link(&mut self, package: &TaxonomyPackage, path: &str, link: Node) {
+        let role = link.attribute((XLINK_NS, "role")).unwrap_or_default().to_string();
+        let mut locators: HashMap<&str, Vec<String>> = HashMap::new();
+        let mut resources: HashMap<&str, Vec<Node>> = HashMap::new();
+        for node in link.children().filter(Node::is_element) {
+            let Some(label) = node.attribute((XLINK_NS, "label")) else {
+                continue;
+            };
+            match xlink_type(node) {
+                Some("locator") => {
+                    if let Some(href) = node.attribute((XLINK_NS, "href")) {
+                        let key = self.locate(package, path, href);
+                        locators.entry(label).or_default().push(key);
+                    }
+                }
+                Some("resource") => resources.entry(label).or_default().push(node),
+                _ => {}
+            }
+        }
+
+        for arc in link.children().filter(|n| xlink_type(*n) == Some("arc")) {
+            let (Some(from), Some(to)) = (arc.attribute((XLINK_NS, "from")), arc.attribute((XLINK_NS, "to"))) else {
+                continue;
+            };
+            let Some(sources) = locators.get(from) else {
+                continue;
+            };
+            let arcrole = arc.attribute((XLINK_NS, "arcrole")).unwrap_or_default();
+
+            if let Some(targets) = resources.get(to) {
+                for source in sources {
+                    for resource in targets {
+                        self.read_resource(source, *resource);
+                    }
+                }
+            } else if let Some(targets) = locators.get(to) {
+                for source in sources {
+                    for target in targets {
+                        self.relationships.push(Relationship {
+                            role: role.clone(),
+                            arcrole: arcrole.to_string(),
+                            from: source.clone(),
+                            to: target.clone(),
+                            order: arc.attribute("order").and_then(|o| o.parse().ok()),
+                            weight: arc.attribute("weight").map(str::to_string),
+                            preferred_label: arc.attribute("preferredLabel").map(str::to_string),
+                            target_role: arc.attribute((XBRLDT_NS, "targetRole")).map(str::to_string),
+                            priority: arc.attribute("priority").and_then(|p| p.parse().ok()).unwrap_or(0),
+                            prohibited: arc.attribute("use") == Some("prohibited"),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn read_resource(&mut self, concept: &str, resource: Node) {
+        if is(resource, LINK_NS, "label") {
+            self.labels.push(Label {
+                concept: concept.to_string(),
+                role: resource
+                    .attribute((XLINK_NS, "role"))
+                    .unwrap_or(STANDARD_LABEL)
+                    .to_string(),
+                lang: resource.attribute((XML_NS, "lang")).map(str::to_string),
+                text: text_of(resource),
+            });
+        } else if is(resource, LINK_NS, "reference") {
+            let parts = resource
+                .children()
+                .filter(Node::is_element)
+                .map(|part| Reference {
+                    name: part.tag_name().name().to_string(),
+                    value: text_of(part),
+                })
+                .collect();
+            self.references.push((concept.to_string(), parts));
+        }
+    }
+
+    /// Queue the document `href` points at, returning its package path
+    fn follow(&mut self, package: &TaxonomyPackage, base: &str, href: &str) -> Option<String> {
+        match package.resolve(base, href) {
+            Some(path) => {
+                if self.visited.insert(path.clone()) {
+                    self.queue.push_back(path.clone());
+                }
+                Some(path)
+            }
+            None => {
+                self.missing.insert(href.split('#').next().unwrap_or(href).to_string());
+                None
+            }
+        }
+    }
+
+    /// Key of the concept a locator points at
+    fn locate(&mut self, package: &TaxonomyPackage, base: &str, href: &str) -> String {
+        let (document, fragment) = href.split_once('#').unwrap_or((href, ""));
+        match self.follow(package, base, href) {
+            Some(path) => format!("{}#{}", path, fragment),
+            None => format!("{}#{}", document, fragment),
+        }
+    }
+
+    fn finish(self, taxonomy: Taxonomy) -> Dts {
+        let name_of = |key: &str| -> String {
+            self.concept_ids.get(key).cloned().unwrap_or_else(|| {
+                let fragment = key.rsplit('#').next().unwrap_or(key);
+                fragment.split_once('_').map_or(fragment, |(_, name)| name).to_string()
+            })
+        };
+
+        let relationships = effective_relationships(self.relationships, &name_of);
+
+        // English labels win over other languages, then the first one read
+        let mut ranked: HashMap<(String, String), (bool, String)> = HashMap::new();
+        for label in &self.labels {
+            let english = label.lang.as_deref().is_some_and(|lang| lang.starts_with("en"));
+            let key = (name_of(&label.concept), label.role.clone());
+            match ranked.get(&key) {
+                Some((true, _)) => {}
+                Some((false, _)) if !english => {}
+                _ => {
+                    ranked.insert(key, (english, label.text.clone()));
+                }
+            }
+        }
+        let labels: HashMap<(String, String), String> = ranked.into_iter().map(|(key, (_, text))| (key, text)).collect();
+        let label_of = |name: &str, role: &str| labels.get(&(name.to_string(), role.to_string())).cloned();
+        let display_label = |name: &str| label_of(name, STANDARD_LABEL).unwrap_or_else(|| name.to_string());
+
+        let mut references: HashMap<String, Vec<Reference>> = HashMap::new();
+        for (key, parts) in self.references {
+            references.entry(name_of(&key)).or_default().extend(parts);
+        }
+
+        let index = index_arcs(&relationships);
+        let (concept_dimensions, dimension_members) = dimensional_relationships(&relationships, &index);
+
+        let concepts: Vec<Concept> = self
+            .concepts
+            .into_iter()
+            .map(|raw| Concept {
+                label: display_label(&raw.name),
+                description: label_of(&raw.name, DOCUMENTATION_LABEL),
+                taxonomy: raw.prefix.unwrap_or_else(|| taxonomy.name.clone()),
+                type_name: raw.type_name,
+                period_type: raw.period_type,
+                balance: raw.balance,
+                standard_label: label_of(&raw.name, STANDARD_LABEL),
+                documentation: label_of(&raw.name, DOCUMENTATION_LABEL),
+                is_abstract: Some(raw.is_abstract),
+                is_nillable: Some(raw.is_nillable),
+                references: references.remove(&raw.name),
+                dimensions: concept_dimensions.get(&raw.name).cloned().map(|dimensions| {
+                    dimensions
+                        .into_iter()
+                        .map(|(dimension_name, members)| ConceptDimension { dimension_name, members })
+                        .collect()
+                }),
+                name: raw.name,
+            })
+            .collect();
+
+        let dimensions = dimension_members
+            .into_iter()
+            .map(|(name, members)| Dimension {
+                label: display_label(&name),
+                description: label_of(&name, DOCUMENTATION_LABEL),
+                taxonomy: taxonomy.name.clone(),
+                members: Some(
+                    members
+                        .into_iter()
+                        .map(|member| DimensionMember {
+                            label: display_label(&member),
+                            description: label_of(&member, DOCUMENTATION_LABEL),
+                            name: member,
+                        })
+                        .collect(),
+                ),
+                name,
+            })
+            .collect();
+
+        let mut networks = Vec::new();
+        for (role, arcs) in arcs_by_role(&relationships, &[PARENT_CHILD]) {
+            let role = role.to_string();
+            let children: HashSet<&str> = arcs.iter().map(|r| r.to.as_str()).collect();
+            let mut seen = HashSet::new();
+            let roots: Vec<&str> = arcs
+                .iter()
+                .map(|arc| arc.from.as_str())
+                .filter(|from| !children.contains(from) && seen.insert(*from))
+                .collect();
+            let nodes = roots
+                .into_iter()
+                .map(|root| NetworkNode {
+                    concept_name: root.to_string(),
+                    concept_label: display_label(root),
+                    parent: None,
+                    order: None,
+                    level: Some(1),
+                    preferred_label: None,
+                    children: presentation_children(
+                        &index,
+                        &role,
+                        root,
+                        2,
+                        &mut vec![root.to_string()],
+                        &labels,
+                        &display_label,
+                    ),
+                })
+                .collect();
+
+            let (id, definition) = self.roles.get(&role).cloned().unwrap_or_default();
+            let short_name = role.rsplit('/').next().map(str::to_string);
+            networks.push(Network {
+                id: id.unwrap_or_else(|| role.clone()),
+                name: definition.or_else(|| short_name.clone()).unwrap_or_else(|| role.clone()),
+                short_name,
+                description: None,
+                taxonomy: taxonomy.name.clone(),
+                role,
+                nodes: Some(nodes),
+            });
+        }
+
+        let mut skipped_arcs = Vec::new();
+        let calculation_networks = arcs_by_role(&relationships, &[SUMMATION_ITEM, SUMMATION_ITEM_1_1])
+            .into_iter()
+            .map(|(role, relationships)| {
+                let mut arcs = Vec::new();
+                for r in relationships {
+                    // The weight is required; guessing one would skew every check using the arc
+                    let weight = match r.weight.as_deref().map(|w| w.trim().parse::<f64>()) {
+                        Some(Ok(weight)) if weight.is_finite() => weight,
+                        Some(_) => {
+                            let reason = format!("invalid weight {:?}", r.weight.as_deref().unwrap_or_default());
+                            skipped_arcs.push(skipped_arc(r, reason));
+                            continue;
+                        }
+                        None => {
+                            skipped_arcs.push(skipped_arc(r, "missing weight".to_string()));
+                            continue;
+                        }
+                    };
+                    arcs.push(CalculationArc {
+                        parent: r.from.clone(),
+                        child: r.to.clone(),
+                        weight,
+                        order: r.order,
+                    });
+                }
+                arcs.sort_by(|a, b| a.order.unwrap_or(0.0).total_cmp(&b.order.unwrap_or(0.0)));
+                CalculationNetwork {
+                    role: role.to_string(),
+                    arcs,
+                }
+            })
+            .collect();
+
+        let concept_index = concepts
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, concept)| (concept.name.clone(), i))
+            .collect();
+        Dts {
+            taxonomy,
+            concepts,
+            concept_index,
+            networks,
+            calculation_networks,
+            dimensions,
+            missing: self.missing.into_iter().collect(),
+            skipped_arcs,
+            labels,
+        }
+    }
+}
+
+fn skipped_arc(relationship: &Relationship, reason: String) -> SkippedArc {
+    SkippedArc {
+        role: relationship.role.clone(),
+        parent: relationship.from.clone(),
+        child: relationship.to.clone(),
+        reason,
+    }
+}
+
+fn xlink_type<'a>(node: Node<'a, '_>) -> Option<&'a str> {
+    node.attribute((XLINK_NS, "type"))
+}
+
+fn is_true(value: Option<&str>) -> bool {
+    matches!(value.map(str::trim), Some("true" | "1"))
+}
+
+/// Apply prohibition and overriding, and swap concept keys for names
+///
+/// Of equivalent arcs only those with the highest priority count, and a
+/// prohibiting arc among them removes the relationship altogether.
+fn effective_relationships(relationships: Vec<Relationship>, name_of: &dyn Fn(&str) -> String) -> Vec<Relationship> {
+    let mut winners: Vec<Relationship> = Vec::new();
+    let mut index: HashMap<(String, String, String, String), usize> = HashMap::new();
+    for mut relationship in relationships {
+        relationship.from = name_of(&relationship.from);
+        relationship.to = name_of(&relationship.to);
+        let key = (
+            relationship.role.clone(),
+            relationship.arcrole.clone(),
+            relationship.from.clone(),
+            relationship.to.clone(),
+        );
+        match index.get(&key) {
+            Some(&i) => {
+                let current = &winners[i];
+                if relationship.priority > current.priority
+                    || (relationship.priority == current.priority && relationship.prohibited)
+                {
+                    winners[i] = relationship;
+                }
+            }
+            None => {
+                index.insert(key, winners.len());
+                winners.push(relationship);
+            }
+        }
+    }
+    winners.retain(|r| !r.prohibited);
+    winners
+}
+
+/// Arcs of the given arcroles grouped by role, roles in first-appearance order
+fn arcs_by_role<'a>(relationships: &'a [Relationship], arcroles: &[&str]) -> Vec<(&'a str, Vec<&'a Relationship>)> {
+    let mut groups: Vec<(&str, Vec<&Relationship>)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for relationship in relationships {
+        if !arcroles.contains(&relationship.arcrole.as_str()) {
+            continue;
+        }
+        let position = *positions.entry(&relationship.role).or_insert_with(|| {
+            groups.push((&relationship.role, Vec::new()));
+            groups.len() - 1
+        });
+        groups[position].1.push(relationship);
+    }
+    groups
+}
+
+/// Arcs keyed by arcrole, role and `from` concept, in document order
+type ArcIndex<'a> = HashMap<(&'a str, &'a str, &'a str), Vec<&'a Relationship>>;
+
+fn index_arcs(relationships: &[Relationship]) -> ArcIndex<'_> {
+    let mut index = ArcIndex::new();
+    for relationship in relationships {
+        index
+            .entry((&relationship.arcrole, &relationship.role, &relationship.from))
+            .or_default()
+            .push(relationship);
+    }
+    index
+}
+
+/// Arcs of `arcrole` in `role` leaving `from`, in document order
+fn arcs_from<'a>(index: &ArcIndex<'a>, arcrole: &str, role: &str, from: &str) -> Vec<&'a Relationship> {
+    index.get(&(arcrole, role, from)).cloned().unwrap_or_default()
+}
+
+fn presentation_children(
+    index: &ArcIndex,
+    role: &str,
+    parent: &str,
+    level: u32,
+    path: &mut Vec<String>,
+    labels: &HashMap<(String, String), String>,
+    display_label: &dyn Fn(&str) -> String,
+) -> Option<Vec<NetworkNode>> {
+    let mut children = arcs_from(index, PARENT_CHILD, role, parent);
+    children.sort_by(|a, b| a.order.unwrap_or(0.0).total_cmp(&b.order.unwrap_or(0.0)));
+
+    let mut nodes = Vec::with_capacity(children.len());
+    for arc in children {
+        // Presentation cycles are invalid; stop rather than recurse forever
+        if path.contains(&arc.to) {
+            continue;
+        }
+        path.push(arc.to.clone());
+        let concept_label = arc
+            .preferred_label
+            .as_ref()
+            .and_then(|role| labels.get(&(arc.to.clone(), role.clone())).cloned())
+            .unwrap_or_else(|| display_label(&arc.to));
+        nodes.push(NetworkNode {
+            concept_name: arc.to.clone(),
+            concept_label,
+            parent: Some(parent.to_string()),
+            order: arc.order,
+            level: Some(level),
+            preferred_label: arc.preferred_label.clone(),
+            children: presentation_children(index, role, &arc.to, level + 1, path, labels, display_label),
+        });
+        path.pop();
+    }
+    (!nodes.is_empty()).then_some(nodes)
+}
+
+type DimensionMap = Vec<(String, Vec<String>)>;
+
+/// Dimensions and members applying to each primary item, and every dimension's members
+///
+/// Primary items are linked to hypercubes by `all` arcs; the hypercube's
+/// dimensions apply to the item and to its domain-member descendants.
+/// `xbrldt:targetRole` moves each step of the chain into another role.
+fn dimensional_relationships(
+    relationships: &[Relationship],
+    index: &ArcIndex,
+) -> (HashMap<String, DimensionMap>, DimensionMap) {
+    let mut by_concept: HashMap<String, DimensionMap> = HashMap::new();
+    let mut all_dimensions: DimensionMap = Vec::new();
+
+    for all in relationships.iter().filter(|r| r.arcrole == ALL) {
+        let mut primary_items = vec![all.from.clone()];
+        primary_items.extend(descendants(index, &all.role, &all.from));
+
+        let cube_role = all.target_role.as_deref().unwrap_or(&all.role);
+        for axis in arcs_from(index, HYPERCUBE_DIMENSION, cube_role, &all.to) {
+            let axis_role = axis.target_role.as_deref().unwrap_or(cube_role);
+            let mut members = Vec::new();
+            for domain in arcs_from(index, DIMENSION_DOMAIN, axis_role, &axis.to) {
+                let domain_role = domain.target_role.as_deref().unwrap_or(axis_role);
+                push_unique(&mut members, domain.to.clone());
+                for member in descendants(index, domain_role, &domain.to) {
+                    push_unique(&mut members, member);
+                }
+            }
+
+            merge(&mut all_dimensions, &axis.to, &members);
+            for item in &primary_items {
+                merge(by_concept.entry(item.clone()).or_default(), &axis.to, &members);
+            }
+        }
+    }
+    (by_concept, all_dimensions)
+}
+
+/// Domain-member descendants, depth first in arc order
+fn descendants(index: &ArcIndex, role: &str, from: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    collect_descendants(index, role, from, &mut found);
+    found.retain(|name| name != from);
+    found
+}
+
+fn collect_descendants(index: &ArcIndex, role: &str, parent: &str, found: &mut Vec<String>) {
+    let mut arcs = arcs_from(index, DOMAIN_MEMBER, role, parent);
+    arcs.sort_by(|a, b| a.order.unwrap_or(0.0).total_cmp(&b.order.unwrap_or(0.0)));
+    for arc in arcs {
+        if !found.contains(&arc.to) {
+            found.push(arc.to.clone());
+            collect_descendants(index, arc.target_role.as_deref().unwrap_or(role), &arc.to, found);
+        }
+    }
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+fn merge(dimensions: &mut DimensionMap, name: &str, members: &[String]) {
+    let index = match dimensions.iter().position(|(existing, _)| existing == name) {
+        Some(index) => index,
+        None => {
+            dimensions.push((name.to_string(), Vec::new()));
+            dimensions.len() - 1
+        }
+    };
+    for member in members {
+        push_unique(&mut dimensions[index].1, member.clone());
+    }
+}
@@ -0,0 +1,442 @@
+use super::{
+    child, in_namespace, is, parse_error, required_attribute, text_of, ISO4217_NS, LINK_NS, XBRLDI_NS, XBRLI_NS,
+    XLINK_NS, XSI_NS,
+};
+use crate::data::facts::{Decimals, Fact, FactContext, FactValue, Precision};
+use crate::data::period::{parse_date, parse_end_date, Period};
+use crate::data::reports::Report;
+use crate::data::units::{Measure, Unit, ISO4217, XBRLI};
+use crate::utils::errors::XbrlResult;
+use roxmltree::{Document, Node, ParsingOptions};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// XML namespace, used for `xml:lang`
+const XML_NS: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// SEC identifier scheme for Central Index Keys
+const CIK_SCHEME: &str = "http://www.sec.gov/CIK";
+
+/// Parsed XBRL 2.1 instance document
+#[derive(Debug)]
+pub struct Instance {
+    /// Report metadata derived from the `dei` cover page facts
+    pub report: Report,
+    /// Contexts by id
+    pub contexts: HashMap<String, FactContext>,
+    /// Units by id
+    pub units: HashMap<String, Unit>,
+    pub facts: Vec<Fact>,
+    pub footnotes: Vec<Footnote>,
+    /// `xlink:href` of each `link:schemaRef`
+    pub schema_refs: Vec<String>,
+}
+
+/// Footnote attached to one or more facts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Footnote {
+    pub id: String,
+    pub role: Option<String>,
+    pub lang: Option<String>,
+    pub text: String,
+    /// Ids of the facts the footnote is linked from
+    pub fact_ids: Vec<String>,
+}
+
+/// Parse an XBRL instance document
+///
+/// Concept names are reported without their prefix (`Assets` for
+/// `us-gaap:Assets`), as the API does. Facts nested in tuples are flattened.
+/// `report_id` becomes `Fact::report_id` and `Report::id` since instances do
+/// not carry an identifier of their own.
+pub fn parse_instance(xml: &str, report_id: &str) -> XbrlResult<Instance> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let document = Document::parse_with_options(xml, options)
+        .map_err(|e| parse_error(format!("invalid instance XML: {}", e)))?;
+    let root = document.root_element();
+    if !is(root, XBRLI_NS, "xbrl") {
+        return Err(parse_error(format!(
+            "expected an xbrli:xbrl root element, found {}",
+            root.tag_name().name()
+        )));
+    }
+
+    let mut contexts = HashMap::new();
+    let mut units = HashMap::new();
+    let mut schema_refs = Vec::new();
+    let mut fact_nodes = Vec::new();
+    let mut footnote_links = Vec::new();
+
+    for node in root.children().filter(Node::is_element) {
+        if is(node, XBRLI_NS, "context") {
+            let id = required_attribute(node, "id")?;
+            contexts.insert(id.to_string(), parse_context(node)?);
+        } else if is(node, XBRLI_NS, "unit") {
+            let id = required_attribute(node, "id")?;
+            units.insert(id.to_string(), parse_unit(node)?);
+        } else if is(node, LINK_NS, "schemaRef") {
+            if let Some(href) = node.attribute((XLINK_NS, "href")) {
+                schema_refs.push(href.to_string());
+            }
+        } else if is(node, LINK_NS, "footnoteLink") {
+            footnote_links.push(node);
+        } else if !in_namespace(node, XBRLI_NS) && !in_namespace(node, LINK_NS) {
+            collect_fact_nodes(node, &mut fact_nodes);
+        }
+    }
+
+    let mut cover = HashMap::new();
+    for node in fact_nodes.iter().filter(|n| is_dei(n.tag_name().namespace())) {
+        cover.entry(node.tag_name().name().to_string()).or_insert_with(|| text_of(*node));
+    }
+    for context in contexts.values_mut() {
+        context.entity_name = cover.get("EntityRegistrantName").cloned();
+    }
+
+    let mut facts = Vec::with_capacity(fact_nodes.len());
+    for (index, node) in fact_nodes.iter().enumerate() {
+        facts.push(parse_fact(*node, index, &contexts, &units, report_id)?);
+    }
+
+    let footnotes = footnote_links.into_iter().flat_map(parse_footnotes).collect();
+    // Any context will do for the entity, they all describe the filer
+    let identifier = root
+        .descendants()
+        .find(|n| is(*n, XBRLI_NS, "identifier"))
+        .map(|n| (n.attribute("scheme").unwrap_or_default(), text_of(n)));
+    let taxonomy = taxonomy_of(fact_nodes.iter().map(|n| {
+        let namespace = n.tag_name().namespace();
+        (namespace, namespace.and_then(|ns| n.lookup_prefix(ns)))
+    }));
+    let report = build_report(report_id, identifier, &cover, taxonomy);
+
+    Ok(Instance {
+        report,
+        contexts,
+        units,
+        facts,
+        footnotes,
+        schema_refs,
+    })
+}
+
+/// Read and parse an instance file, using the file stem as the report id
+pub fn parse_instance_file(path: impl AsRef<Path>) -> XbrlResult<Instance> {
+    let path = path.as_ref();
+    let xml = std::fs::read_to_string(path)
+        .map_err(|e| parse_error(format!("cannot read {}: {}", path.display(), e)))?;
+    let report_id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    parse_instance(&xml, report_id)
+}
+
+pub(super) fn parse_context(node: Node) -> XbrlResult<FactContext> {
+    let id = node.attribute("id").unwrap_or_default();
+    let entity = child(node, XBRLI_NS, "entity")
+        .ok_or_else(|| parse_error(format!("context {} has no entity", id)))?;
+    let entity_id = child(entity, XBRLI_NS, "identifier")
+        .map(text_of)
+        .ok_or_else(|| parse_error(format!("context {} has no entity identifier", id)))?;
+
+    let period = child(node, XBRLI_NS, "period")
+        .ok_or_else(|| parse_error(format!("context {} has no period", id)))?;
+    let date = |name: &str| child(period, XBRLI_NS, name).map(text_of);
+    let period = if child(period, XBRLI_NS, "forever").is_some() {
+        Period::Forever
+    } else if let Some(instant) = date("instant") {
+        Period::Instant(parse_end_date(&instant)?)
+    } else {
+        Period::from_raw(date("startDate").as_deref(), date("endDate").as_deref())?
+    };
+
+    // Dimensions may sit in the segment (usual) or the scenario
+    let mut dimensions = HashMap::new();
+    let containers = [child(entity, XBRLI_NS, "segment"), child(node, XBRLI_NS, "scenario")];
+    for container in containers.into_iter().flatten() {
+        for member in container.children().filter(Node::is_element) {
+            let Some(dimension) = member.attribute("dimension") else {
+                continue;
+            };
+            let value = if is(member, XBRLDI_NS, "typedMember") {
+                member.children().find(Node::is_element).map(text_of).unwrap_or_default()
+            } else if is(member, XBRLDI_NS, "explicitMember") {
+                text_of(member)
+            } else {
+                continue;
+            };
+            dimensions.insert(dimension.to_string(), value);
+        }
+    }
+
+    Ok(FactContext {
+        entity_id,
+        entity_name: None,
+        period,
+        dimensions,
+    })
+}
+
+pub(super) fn parse_unit(node: Node) -> XbrlResult<Unit> {
+    let measures = |parent: Node| -> XbrlResult<Vec<Measure>> {
+        let mut measures = parent
+            .children()
+            .filter(|n| is(*n, XBRLI_NS, "measure"))
+            .map(|n| parse_measure(n, &text_of(n)))
+            .collect::<XbrlResult<Vec<_>>>()?;
+        measures.sort();
+        Ok(measures)
+    };
+
+    match child(node, XBRLI_NS, "divide") {
+        Some(divide) => {
+            let numerator = child(divide, XBRLI_NS, "unitNumerator")
+                .ok_or_else(|| parse_error("divide unit without a numerator"))?;
+            let denominator = child(divide, XBRLI_NS, "unitDenominator")
+                .ok_or_else(|| parse_error("divide unit without a denominator"))?;
+            Ok(Unit::Divide {
+                numerators: measures(numerator)?,
+                denominators: measures(denominator)?,
+            })
+        }
+        None => Ok(Unit::Measures(measures(node)?)),
+    }
+}
+
+/// Resolve a measure QName, mapping the standard namespaces to their usual prefixes
+fn parse_measure(node: Node, qname: &str) -> XbrlResult<Measure> {
+    let (prefix, name) = match qname.split_once(':') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, qname),
+    };
+    let namespace = node.lookup_namespace_uri(prefix);
+    let prefix = match namespace {
+        Some(ISO4217_NS) => Some(ISO4217),
+        Some(XBRLI_NS) => Some(XBRLI),
+        _ => prefix,
+    };
+    match prefix {
+        Some(prefix) => Ok(Measure::new(prefix, name)),
+        None => Measure::parse(name),
+    }
+}
+
+fn collect_fact_nodes<'a, 'input>(node: Node<'a, 'input>, facts: &mut Vec<Node<'a, 'input>>) {
+    if node.attribute("contextRef").is_some() {
+        facts.push(node);
+    } else {
+        // Tuple: its children are facts (or further tuples)
+        for child in node.children().filter(Node::is_element) {
+            collect_fact_nodes(child, facts);
+        }
+    }
+}
+
+fn parse_fact(
+    node: Node,
+    index: usize,
+    contexts: &HashMap<String, FactContext>,
+    units: &HashMap<String, Unit>,
+    report_id: &str,
+) -> XbrlResult<Fact> {
+    let unit_ref = node.attribute("unitRef");
+    let text = text_of(node);
+    let value = if is_nil(node) {
+        FactValue::Nil
+    } else if unit_ref.is_some() {
+        parse_numeric(&text).map(FactValue::Decimal).unwrap_or(FactValue::String(text))
+    } else {
+        FactValue::String(text)
+    };
+
+    let raw = RawFact {
+        id: node.attribute("id").map(str::to_string).unwrap_or_else(|| format!("f{}", index + 1)),
+        concept_name: node.tag_name().name().to_string(),
+        context_ref: required_attribute(node, "contextRef")?,
+        unit_ref,
+        value,
+        decimals: node.attribute("decimals"),
+        precision: node.attribute("precision"),
+    };
+    raw.into_fact(contexts, units, report_id)
+}
+
+/// Fact attributes as read from a document, before contexts and units are resolved
+pub(super) struct RawFact<'a> {
+    pub id: String,
+    pub concept_name: String,
+    pub context_ref: &'a str,
+    pub unit_ref: Option<&'a str>,
+    pub value: FactValue,
+    pub decimals: Option<&'a str>,
+    pub precision: Option<&'a str>,
+}
+
+impl RawFact<'_> {
+    pub(super) fn into_fact(
+        self,
+        contexts: &HashMap<String, FactContext>,
+        units: &HashMap<String, Unit>,
+        report_id: &str,
+    ) -> XbrlResult<Fact> {
+        let concept_name = self.concept_name;
+        let context = contexts.get(self.context_ref).ok_or_else(|| {
+            parse_error(format!("fact {} refers to unknown context {}", concept_name, self.context_ref))
+        })?;
+        let unit = self
+            .unit_ref
+            .map(|id| {
+                units
+                    .get(id)
+                    .cloned()
+                    .ok_or_else(|| parse_error(format!("fact {} refers to unknown unit {}", concept_name, id)))
+            })
+            .transpose()?;
+
+        Ok(Fact {
+            id: self.id,
+            concept_name,
+            concept_label: None,
+            entity_id: context.entity_id.clone(),
+            entity_name: context.entity_name.clone(),
+            period: context.period,
+            value: self.value,
+            unit,
+            decimals: self.decimals.map(parse_decimals).transpose()?,
+            precision: self.precision.map(parse_precision).transpose()?,
+            dimensions: (!context.dimensions.is_empty()).then(|| context.dimensions.clone()),
+            report_id: report_id.to_string(),
+            filing_url: None,
+        })
+    }
+}
+
+/// Whether an element carries `xsi:nil="true"`
+pub(super) fn is_nil(node: Node) -> bool {
+    node.attribute((XSI_NS, "nil")).is_some_and(|v| v.trim() == "true" || v.trim() == "1")
+}
+
+fn parse_footnotes(link: Node) -> Vec<Footnote> {
+    let label = |node: Node| node.attribute((XLINK_NS, "label")).unwrap_or_default().to_string();
+
+    let mut locators: HashMap<String, Vec<String>> = HashMap::new();
+    let mut footnotes: Vec<(String, Footnote)> = Vec::new();
+    for node in link.children().filter(Node::is_element) {
+        if is(node, LINK_NS, "loc") {
+            // Locators point at facts with `#id` fragments
+            if let Some((_, fact_id)) = node.attribute((XLINK_NS, "href")).and_then(|h| h.split_once('#')) {
+                locators.entry(label(node)).or_default().push(fact_id.to_string());
+            }
+        } else if is(node, LINK_NS, "footnote") {
+            footnotes.push((
+                label(node),
+                Footnote {
+                    id: node.attribute("id").map(str::to_string).unwrap_or_else(|| label(node)),
+                    role: node.attribute((XLINK_NS, "role")).map(str::to_string),
+                    lang: node.attribute((XML_NS, "lang")).map(str::to_string),
+                    text: text_of(node),
+                    fact_ids: Vec::new(),
+                },
+            ));
+        }
+    }
+
+    for arc in link.children().filter(|n| is(*n, LINK_NS, "footnoteArc")) {
+        let from = arc.attribute((XLINK_NS, "from")).unwrap_or_default();
+        let to = arc.attribute((XLINK_NS, "to")).unwrap_or_default();
+        let fact_ids = locators.get(from).cloned().unwrap_or_default();
+        for (_, footnote) in footnotes.iter_mut().filter(|(label, _)| label == to) {
+            footnote.fact_ids.extend(fact_ids.iter().cloned());
+        }
+    }
+
+    footnotes.into_iter().map(|(_, footnote)| footnote).collect()
+}
+
+/// Build report metadata from the entity identifier and `dei` cover page values
+pub(super) fn build_report(
+    report_id: &str,
+    identifier: Option<(&str, String)>,
+    cover: &HashMap<String, String>,
+    taxonomy: String,
+) -> Report {
+    let cover = |name: &str| cover.get(name).cloned();
+    let cik = cover("EntityCentralIndexKey").or_else(|| {
+        identifier
+            .as_ref()
+            .filter(|(scheme, _)| *scheme == CIK_SCHEME)
+            .map(|(_, value)| value.clone())
+    });
+
+    let fiscal_year = cover("DocumentFiscalYearFocus")
+        .and_then(|year| year.parse().ok())
+        .or_else(|| {
+            cover("DocumentPeriodEndDate")
+                .and_then(|date| parse_date(&date).ok())
+                .map(|date| chrono::Datelike::year(&date) as u32)
+        })
+        .unwrap_or_default();
+
+    Report {
+        id: report_id.to_string(),
+        accession_number: None,
+        filing_date: String::new(),
+        fiscal_period: cover("DocumentFiscalPeriodFocus").unwrap_or_default(),
+        fiscal_year,
+        entity_id: identifier.map(|(_, value)| value).unwrap_or_default(),
+        entity_name: cover("EntityRegistrantName").unwrap_or_default(),
+        cik,
+        taxonomy,
+        report_type: cover("DocumentType"),
+        filing_url: None,
+    }
+}
+
+/// Whether a namespace belongs to an SEC `dei` taxonomy
+pub(super) fn is_dei(namespace: Option<&str>) -> bool {
+    namespace.is_some_and(|ns| ns.contains("/dei/"))
+}
+
+/// Base taxonomy of the report, judged from the `(namespace, prefix)` of its facts
+pub(super) fn taxonomy_of<'a>(names: impl IntoIterator<Item = (Option<&'a str>, Option<&'a str>)>) -> String {
+    let names: Vec<_> = names.into_iter().collect();
+    let has = |needle: &str| names.iter().any(|(ns, _)| ns.is_some_and(|ns| ns.contains(needle)));
+    if has("fasb.org/us-gaap") {
+        "us-gaap".to_string()
+    } else if has("xbrl.ifrs.org") {
+        "ifrs-full".to_string()
+    } else {
+        names.first().and_then(|(_, prefix)| *prefix).unwrap_or_default().to_string()
+    }
+}
+
+/// Parse an `xs:decimal` or `xs:double` lexical value
+pub(super) fn parse_numeric(text: &str) -> Option<Decimal> {
+    let text = text.trim();
+    Decimal::from_str(text)
+        .or_else(|_| Decimal::from_scientific(text))
+        .ok()
+}
+
+pub(super) fn parse_decimals(value: &str) -> XbrlResult<Decimals> {
+    match value.trim() {
+        "INF" => Ok(Decimals::Infinite),
+        value => value
+            .parse()
+            .map(Decimals::Finite)
+            .map_err(|_| parse_error(format!("invalid decimals attribute: {}", value))),
+    }
+}
+
+pub(super) fn parse_precision(value: &str) -> XbrlResult<Precision> {
+    match value.trim() {
+        "INF" => Ok(Precision::Infinite),
+        value => value
+            .parse()
+            .map(Precision::Finite)
+            .map_err(|_| parse_error(format!("invalid precision attribute: {}", value))),
+    }
+}
@@ -0,0 +1,303 @@
+use super::instance::{build_report, is_dei, is_nil, parse_context, parse_numeric, parse_unit, taxonomy_of, RawFact};
+use super::transform::{apply_format, is_supported_format};
+use super::{is, parse_error, required_attribute, text_of, LINK_NS, XBRLI_NS, XLINK_NS};
+use crate::data::facts::{Fact, FactContext, FactValue};
+use crate::data::reports::Report;
+use crate::data::units::Unit;
+use crate::utils::errors::XbrlResult;
+use roxmltree::{Document, Node, ParsingOptions};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Inline XBRL 1.1 namespace
+pub const IX_NS: &str = "http://www.xbrl.org/2013/inlineXBRL";
+
+/// Inline XBRL 1.0 namespace
+pub const IX_1_0_NS: &str = "http://www.xbrl.org/2008/inlineXBRL";
+
+/// Facts and metadata extracted from an Inline XBRL document
+#[derive(Debug)]
+pub struct InlineDocument {
+    pub report: Report,
+    pub contexts: HashMap<String, FactContext>,
+    pub units: HashMap<String, Unit>,
+    pub facts: Vec<InlineFact>,
+    pub schema_refs: Vec<String>,
+    /// Facts left out because they could not be read
+    pub skipped: Vec<SkippedFact>,
+}
+
+/// Fact together with where it was tagged in the HTML
+#[derive(Debug)]
+pub struct InlineFact {
+    pub fact: Fact,
+    pub location: SourceLocation,
+}
+
+/// Fact left out of an [`InlineDocument`], and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFact {
+    /// `id` attribute, or the id the fact would have been given
+    pub id: String,
+    /// Concept name as tagged, with its prefix
+    pub name: String,
+    pub location: SourceLocation,
+    pub reason: String,
+}
+
+/// Position of a tagging element in the source document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// 1-based line of the opening tag
+    pub line: u32,
+    /// 1-based column of the opening tag
+    pub column: u32,
+    /// Byte offset of the opening tag
+    pub offset: usize,
+    /// Whether the fact sits in `ix:hidden` and is not displayed
+    pub hidden: bool,
+}
+
+impl InlineDocument {
+    /// Drop the source locations and keep the facts
+    pub fn into_facts(self) -> Vec<Fact> {
+        self.facts.into_iter().map(|inline| inline.fact).collect()
+    }
+}
+
+/// Extract the facts of an Inline XBRL (XHTML) document
+///
+/// `ix:nonFraction` values go through their `format` transformation and are
+/// then scaled by `scale` and negated by `sign="-"`. `ix:nonNumeric` text
+/// skips `ix:exclude` content, follows `continuedAt` chains through
+/// `ix:continuation` elements and has its whitespace collapsed. As with
+/// [`parse_instance`], concept names lose their prefix and `report_id`
+/// identifies the report. Facts using a transformation outside the registry,
+/// or whose value cannot be read (invalid numbers, bad `scale`, broken
+/// continuations), are left out and listed in [`InlineDocument::skipped`].
+///
+/// [`parse_instance`]: super::instance::parse_instance
+pub fn parse_inline(html: &str, report_id: &str) -> XbrlResult<InlineDocument> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let document = Document::parse_with_options(html, options)
+        .map_err(|e| parse_error(format!("invalid Inline XBRL document: {}", e)))?;
+
+    let mut contexts = HashMap::new();
+    let mut units = HashMap::new();
+    let mut schema_refs = Vec::new();
+    let mut continuations = HashMap::new();
+    let mut fact_nodes = Vec::new();
+
+    for node in document.descendants().filter(Node::is_element) {
+        if is(node, XBRLI_NS, "context") {
+            contexts.insert(required_attribute(node, "id")?.to_string(), parse_context(node)?);
+        } else if is(node, XBRLI_NS, "unit") {
+            units.insert(required_attribute(node, "id")?.to_string(), parse_unit(node)?);
+        } else if is(node, LINK_NS, "schemaRef") {
+            if let Some(href) = node.attribute((XLINK_NS, "href")) {
+                schema_refs.push(href.to_string());
+            }
+        } else if is_ix(node, "continuation") {
+            continuations.insert(required_attribute(node, "id")?, node);
+        } else if is_ix(node, "nonFraction") || is_ix(node, "nonNumeric") {
+            // Nested nonFractions are facts in their own right
+            fact_nodes.push(node);
+        }
+    }
+    if fact_nodes.is_empty() && contexts.is_empty() {
+        return Err(parse_error("document contains no Inline XBRL markup"));
+    }
+
+    let mut raw_facts = Vec::with_capacity(fact_nodes.len());
+    let mut skipped = Vec::new();
+    let mut cover = HashMap::new();
+    for (index, node) in fact_nodes.iter().enumerate() {
+        let name = required_attribute(*node, "name")?;
+        let id = node.attribute("id").map(str::to_string).unwrap_or_else(|| format!("f{}", index + 1));
+        if let Some(format) = node.attribute("format").filter(|format| !is_supported_format(format)) {
+            skipped.push(SkippedFact {
+                id,
+                name: name.to_string(),
+                location: location(&document, *node),
+                reason: format!("unsupported transformation {}", format),
+            });
+            continue;
+        }
+        let (prefix, local_name) = match name.split_once(':') {
+            Some((prefix, local_name)) => (Some(prefix), local_name),
+            None => (None, name),
+        };
+        let namespace = node.lookup_namespace_uri(prefix);
+
+        let value = if is_ix(*node, "nonFraction") {
+            non_fraction_value(*node)
+        } else {
+            non_numeric_value(*node, &continuations)
+        };
+        let value = match value {
+            Ok(value) => value,
+            Err(error) => {
+                skipped.push(SkippedFact {
+                    id,
+                    name: name.to_string(),
+                    location: location(&document, *node),
+                    reason: error.to_string(),
+                });
+                continue;
+            }
+        };
+        if is_dei(namespace)
+            && let FactValue::String(text) = &value
+        {
+            cover.entry(local_name.to_string()).or_insert_with(|| text.clone());
+        }
+
+        raw_facts.push((
+            *node,
+            RawFact {
+                id,
+                concept_name: local_name.to_string(),
+                context_ref: required_attribute(*node, "contextRef")?,
+                unit_ref: node.attribute("unitRef"),
+                value,
+                decimals: node.attribute("decimals"),
+                precision: node.attribute("precision"),
+            },
+            (namespace, prefix),
+        ));
+    }
+
+    for context in contexts.values_mut() {
+        context.entity_name = cover.get("EntityRegistrantName").cloned();
+    }
+
+    let taxonomy = taxonomy_of(raw_facts.iter().map(|(_, _, name)| *name));
+    let mut facts = Vec::with_capacity(raw_facts.len());
+    for (node, raw, _) in raw_facts {
+        facts.push(InlineFact {
+            fact: raw.into_fact(&contexts, &units, report_id)?,
+            location: location(&document, node),
+        });
+    }
+
+    let identifier = document
+        .descendants()
+        .find(|n| is(*n, XBRLI_NS, "identifier"))
+        .map(|n| (n.attribute("scheme").unwrap_or_default(), text_of(n)));
+    let report = build_report(report_id, identifier, &cover, taxonomy);
+
+    Ok(InlineDocument {
+        report,
+        contexts,
+        units,
+        facts,
+        schema_refs,
+        skipped,
+    })
+}
+
+/// Read and parse an Inline XBRL file, using the file stem as the report id
+pub fn parse_inline_file(path: impl AsRef<Path>) -> XbrlResult<InlineDocument> {
+    let path = path.as_ref();
+    let html = std::fs::read_to_string(path)
+        .map_err(|e| parse_error(format!("cannot read {}: {}", path.display(), e)))?;
+    let report_id = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+    parse_inline(&html, report_id)
+}
+
+fn location(document: &Document, node: Node) -> SourceLocation {
+    let position = document.text_pos_at(node.range().start);
+    SourceLocation {
+        line: position.row,
+        column: position.col,
+        offset: node.range().start,
+        hidden: node.ancestors().any(|a| is_ix(a, "hidden")),
+    }
+}
+
+fn is_ix(node: Node, name: &str) -> bool {
+    is(node, IX_NS, name) || is(node, IX_1_0_NS, name)
+}
+
+fn non_fraction_value(node: Node) -> XbrlResult<FactValue> {
+    if is_nil(node) {
+        return Ok(FactValue::Nil);
+    }
+    let name = node.attribute("name").unwrap_or_default();
+    let text = text_of(node);
+    let text = match node.attribute("format") {
+        Some(format) => apply_format(format, &text)?,
+        None => text,
+    };
+    let mut value = parse_numeric(&text)
+        .ok_or_else(|| parse_error(format!("fact {} has a non-numeric value {:?}", name, text)))?;
+
+    if let Some(scale) = node.attribute("scale") {
+        let scale: i32 = scale
+            .trim()
+            .parse()
+            .map_err(|_| parse_error(format!("fact {} has an invalid scale {}", name, scale)))?;
+        value = apply_scale(value, scale)
+            .ok_or_else(|| parse_error(format!("fact {} overflows when scaled by {}", name, scale)))?;
+    }
+    if node.attribute("sign") == Some("-") {
+        value = -value;
+    }
+    Ok(FactValue::Decimal(value))
+}
+
+fn non_numeric_value(node: Node, continuations: &HashMap<&str, Node>) -> XbrlResult<FactValue> {
+    if is_nil(node) {
+        return Ok(FactValue::Nil);
+    }
+
+    let mut text = String::new();
+    displayed_text(node, &mut text);
+    let mut seen = HashSet::new();
+    let mut next = node.attribute("continuedAt");
+    while let Some(id) = next {
+        if !seen.insert(id) {
+            return Err(parse_error(format!("continuation cycle at {}", id)));
+        }
+        let continuation = continuations
+            .get(id)
+            .ok_or_else(|| parse_error(format!("missing ix:continuation {}", id)))?;
+        displayed_text(*continuation, &mut text);
+        next = continuation.attribute("continuedAt");
+    }
+
+    // Whitespace is not significant in the rendered HTML
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    Ok(FactValue::String(match node.attribute("format") {
+        Some(format) => apply_format(format, &text)?,
+        None => text,
+    }))
+}
+
+/// Text content, leaving out `ix:exclude` subtrees
+fn displayed_text(node: Node, out: &mut String) {
+    for child in node.children() {
+        if child.is_text() {
+            out.push_str(child.text().unwrap_or_default());
+        } else if child.is_element() && !is_ix(child, "exclude") {
+            displayed_text(child, out);
+        }
+    }
+}
+
+/// Multiply by `10^scale`
+fn apply_scale(value: Decimal, scale: i32) -> Option<Decimal> {
+    let mut value = value;
+    for _ in 0..scale.unsigned_abs() {
+        value = if scale > 0 {
+            value.checked_mul(Decimal::TEN)?
+        } else {
+            value.checked_div(Decimal::TEN)?
+        };
+    }
+    Some(value.normalize())
+}
@@ -0,0 +1,64 @@
+//! Offline parsers for XBRL documents
+//!
+//! These produce the same data types as the REST API so downstream code does
+//! not need to care where the data came from.
+
+pub mod dts;
+pub mod instance;
+pub mod ixbrl;
+pub mod offline;
+pub mod package;
+pub mod transform;
+
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use roxmltree::Node;
+
+/// XBRL 2.1 instance namespace
+pub const XBRLI_NS: &str = "http://www.xbrl.org/2003/instance";
+
+/// XBRL 2.1 linkbase namespace
+pub const LINK_NS: &str = "http://www.xbrl.org/2003/linkbase";
+
+/// XLink namespace
+pub const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+
+/// XBRL Dimensions instance namespace
+pub const XBRLDI_NS: &str = "http://xbrl.org/2006/xbrldi";
+
+/// ISO 4217 currency namespace
+pub const ISO4217_NS: &str = "http://www.xbrl.org/2003/iso4217";
+
+/// XML Schema instance namespace (for `xsi:nil`)
+pub const XSI_NS: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+fn parse_error(message: impl Into<String>) -> XbrlApiError {
+    XbrlApiError::ParseError(message.into())
+}
+
+fn is(node: Node, namespace: &str, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name && node.tag_name().namespace() == Some(namespace)
+}
+
+fn in_namespace(node: Node, namespace: &str) -> bool {
+    node.tag_name().namespace() == Some(namespace)
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, namespace: &str, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| is(*n, namespace, name))
+}
+
+fn required_attribute<'a>(node: Node<'a, '_>, name: &str) -> XbrlResult<&'a str> {
+    node.attribute(name).ok_or_else(|| {
+        parse_error(format!("{} element is missing the {} attribute", node.tag_name().name(), name))
+    })
+}
+
+/// Concatenated, trimmed text content of an element
+fn text_of(node: Node) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|n| n.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,209 @@
+//! Parsed files served through [`XbrlApi`]
+//!
+//! [`OfflineApi`] answers API calls from taxonomies and reports loaded from
+//! disk, so code written against the trait runs without network access or a
+//! local database.
+
+use super::dts::{load_taxonomy_directory, load_taxonomy_package, Dts};
+use super::instance::{parse_instance_file, Instance};
+use super::ixbrl::{parse_inline_file, InlineDocument};
+use crate::api::models::SearchParams;
+use crate::api::traits::XbrlApi;
+use crate::data::facts::{Fact, FactValue};
+use crate::data::reports::Report;
+use crate::data::taxonomy::{Concept, Dimension, Network, Taxonomy};
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use async_trait::async_trait;
+use rust_decimal::prelude::ToPrimitive;
+use std::path::Path;
+
+/// Taxonomies and reports loaded from local files
+///
+/// A loaded taxonomy answers for its own name and for the prefixes of its
+/// concepts, so `get_concepts("us-gaap")` finds base taxonomy concepts loaded
+/// with an extension DTS. Search follows `Store::search` of the `store` feature.
+#[derive(Debug, Default)]
+pub struct OfflineApi {
+    taxonomies: Vec<Dts>,
+    reports: Vec<(Report, Vec<Fact>)>,
+}
+
+impl OfflineApi {
+    /// Serve nothing until taxonomies and reports are added
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a loaded taxonomy
+    pub fn taxonomy(mut self, dts: Dts) -> Self {
+        self.taxonomies.push(dts);
+        self
+    }
+
+    /// Add a report and its facts, for example from an xBRL-JSON or xBRL-CSV file
+    pub fn report(mut self, report: Report, facts: Vec<Fact>) -> Self {
+        self.reports.push((report, facts));
+        self
+    }
+
+    /// Add a parsed instance document
+    pub fn instance(self, instance: Instance) -> Self {
+        self.report(instance.report, instance.facts)
+    }
+
+    /// Add a parsed Inline XBRL document
+    pub fn inline(self, document: InlineDocument) -> Self {
+        let report = document.report.clone();
+        self.report(report, document.into_facts())
+    }
+
+    /// Load a taxonomy from a directory or a taxonomy package ZIP
+    pub fn open_taxonomy(self, path: impl AsRef<Path>) -> XbrlResult<Self> {
+        let path = path.as_ref();
+        let dts = if path.is_dir() { load_taxonomy_directory(path)? } else { load_taxonomy_package(path)? };
+        Ok(self.taxonomy(dts))
+    }
+
+    /// Load an instance document, or an Inline XBRL document if the file is HTML
+    pub fn open_report(self, path: impl AsRef<Path>) -> XbrlResult<Self> {
+        let path = path.as_ref();
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+        match extension.as_str() {
+            "htm" | "html" | "xhtml" => Ok(self.inline(parse_inline_file(path)?)),
+            _ => Ok(self.instance(parse_instance_file(path)?)),
+        }
+    }
+
+    fn concepts_of<'a>(&'a self, taxonomy: &'a str) -> impl Iterator<Item = &'a Concept> + 'a {
+        self.taxonomies.iter().flat_map(move |dts| {
+            let whole = dts.taxonomy.name == taxonomy;
+            dts.concepts.iter().filter(move |concept| whole || concept.taxonomy == taxonomy)
+        })
+    }
+
+    fn taxonomies_of<'a>(&'a self, taxonomy: &'a str) -> impl Iterator<Item = &'a Dts> + 'a {
+        self.taxonomies.iter().filter(move |dts| {
+            dts.taxonomy.name == taxonomy || dts.concepts.iter().any(|concept| concept.taxonomy == taxonomy)
+        })
+    }
+}
+
+#[async_trait]
+impl XbrlApi for OfflineApi {
+    async fn get_taxonomies(&self) -> XbrlResult<Vec<Taxonomy>> {
+        Ok(self.taxonomies.iter().map(|dts| dts.taxonomy.clone()).collect())
+    }
+
+    async fn get_reports(&self, taxonomy: &str) -> XbrlResult<Vec<Report>> {
+        Ok(self
+            .reports
+            .iter()
+            .filter(|(report, _)| report.taxonomy == taxonomy)
+            .map(|(report, _)| report.clone())
+            .collect())
+    }
+
+    async fn get_facts(&self, report_id: &str) -> XbrlResult<Vec<Fact>> {
+        Ok(self
+            .reports
+            .iter()
+            .filter(|(report, _)| report.id == report_id)
+            .flat_map(|(_, facts)| facts.iter().cloned())
+            .collect())
+    }
+
+    async fn get_concepts(&self, taxonomy: &str) -> XbrlResult<Vec<Concept>> {
+        Ok(self.concepts_of(taxonomy).cloned().collect())
+    }
+
+    async fn get_concept_details(&self, taxonomy: &str, concept_name: &str) -> XbrlResult<Concept> {
+        self.concepts_of(taxonomy)
+            .find(|concept| concept.name == concept_name)
+            .cloned()
+            .ok_or_else(|| concept_not_found(taxonomy, concept_name))
+    }
+
+    async fn get_dimensions(&self, taxonomy: &str) -> XbrlResult<Vec<Dimension>> {
+        Ok(self.taxonomies_of(taxonomy).flat_map(|dts| dts.dimensions.iter().cloned()).collect())
+    }
+
+    async fn get_networks(&self, taxonomy: &str) -> XbrlResult<Vec<Network>> {
+        Ok(self.taxonomies_of(taxonomy).flat_map(|dts| dts.networks.iter().cloned()).collect())
+    }
+
+    async fn get_network_details(&self, network_id: &str) -> XbrlResult<Network> {
+        self.taxonomies
+            .iter()
+            .flat_map(|dts| &dts.networks)
+            .find(|network| network.id == network_id)
+            .cloned()
+            .ok_or_else(|| network_not_found(network_id))
+    }
+
+    async fn search(&self, params: SearchParams) -> XbrlResult<Vec<Fact>> {
+        Ok(self
+            .reports
+            .iter()
+            .filter(|(report, _)| report_matches(report, &params))
+            .flat_map(|(_, facts)| facts.iter().filter(|fact| fact_matches(fact, &params)).cloned())
+            .collect())
+    }
+}
+
+fn report_matches(report: &Report, params: &SearchParams) -> bool {
+    (params.taxonomy.is_empty() || report.taxonomy == params.taxonomy)
+        && params.fiscal_year.is_none_or(|year| report.fiscal_year == year)
+        && params.fiscal_period.as_deref().is_none_or(|period| report.fiscal_period == period)
+}
+
+fn fact_matches(fact: &Fact, params: &SearchParams) -> bool {
+    let dimensions = fact.dimensions.iter().flatten();
+    let value = match &fact.value {
+        FactValue::Decimal(value) => value.to_f64(),
+        FactValue::Number(value) => Some(*value),
+        _ => None,
+    };
+
+    params.concept_name.as_deref().is_none_or(|name| fact.concept_name == name)
+        && params.entity_id.as_deref().is_none_or(|id| fact.entity_id == id)
+        && params
+            .dimension_name
+            .as_deref()
+            .is_none_or(|axis| dimensions.clone().any(|(dimension, _)| same_name(dimension, axis)))
+        && params
+            .member_name
+            .as_deref()
+            .is_none_or(|member| dimensions.clone().any(|(_, value)| same_name(value, member)))
+        && params.text_search.as_deref().is_none_or(|text| {
+            // Case-insensitive, like SQLite's LIKE
+            let text = text.to_lowercase();
+            let contains = |haystack: &str| haystack.to_lowercase().contains(&text);
+            contains(&fact.concept_name)
+                || fact.concept_label.as_deref().is_some_and(contains)
+                || (!fact.value.is_nil() && contains(&fact.value.to_string()))
+        })
+        && params.value_greater_than.is_none_or(|bound| value.is_some_and(|value| value > bound))
+        && params.value_less_than.is_none_or(|bound| value.is_some_and(|value| value < bound))
+}
+
+/// Whether a QName matches a name given with or without its prefix
+fn same_name(qname: &str, name: &str) -> bool {
+    qname == name || qname.rsplit_once(':').is_some_and(|(_, local)| local == name)
+}
+
+/// Missing concept, reported against the endpoint the API would have used
+fn concept_not_found(taxonomy: &str, concept_name: &str) -> XbrlApiError {
+    XbrlApiError::NotFound {
+        message: format!("concept {} is not loaded for {}", concept_name, taxonomy),
+        method: "GET".to_string(),
+        url: format!("concepts/{}?taxonomy={}", concept_name, taxonomy),
+    }
+}
+
+fn network_not_found(network_id: &str) -> XbrlApiError {
+    XbrlApiError::NotFound {
+        message: format!("network {} is not loaded", network_id),
+        method: "GET".to_string(),
+        url: format!("networks/{}", network_id),
+    }
+}
@@ -0,0 +1,289 @@
+use super::parse_error;
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+/// OASIS XML catalog namespace used by `META-INF/catalog.xml`
+const CATALOG_NS: &str = "urn:oasis:names:tc:entity:xmlns:xml:catalog";
+
+/// Largest uncompressed file accepted from a package ZIP
+///
+/// The biggest linkbases of the US GAAP taxonomy are a few tens of MB; the
+/// limit keeps a crafted archive from inflating into memory without bound.
+pub const MAX_ENTRY_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Files of a taxonomy, read from a directory or a taxonomy package ZIP
+///
+/// Paths are `/`-separated and relative to the directory or archive root.
+/// `META-INF/catalog.xml` remappings and `META-INF/taxonomyPackage.xml`
+/// metadata are picked up wherever the `META-INF` folder sits, since packages
+/// usually wrap everything in a single top-level folder.
+#[derive(Debug)]
+pub struct TaxonomyPackage {
+    source: Source,
+    /// `(uriStartString, local rewrite prefix)`, longest prefix first
+    rewrites: Vec<(String, String)>,
+    metadata: Option<PackageMetadata>,
+    metadata_path: Option<String>,
+}
+
+/// Contents of `META-INF/taxonomyPackage.xml`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PackageMetadata {
+    pub identifier: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub publisher_url: Option<String>,
+    /// `href` of every entry point document, as written in the package
+    pub entry_points: Vec<String>,
+}
+
+#[derive(Debug)]
+enum Source {
+    Directory(PathBuf),
+    Archive(HashMap<String, Vec<u8>>),
+}
+
+impl TaxonomyPackage {
+    /// Open a taxonomy stored as plain files
+    pub fn open_directory(path: impl AsRef<Path>) -> XbrlResult<Self> {
+        let path = path.as_ref();
+        if !path.is_dir() {
+            return Err(parse_error(format!("{} is not a directory", path.display())));
+        }
+        Self::new(Source::Directory(path.to_path_buf()))
+    }
+
+    /// Open a taxonomy package ZIP file
+    pub fn open_zip(path: impl AsRef<Path>) -> XbrlResult<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| parse_error(format!("cannot read {}: {}", path.display(), e)))?;
+        Self::from_zip_bytes(bytes)
+    }
+
+    /// Open a taxonomy package ZIP held in memory
+    ///
+    /// Entries larger than [`MAX_ENTRY_SIZE`] once decompressed are rejected.
+    pub fn from_zip_bytes(bytes: Vec<u8>) -> XbrlResult<Self> {
+        let mut archive =
+            zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| parse_error(format!("invalid ZIP archive: {}", e)))?;
+        let mut files = HashMap::new();
+        for index in 0..archive.len() {
+            let mut file = archive
+                .by_index(index)
+                .map_err(|e| parse_error(format!("invalid ZIP entry: {}", e)))?;
+            if file.is_dir() {
+                continue;
+            }
+            let name = file.name().replace('\\', "/");
+            // The header's size can lie, so the read itself is capped as well
+            if file.size() > MAX_ENTRY_SIZE {
+                return Err(too_large(&name));
+            }
+            let mut contents = Vec::new();
+            (&mut file)
+                .take(MAX_ENTRY_SIZE + 1)
+                .read_to_end(&mut contents)
+                .map_err(|e| parse_error(format!("cannot read {} from ZIP: {}", name, e)))?;
+            if contents.len() as u64 > MAX_ENTRY_SIZE {
+                return Err(too_large(&name));
+            }
+            files.insert(normalize(&name), contents);
+        }
+        Self::new(Source::Archive(files))
+    }
+
+    fn new(source: Source) -> XbrlResult<Self> {
+        let mut package = Self {
+            source,
+            rewrites: Vec::new(),
+            metadata: None,
+            metadata_path: None,
+        };
+
+        let files = package.files();
+        if let Some(catalog) = files.iter().find(|f| f.ends_with("META-INF/catalog.xml") || *f == "catalog.xml") {
+            let xml = package.read(catalog)?;
+            package.rewrites = parse_catalog(&xml, catalog)?;
+        }
+        if let Some(metadata) = files.iter().find(|f| f.ends_with("META-INF/taxonomyPackage.xml")) {
+            let xml = package.read(metadata)?;
+            package.metadata = Some(parse_metadata(&xml)?);
+            package.metadata_path = Some(metadata.clone());
+        }
+        Ok(package)
+    }
+
+    /// Package metadata, when the package has a `taxonomyPackage.xml`
+    pub fn metadata(&self) -> Option<&PackageMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Package paths of the documents a DTS should be discovered from
+    ///
+    /// These are the entry points declared in `taxonomyPackage.xml`, or every
+    /// schema outside `META-INF` when the package does not declare any.
+    pub fn entry_points(&self) -> XbrlResult<Vec<String>> {
+        match (&self.metadata, &self.metadata_path) {
+            (Some(metadata), Some(base)) if !metadata.entry_points.is_empty() => metadata
+                .entry_points
+                .iter()
+                .map(|href| {
+                    self.resolve(base, href)
+                        .ok_or_else(|| parse_error(format!("entry point {} is not in the package", href)))
+                })
+                .collect(),
+            _ => Ok(self
+                .files()
+                .into_iter()
+                .filter(|f| f.ends_with(".xsd") && !f.contains("META-INF/"))
+                .collect()),
+        }
+    }
+
+    /// Every file in the package
+    pub fn files(&self) -> Vec<String> {
+        let mut files = match &self.source {
+            Source::Directory(root) => {
+                let mut files = Vec::new();
+                list_directory(root, "", &mut files);
+                files
+            }
+            Source::Archive(entries) => entries.keys().cloned().collect(),
+        };
+        files.sort();
+        files
+    }
+
+    /// Read a file by its package path
+    pub fn read(&self, path: &str) -> XbrlResult<String> {
+        let bytes = match &self.source {
+            Source::Directory(root) => std::fs::read(root.join(path))
+                .map_err(|e| parse_error(format!("cannot read {}: {}", path, e)))?,
+            Source::Archive(entries) => entries
+                .get(path)
+                .cloned()
+                .ok_or_else(|| parse_error(format!("{} is not in the package", path)))?,
+        };
+        String::from_utf8(bytes).map_err(|_| parse_error(format!("{} is not valid UTF-8", path)))
+    }
+
+    /// Whether the package contains a file
+    pub fn contains(&self, path: &str) -> bool {
+        match &self.source {
+            Source::Directory(root) => root.join(path).is_file(),
+            Source::Archive(entries) => entries.contains_key(path),
+        }
+    }
+
+    /// Resolve `href` found in the document at `base` to a package path
+    ///
+    /// Absolute URLs go through the catalog; `None` means the target is not
+    /// part of the package (for example a remote base taxonomy).
+    pub fn resolve(&self, base: &str, href: &str) -> Option<String> {
+        let href = href.split('#').next().unwrap_or_default();
+        if href.is_empty() {
+            return Some(base.to_string());
+        }
+        let path = if href.contains("://") {
+            let (start, prefix) = self.rewrites.iter().find(|(start, _)| href.starts_with(start.as_str()))?;
+            normalize(&format!("{}{}", prefix, &href[start.len()..]))
+        } else {
+            join(base, href)
+        };
+        self.contains(&path).then_some(path)
+    }
+}
+
+fn too_large(name: &str) -> XbrlApiError {
+    parse_error(format!("{} is larger than {} bytes uncompressed", name, MAX_ENTRY_SIZE))
+}
+
+fn list_directory(root: &Path, prefix: &str, files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(root.join(prefix)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = if prefix.is_empty() { name } else { format!("{}/{}", prefix, name) };
+        if entry.path().is_dir() {
+            list_directory(root, &path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Resolve a relative reference against the document it appears in
+pub(super) fn join(base: &str, href: &str) -> String {
+    let directory = base.rsplit_once('/').map(|(directory, _)| directory).unwrap_or_default();
+    if directory.is_empty() {
+        normalize(href)
+    } else {
+        normalize(&format!("{}/{}", directory, href))
+    }
+}
+
+/// Remove `.` and `..` segments
+fn normalize(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+fn parse_catalog(xml: &str, catalog_path: &str) -> XbrlResult<Vec<(String, String)>> {
+    let document = Document::parse(xml).map_err(|e| parse_error(format!("invalid catalog.xml: {}", e)))?;
+    let mut rewrites: Vec<(String, String)> = document
+        .descendants()
+        .filter(|n| n.has_tag_name((CATALOG_NS, "rewriteURI")))
+        .filter_map(|n| {
+            let start = n.attribute("uriStartString")?;
+            let prefix = n.attribute("rewritePrefix")?;
+            // Keep the trailing slash of directory prefixes through normalisation
+            let mut local = join(catalog_path, prefix);
+            if prefix.ends_with('/') && !local.is_empty() {
+                local.push('/');
+            }
+            Some((start.to_string(), local))
+        })
+        .collect();
+    rewrites.sort_by_key(|(start, _)| std::cmp::Reverse(start.len()));
+    Ok(rewrites)
+}
+
+fn parse_metadata(xml: &str) -> XbrlResult<PackageMetadata> {
+    let document = Document::parse(xml).map_err(|e| parse_error(format!("invalid taxonomyPackage.xml: {}", e)))?;
+    let root = document.root_element();
+    // Elements are looked up by local name so all package spec versions work
+    let text = |name: &str| {
+        root.children()
+            .find(|n| n.is_element() && n.tag_name().name() == name)
+            .and_then(|n| n.text())
+            .map(|t| t.trim().to_string())
+    };
+    let entry_points = root
+        .descendants()
+        .filter(|n: &Node| n.is_element() && n.tag_name().name() == "entryPointDocument")
+        .filter_map(|n| n.attribute("href").map(str::to_string))
+        .collect();
+
+    Ok(PackageMetadata {
+        identifier: text("identifier"),
+        name: text("name"),
+        description: text("description"),
+        version: text("version"),
+        publisher_url: text("publisherURL"),
+        entry_points,
+    })
+}
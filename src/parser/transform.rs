@@ -0,0 +1,153 @@
+use super::parse_error;
+use crate::utils::errors::XbrlResult;
+use chrono::NaiveDate;
+
+const MONTHS: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june", "july", "august", "september", "october", "november",
+    "december",
+];
+
+/// Apply an Inline XBRL transformation registry format to displayed text
+///
+/// `format` is the `format` attribute value, with or without its prefix. Both
+/// the hyphenated names of registries 3 to 5 (`num-dot-decimal`) and the
+/// older run-together names (`numdotdecimal`) are accepted. Dates come back as
+/// `YYYY-MM-DD`.
+pub fn apply_format(format: &str, text: &str) -> XbrlResult<String> {
+    transform(format, text).unwrap_or_else(|| {
+        Err(parse_error(format!("unsupported transformation {}", local_name(format))))
+    })
+}
+
+/// Whether `format` names a transformation [`apply_format`] implements
+pub fn is_supported_format(format: &str) -> bool {
+    transform(format, "").is_some()
+}
+
+fn local_name(format: &str) -> &str {
+    format.rsplit(':').next().unwrap_or(format)
+}
+
+/// Transformed text, or `None` if the format is not in the registry
+fn transform(format: &str, text: &str) -> Option<XbrlResult<String>> {
+    let name = local_name(format);
+    let text = text.trim();
+    let unsupported = || parse_error(format!("unsupported or invalid transformation {} for {:?}", name, text));
+
+    Some(match name {
+        "num-dot-decimal" | "numdotdecimal" | "numcommadot" | "numspacedot" => number(text, '.'),
+        "num-comma-decimal" | "numcommadecimal" | "numdotcomma" | "numspacecomma" => number(text, ','),
+        "fixed-zero" | "zerodash" | "numdash" => Ok("0".to_string()),
+        "fixed-empty" => Ok(String::new()),
+        "fixed-false" | "booleanfalse" => Ok("false".to_string()),
+        "fixed-true" | "booleantrue" => Ok("true".to_string()),
+        "date-monthname-day-year-en" | "datemonthdayyearen" | "datemonthnamedayyearen" => {
+            let parts = words(text);
+            match parts.as_slice() {
+                [month, day, year] => date(year, month_number(month), day),
+                _ => None,
+            }
+            .ok_or_else(unsupported)
+        }
+        "date-day-monthname-year-en" | "datedaymonthyearen" | "datedaymonthnameyearen" => {
+            let parts = words(text);
+            match parts.as_slice() {
+                [day, month, year] => date(year, month_number(month), day),
+                _ => None,
+            }
+            .ok_or_else(unsupported)
+        }
+        "date-month-day-year" | "datemonthdayyear" | "dateslashus" | "datedotus" => {
+            let parts = numbers(text);
+            match parts.as_slice() {
+                [month, day, year] => date(year, month.parse().ok(), day),
+                _ => None,
+            }
+            .ok_or_else(unsupported)
+        }
+        "date-day-month-year" | "datedaymonthyear" | "dateslasheu" | "datedoteu" => {
+            let parts = numbers(text);
+            match parts.as_slice() {
+                [day, month, year] => date(year, month.parse().ok(), day),
+                _ => None,
+            }
+            .ok_or_else(unsupported)
+        }
+        "date-year-month-day" | "dateyearmonthday" => {
+            let parts = numbers(text);
+            match parts.as_slice() {
+                [year, month, day] => date(year, month.parse().ok(), day),
+                _ => None,
+            }
+            .ok_or_else(unsupported)
+        }
+        _ => return None,
+    })
+}
+
+/// Drop group separators and turn `decimal_separator` into a dot
+///
+/// Text must be digits with optional group separators (spaces, apostrophes
+/// or the other one of `.` and `,`) and at most one decimal separator
+/// followed only by digits; anything else is rejected.
+fn number(text: &str, decimal_separator: char) -> XbrlResult<String> {
+    let invalid = || parse_error(format!("invalid number {:?}", text));
+    let group_separator = if decimal_separator == '.' { ',' } else { '.' };
+    let is_group_separator = |c: char| c == group_separator || c == '\'' || c.is_whitespace();
+
+    let (integer, fraction) = match text.split_once(decimal_separator) {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (text, None),
+    };
+    let starts_and_ends_with_digit = |part: &str| {
+        part.starts_with(|c: char| c.is_ascii_digit()) && part.ends_with(|c: char| c.is_ascii_digit())
+    };
+    if !starts_and_ends_with_digit(integer)
+        || !integer.chars().all(|c| c.is_ascii_digit() || is_group_separator(c))
+    {
+        return Err(invalid());
+    }
+
+    let mut out: String = integer.chars().filter(char::is_ascii_digit).collect();
+    if let Some(fraction) = fraction {
+        if !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        if !fraction.is_empty() {
+            out.push('.');
+            out.push_str(fraction);
+        }
+    }
+    Ok(out)
+}
+
+fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| c.is_whitespace() || c == ',' || c == '.')
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn numbers(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_ascii_digit()).filter(|part| !part.is_empty()).collect()
+}
+
+/// Month number from an English month name or its abbreviation
+fn month_number(name: &str) -> Option<u32> {
+    let name = name.to_lowercase();
+    if name.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|month| month.starts_with(name.trim_end_matches('.')))
+        .map(|index| index as u32 + 1)
+}
+
+fn date(year: &str, month: Option<u32>, day: &str) -> Option<String> {
+    let mut year: i32 = year.parse().ok()?;
+    // Two-digit years are taken to be in this century
+    if year < 100 {
+        year += 2000;
+    }
+    NaiveDate::from_ymd_opt(year, month?, day.parse().ok()?).map(|date| date.to_string())
+}
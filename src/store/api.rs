@@ -0,0 +1,100 @@
+use super::Store;
+use crate::api::models::SearchParams;
+use crate::api::traits::XbrlApi;
+use crate::data::facts::Fact;
+use crate::data::reports::Report;
+use crate::data::taxonomy::{Concept, Dimension, Network, Taxonomy};
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use async_trait::async_trait;
+use std::sync::{Mutex, MutexGuard};
+
+/// [`Store`] served through [`XbrlApi`]
+///
+/// SQLite connections cannot be shared between threads, so the store sits
+/// behind a mutex and queries run one at a time. Missing concepts and
+/// networks are reported as [`XbrlApiError::NotFound`], as the API does.
+pub struct StoreApi {
+    store: Mutex<Store>,
+}
+
+impl StoreApi {
+    /// Serve a store
+    pub fn new(store: Store) -> Self {
+        Self { store: Mutex::new(store) }
+    }
+
+    /// Take the store back, for example to save or sync more data
+    pub fn into_inner(self) -> Store {
+        self.store.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn store(&self) -> MutexGuard<'_, Store> {
+        self.store.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl From<Store> for StoreApi {
+    fn from(store: Store) -> Self {
+        Self::new(store)
+    }
+}
+
+#[async_trait]
+impl XbrlApi for StoreApi {
+    async fn get_taxonomies(&self) -> XbrlResult<Vec<Taxonomy>> {
+        self.store().taxonomies()
+    }
+
+    async fn get_reports(&self, taxonomy: &str) -> XbrlResult<Vec<Report>> {
+        self.store().reports(taxonomy)
+    }
+
+    async fn get_facts(&self, report_id: &str) -> XbrlResult<Vec<Fact>> {
+        self.store().facts(report_id)
+    }
+
+    async fn get_concepts(&self, taxonomy: &str) -> XbrlResult<Vec<Concept>> {
+        self.store().concepts(taxonomy)
+    }
+
+    async fn get_concept_details(&self, taxonomy: &str, concept_name: &str) -> XbrlResult<Concept> {
+        self.store()
+            .concept(taxonomy, concept_name)?
+            .ok_or_else(|| concept_not_found(taxonomy, concept_name))
+    }
+
+    async fn get_dimensions(&self, taxonomy: &str) -> XbrlResult<Vec<Dimension>> {
+        self.store().dimensions(taxonomy)
+    }
+
+    async fn get_networks(&self, taxonomy: &str) -> XbrlResult<Vec<Network>> {
+        self.store().networks(taxonomy)
+    }
+
+    async fn get_network_details(&self, network_id: &str) -> XbrlResult<Network> {
+        self.store()
+            .network(network_id)?
+            .ok_or_else(|| network_not_found(network_id))
+    }
+
+    async fn search(&self, params: SearchParams) -> XbrlResult<Vec<Fact>> {
+        self.store().search(&params)
+    }
+}
+
+/// Missing concept, reported against the endpoint the API would have used
+fn concept_not_found(taxonomy: &str, concept_name: &str) -> XbrlApiError {
+    XbrlApiError::NotFound {
+        message: format!("concept {} is not stored for {}", concept_name, taxonomy),
+        method: "GET".to_string(),
+        url: format!("concepts/{}?taxonomy={}", concept_name, taxonomy),
+    }
+}
+
+fn network_not_found(network_id: &str) -> XbrlApiError {
+    XbrlApiError::NotFound {
+        message: format!("network {} is not stored", network_id),
+        method: "GET".to_string(),
+        url: format!("networks/{}", network_id),
+    }
+}
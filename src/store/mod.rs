@@ -0,0 +1,879 @@
+//! Local SQLite warehouse for XBRL data
+//!
+//! [`Store`] keeps taxonomies, reports, facts, concepts and networks in a
+//! normalized schema and reads them back as the crate's model types, so data
+//! fetched once can be queried offline. [`Store::sync`] brings it up to date
+//! from the API, downloading facts only for reports it has not seen.
+
+mod api;
+mod sync;
+
+pub use api::StoreApi;
+pub use sync::{SyncOptions, SyncSummary};
+
+use crate::api::models::SearchParams;
+use crate::data::facts::{Decimals, Fact, FactValue, Precision};
+use crate::data::period::{parse_date, Period};
+use crate::data::reports::Report;
+use crate::data::taxonomy::{Concept, ConceptDimension, Dimension, DimensionMember, Network, NetworkNode, Reference, Taxonomy};
+use crate::data::units::Unit;
+use crate::utils::errors::{XbrlApiError, XbrlResult};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row, Transaction};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS taxonomies (
+    name TEXT PRIMARY KEY,
+    description TEXT NOT NULL,
+    version TEXT NOT NULL,
+    documentation_url TEXT
+);
+
+CREATE TABLE IF NOT EXISTS entities (
+    id TEXT PRIMARY KEY,
+    name TEXT,
+    cik TEXT
+);
+
+CREATE TABLE IF NOT EXISTS reports (
+    id TEXT PRIMARY KEY,
+    accession_number TEXT,
+    filing_date TEXT NOT NULL,
+    fiscal_period TEXT NOT NULL,
+    fiscal_year INTEGER NOT NULL,
+    entity_id TEXT NOT NULL REFERENCES entities (id),
+    taxonomy TEXT NOT NULL,
+    report_type TEXT,
+    filing_url TEXT
+);
+CREATE INDEX IF NOT EXISTS reports_by_taxonomy ON reports (taxonomy, filing_date);
+
+CREATE TABLE IF NOT EXISTS facts (
+    report_id TEXT NOT NULL,
+    id TEXT NOT NULL,
+    concept_name TEXT NOT NULL,
+    concept_label TEXT,
+    entity_id TEXT NOT NULL REFERENCES entities (id),
+    period_start TEXT,
+    period_end TEXT,
+    -- decimal, number, string or boolean; NULL for nil facts
+    value_type TEXT,
+    value TEXT,
+    unit TEXT,
+    decimals TEXT,
+    precision TEXT,
+    filing_url TEXT,
+    PRIMARY KEY (report_id, id)
+);
+CREATE INDEX IF NOT EXISTS facts_by_concept ON facts (concept_name);
+
+CREATE TABLE IF NOT EXISTS fact_dimensions (
+    report_id TEXT NOT NULL,
+    fact_id TEXT NOT NULL,
+    dimension TEXT NOT NULL,
+    member TEXT NOT NULL,
+    PRIMARY KEY (report_id, fact_id, dimension),
+    FOREIGN KEY (report_id, fact_id) REFERENCES facts (report_id, id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS concepts (
+    taxonomy TEXT NOT NULL,
+    name TEXT NOT NULL,
+    label TEXT NOT NULL,
+    description TEXT,
+    type_name TEXT NOT NULL,
+    period_type TEXT,
+    balance TEXT,
+    standard_label TEXT,
+    documentation TEXT,
+    is_abstract INTEGER,
+    is_nillable INTEGER,
+    PRIMARY KEY (taxonomy, name)
+);
+
+CREATE TABLE IF NOT EXISTS concept_references (
+    taxonomy TEXT NOT NULL,
+    concept TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (taxonomy, concept, position),
+    FOREIGN KEY (taxonomy, concept) REFERENCES concepts (taxonomy, name) ON DELETE CASCADE
+);
+
+-- One row per member; dimensions without members have a single NULL member
+CREATE TABLE IF NOT EXISTS concept_dimension_members (
+    taxonomy TEXT NOT NULL,
+    concept TEXT NOT NULL,
+    dimension_position INTEGER NOT NULL,
+    dimension TEXT NOT NULL,
+    member_position INTEGER NOT NULL,
+    member TEXT,
+    PRIMARY KEY (taxonomy, concept, dimension_position, member_position),
+    FOREIGN KEY (taxonomy, concept) REFERENCES concepts (taxonomy, name) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS networks (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    short_name TEXT,
+    description TEXT,
+    taxonomy TEXT NOT NULL,
+    role TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS network_nodes (
+    network_id TEXT NOT NULL REFERENCES networks (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    parent_position INTEGER,
+    concept_name TEXT NOT NULL,
+    concept_label TEXT NOT NULL,
+    parent TEXT,
+    sort_order REAL,
+    level INTEGER,
+    preferred_label TEXT,
+    PRIMARY KEY (network_id, position)
+);
+
+CREATE TABLE IF NOT EXISTS sync_state (
+    taxonomy TEXT PRIMARY KEY,
+    last_filing_date TEXT,
+    last_synced_at TEXT NOT NULL
+);
+";
+
+/// A bound `?` with its LIKE wildcards escaped, for patterns using `ESCAPE '\'`
+const LIKE_LITERAL: &str = r"replace(replace(replace(?, '\', '\\'), '%', '\%'), '_', '\_')";
+
+/// Columns read by [`Store::load_facts`]
+const FACT_COLUMNS: &str = "f.report_id, f.id, f.concept_name, f.concept_label, f.entity_id, e.name, \
+    f.period_start, f.period_end, f.value_type, f.value, f.unit, f.decimals, f.precision, f.filing_url, \
+    d.dimension, d.member";
+
+/// SQLite database of XBRL data
+///
+/// Saving replaces rows with the same key, so the same data can be saved
+/// repeatedly. Facts are keyed by report and fact id.
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    /// Open or create a database file
+    pub fn open(path: impl AsRef<Path>) -> XbrlResult<Self> {
+        Self::init(Connection::open(path).map_err(sqlite_error)?)
+    }
+
+    /// Open a database that lives only as long as the store
+    pub fn open_in_memory() -> XbrlResult<Self> {
+        Self::init(Connection::open_in_memory().map_err(sqlite_error)?)
+    }
+
+    fn init(connection: Connection) -> XbrlResult<Self> {
+        connection.execute_batch("PRAGMA foreign_keys = ON;").map_err(sqlite_error)?;
+        connection.execute_batch(SCHEMA).map_err(sqlite_error)?;
+        Ok(Self { connection })
+    }
+
+    /// Save taxonomies
+    pub fn save_taxonomies(&mut self, taxonomies: &[Taxonomy]) -> XbrlResult<()> {
+        self.write(|tx| {
+            let mut insert = tx.prepare_cached(
+                "INSERT OR REPLACE INTO taxonomies (name, description, version, documentation_url)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for taxonomy in taxonomies {
+                insert.execute(params![
+                    taxonomy.name,
+                    taxonomy.description,
+                    taxonomy.version,
+                    taxonomy.documentation_url
+                ])?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Save reports and their entities
+    pub fn save_reports(&mut self, reports: &[Report]) -> XbrlResult<()> {
+        self.write(|tx| {
+            for report in reports {
+                save_report(tx, report)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Save facts, replacing the dimensions of facts saved before
+    pub fn save_facts(&mut self, facts: &[Fact]) -> XbrlResult<()> {
+        self.write(|tx| save_facts(tx, facts))
+    }
+
+    /// Save concepts with their references and dimensions
+    pub fn save_concepts(&mut self, concepts: &[Concept]) -> XbrlResult<()> {
+        self.write(|tx| {
+            for concept in concepts {
+                let key = params![concept.taxonomy, concept.name];
+                tx.execute("DELETE FROM concepts WHERE taxonomy = ?1 AND name = ?2", key)?;
+                tx.execute(
+                    "INSERT INTO concepts (taxonomy, name, label, description, type_name, period_type, balance,
+                     standard_label, documentation, is_abstract, is_nillable)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                    params![
+                        concept.taxonomy,
+                        concept.name,
+                        concept.label,
+                        concept.description,
+                        concept.type_name,
+                        concept.period_type,
+                        concept.balance,
+                        concept.standard_label,
+                        concept.documentation,
+                        concept.is_abstract,
+                        concept.is_nillable
+                    ],
+                )?;
+                for (position, reference) in concept.references.iter().flatten().enumerate() {
+                    tx.execute(
+                        "INSERT INTO concept_references (taxonomy, concept, position, name, value)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![concept.taxonomy, concept.name, position as i64, reference.name, reference.value],
+                    )?;
+                }
+                for (dimension_position, dimension) in concept.dimensions.iter().flatten().enumerate() {
+                    let members = dimension.members.iter().map(Some);
+                    let members: Vec<Option<&String>> =
+                        if dimension.members.is_empty() { vec![None] } else { members.collect() };
+                    for (member_position, member) in members.into_iter().enumerate() {
+                        tx.execute(
+                            "INSERT INTO concept_dimension_members
+                             (taxonomy, concept, dimension_position, dimension, member_position, member)
+                             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                            params![
+                                concept.taxonomy,
+                                concept.name,
+                                dimension_position as i64,
+                                dimension.dimension_name,
+                                member_position as i64,
+                                member
+                            ],
+                        )?;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Save networks with their node trees
+    pub fn save_networks(&mut self, networks: &[Network]) -> XbrlResult<()> {
+        self.write(|tx| {
+            for network in networks {
+                tx.execute("DELETE FROM networks WHERE id = ?1", params![network.id])?;
+                tx.execute(
+                    "INSERT INTO networks (id, name, short_name, description, taxonomy, role)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![
+                        network.id,
+                        network.name,
+                        network.short_name,
+                        network.description,
+                        network.taxonomy,
+                        network.role
+                    ],
+                )?;
+                let mut position = 0;
+                for node in network.nodes.iter().flatten() {
+                    save_node(tx, &network.id, node, None, &mut position)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Every stored taxonomy, by name
+    pub fn taxonomies(&self) -> XbrlResult<Vec<Taxonomy>> {
+        self.query_all(
+            "SELECT name, description, version, documentation_url FROM taxonomies ORDER BY name",
+            [],
+            taxonomy_from_row,
+        )
+    }
+
+    /// A stored taxonomy
+    pub fn taxonomy(&self, name: &str) -> XbrlResult<Option<Taxonomy>> {
+        self.query_one(
+            "SELECT name, description, version, documentation_url FROM taxonomies WHERE name = ?1",
+            params![name],
+            taxonomy_from_row,
+        )
+    }
+
+    /// Stored reports of a taxonomy, oldest filing first
+    pub fn reports(&self, taxonomy: &str) -> XbrlResult<Vec<Report>> {
+        self.query_all(
+            &format!("{} WHERE r.taxonomy = ?1 ORDER BY r.filing_date, r.id", REPORT_QUERY),
+            params![taxonomy],
+            report_from_row,
+        )
+    }
+
+    /// A stored report
+    pub fn report(&self, id: &str) -> XbrlResult<Option<Report>> {
+        self.query_one(&format!("{} WHERE r.id = ?1", REPORT_QUERY), params![id], report_from_row)
+    }
+
+    /// Stored facts of a report, in the order they were saved
+    pub fn facts(&self, report_id: &str) -> XbrlResult<Vec<Fact>> {
+        self.load_facts("f.report_id = ?1", vec![Value::from(report_id.to_string())])
+    }
+
+    /// Stored facts matching search parameters, as [`XbrlClient::search`](crate::XbrlClient::search) would return them
+    ///
+    /// `taxonomy`, `fiscal_year` and `fiscal_period` refer to the fact's
+    /// report, so facts saved without their report only match when those are
+    /// unset (an empty `taxonomy` matches every taxonomy). Dimension and member
+    /// names match with or without a prefix; `text_search` looks at concept
+    /// names, labels and values.
+    pub fn search(&self, params: &SearchParams) -> XbrlResult<Vec<Fact>> {
+        let mut conditions = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+        let mut bind = |condition: &str, value: Value| {
+            values.push(value);
+            conditions.push(condition.replace('?', &format!("?{}", values.len())));
+        };
+
+        if !params.taxonomy.is_empty() {
+            bind("r.taxonomy = ?", params.taxonomy.clone().into());
+        }
+        if let Some(concept_name) = &params.concept_name {
+            bind("f.concept_name = ?", concept_name.clone().into());
+        }
+        if let Some(entity_id) = &params.entity_id {
+            bind("f.entity_id = ?", entity_id.clone().into());
+        }
+        if let Some(fiscal_year) = params.fiscal_year {
+            bind("r.fiscal_year = ?", i64::from(fiscal_year).into());
+        }
+        if let Some(fiscal_period) = &params.fiscal_period {
+            bind("r.fiscal_period = ?", fiscal_period.clone().into());
+        }
+        if let Some(dimension) = &params.dimension_name {
+            bind(
+                &format!(
+                    "EXISTS (SELECT 1 FROM fact_dimensions d WHERE d.report_id = f.report_id AND d.fact_id = f.id
+                     AND (d.dimension = ? OR d.dimension LIKE '%:' || {LIKE_LITERAL} ESCAPE '\\'))"
+                ),
+                dimension.clone().into(),
+            );
+        }
+        if let Some(member) = &params.member_name {
+            bind(
+                &format!(
+                    "EXISTS (SELECT 1 FROM fact_dimensions d WHERE d.report_id = f.report_id AND d.fact_id = f.id
+                     AND (d.member = ? OR d.member LIKE '%:' || {LIKE_LITERAL} ESCAPE '\\'))"
+                ),
+                member.clone().into(),
+            );
+        }
+        if let Some(text) = &params.text_search {
+            bind(
+                &format!(
+                    "(f.concept_name LIKE '%' || {LIKE_LITERAL} || '%' ESCAPE '\\'
+                     OR f.concept_label LIKE '%' || {LIKE_LITERAL} || '%' ESCAPE '\\'
+                     OR f.value LIKE '%' || {LIKE_LITERAL} || '%' ESCAPE '\\')"
+                ),
+                text.clone().into(),
+            );
+        }
+        if let Some(bound) = params.value_greater_than {
+            bind("f.value_type IN ('decimal', 'number') AND CAST(f.value AS REAL) > ?", bound.into());
+        }
+        if let Some(bound) = params.value_less_than {
+            bind("f.value_type IN ('decimal', 'number') AND CAST(f.value AS REAL) < ?", bound.into());
+        }
+
+        let filter = format!(
+            "f.rowid IN (SELECT f.rowid FROM facts f LEFT JOIN reports r ON r.id = f.report_id WHERE {})",
+            if conditions.is_empty() { "1".to_string() } else { conditions.join(" AND ") }
+        );
+        self.load_facts(&filter, values)
+    }
+
+    /// Stored concepts of a taxonomy, by name
+    pub fn concepts(&self, taxonomy: &str) -> XbrlResult<Vec<Concept>> {
+        self.load_concepts(taxonomy, None)
+    }
+
+    /// A stored concept
+    pub fn concept(&self, taxonomy: &str, name: &str) -> XbrlResult<Option<Concept>> {
+        Ok(self.load_concepts(taxonomy, Some(name))?.pop())
+    }
+
+    /// Dimensions used by the stored concepts of a taxonomy, by name
+    ///
+    /// Only names are stored, so labels repeat the names. Members are listed
+    /// once each, in the order concepts first use them.
+    pub fn dimensions(&self, taxonomy: &str) -> XbrlResult<Vec<Dimension>> {
+        let members: Vec<(String, Option<String>)> = self.query_all(
+            "SELECT dimension, member FROM concept_dimension_members
+             WHERE taxonomy = ?1 ORDER BY dimension, concept, dimension_position, member_position",
+            params![taxonomy],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let mut dimensions: Vec<Dimension> = Vec::new();
+        for (name, member) in members {
+            if dimensions.last().is_none_or(|dimension| dimension.name != name) {
+                dimensions.push(Dimension {
+                    label: name.clone(),
+                    name,
+                    description: None,
+                    taxonomy: taxonomy.to_string(),
+                    members: None,
+                });
+            }
+            if let (Some(member), Some(dimension)) = (member, dimensions.last_mut()) {
+                let members = dimension.members.get_or_insert_with(Vec::new);
+                if !members.iter().any(|existing| existing.name == member) {
+                    members.push(DimensionMember { label: member.clone(), name: member, description: None });
+                }
+            }
+        }
+        Ok(dimensions)
+    }
+
+    /// Stored networks of a taxonomy, by id
+    pub fn networks(&self, taxonomy: &str) -> XbrlResult<Vec<Network>> {
+        let ids: Vec<String> = self.query_all(
+            "SELECT id FROM networks WHERE taxonomy = ?1 ORDER BY id",
+            params![taxonomy],
+            |row| row.get(0),
+        )?;
+        ids.iter().filter_map(|id| self.network(id).transpose()).collect()
+    }
+
+    /// A stored network with its node tree
+    pub fn network(&self, id: &str) -> XbrlResult<Option<Network>> {
+        let network = self.query_one(
+            "SELECT id, name, short_name, description, taxonomy, role FROM networks WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok(Network {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    short_name: row.get(2)?,
+                    description: row.get(3)?,
+                    taxonomy: row.get(4)?,
+                    role: row.get(5)?,
+                    nodes: None,
+                })
+            },
+        )?;
+        let Some(mut network) = network else {
+            return Ok(None);
+        };
+
+        // Rows come in save order, so parents precede their children
+        let rows: Vec<(i64, Option<i64>, NetworkNode)> = self.query_all(
+            "SELECT position, parent_position, concept_name, concept_label, parent, sort_order, level, preferred_label
+             FROM network_nodes WHERE network_id = ?1 ORDER BY position",
+            params![id],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    NetworkNode {
+                        concept_name: row.get(2)?,
+                        concept_label: row.get(3)?,
+                        parent: row.get(4)?,
+                        order: row.get(5)?,
+                        level: row.get(6)?,
+                        preferred_label: row.get(7)?,
+                        children: None,
+                    },
+                ))
+            },
+        )?;
+        if !rows.is_empty() {
+            network.nodes = Some(build_tree(rows));
+        }
+        Ok(Some(network))
+    }
+
+    /// Run `f` in a transaction, committing when it succeeds
+    fn write<T>(&mut self, f: impl FnOnce(&Transaction) -> rusqlite::Result<T>) -> XbrlResult<T> {
+        let tx = self.connection.transaction().map_err(sqlite_error)?;
+        let result = f(&tx).map_err(sqlite_error)?;
+        tx.commit().map_err(sqlite_error)?;
+        Ok(result)
+    }
+
+    fn query_all<T, P: rusqlite::Params>(
+        &self,
+        sql: &str,
+        params: P,
+        f: impl FnMut(&Row) -> rusqlite::Result<T>,
+    ) -> XbrlResult<Vec<T>> {
+        let mut statement = self.connection.prepare_cached(sql).map_err(sqlite_error)?;
+        let rows = statement.query_map(params, f).map_err(sqlite_error)?;
+        rows.collect::<rusqlite::Result<_>>().map_err(sqlite_error)
+    }
+
+    fn query_one<T, P: rusqlite::Params>(
+        &self,
+        sql: &str,
+        params: P,
+        f: impl FnOnce(&Row) -> rusqlite::Result<T>,
+    ) -> XbrlResult<Option<T>> {
+        self.connection.query_row(sql, params, f).optional().map_err(sqlite_error)
+    }
+
+    /// Facts matching a condition on `facts f`, with their dimensions
+    fn load_facts(&self, condition: &str, values: Vec<Value>) -> XbrlResult<Vec<Fact>> {
+        let sql = format!(
+            "SELECT {} FROM facts f
+             JOIN entities e ON e.id = f.entity_id
+             LEFT JOIN fact_dimensions d ON d.report_id = f.report_id AND d.fact_id = f.id
+             WHERE {} ORDER BY f.rowid, d.dimension",
+            FACT_COLUMNS, condition
+        );
+        let rows: Vec<FactRow> = self.query_all(&sql, params_from_iter(values), FactRow::from_row)?;
+
+        let mut facts: Vec<Fact> = Vec::new();
+        let mut last_key: Option<(String, String)> = None;
+        for row in rows {
+            let key = (row.report_id.clone(), row.id.clone());
+            let dimension = row.dimension.clone().zip(row.member.clone());
+            if last_key.as_ref() != Some(&key) {
+                facts.push(row.into_fact()?);
+                last_key = Some(key);
+            }
+            if let (Some((dimension, member)), Some(fact)) = (dimension, facts.last_mut()) {
+                fact.dimensions.get_or_insert_with(HashMap::new).insert(dimension, member);
+            }
+        }
+        Ok(facts)
+    }
+
+    /// Concepts of a taxonomy, all of them when `name` is `None`
+    fn load_concepts(&self, taxonomy: &str, name: Option<&str>) -> XbrlResult<Vec<Concept>> {
+        let mut concepts: Vec<Concept> = self.query_all(
+            "SELECT name, label, description, taxonomy, type_name, period_type, balance, standard_label,
+             documentation, is_abstract, is_nillable FROM concepts
+             WHERE taxonomy = ?1 AND (?2 IS NULL OR name = ?2) ORDER BY name",
+            params![taxonomy, name],
+            |row| {
+                Ok(Concept {
+                    name: row.get(0)?,
+                    label: row.get(1)?,
+                    description: row.get(2)?,
+                    taxonomy: row.get(3)?,
+                    type_name: row.get(4)?,
+                    period_type: row.get(5)?,
+                    balance: row.get(6)?,
+                    standard_label: row.get(7)?,
+                    documentation: row.get(8)?,
+                    is_abstract: row.get(9)?,
+                    is_nillable: row.get(10)?,
+                    references: None,
+                    dimensions: None,
+                })
+            },
+        )?;
+        let references: Vec<(String, Reference)> = self.query_all(
+            "SELECT concept, name, value FROM concept_references
+             WHERE taxonomy = ?1 AND (?2 IS NULL OR concept = ?2) ORDER BY concept, position",
+            params![taxonomy, name],
+            |row| Ok((row.get(0)?, Reference { name: row.get(1)?, value: row.get(2)? })),
+        )?;
+        let mut references_by_concept: HashMap<String, Vec<Reference>> = HashMap::new();
+        for (concept, reference) in references {
+            references_by_concept.entry(concept).or_default().push(reference);
+        }
+
+        let members: Vec<(String, i64, String, Option<String>)> = self.query_all(
+            "SELECT concept, dimension_position, dimension, member FROM concept_dimension_members
+             WHERE taxonomy = ?1 AND (?2 IS NULL OR concept = ?2)
+             ORDER BY concept, dimension_position, member_position",
+            params![taxonomy, name],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+        let mut dimensions_by_concept: HashMap<String, Vec<(i64, ConceptDimension)>> = HashMap::new();
+        for (concept, position, dimension_name, member) in members {
+            let dimensions = dimensions_by_concept.entry(concept).or_default();
+            if dimensions.last().is_none_or(|(last, _)| *last != position) {
+                dimensions.push((position, ConceptDimension { dimension_name, members: Vec::new() }));
+            }
+            if let (Some(member), Some((_, dimension))) = (member, dimensions.last_mut()) {
+                dimension.members.push(member);
+            }
+        }
+
+        for concept in &mut concepts {
+            concept.references = references_by_concept.remove(&concept.name);
+            concept.dimensions = dimensions_by_concept
+                .remove(&concept.name)
+                .map(|dimensions| dimensions.into_iter().map(|(_, dimension)| dimension).collect());
+        }
+        Ok(concepts)
+    }
+}
+
+const REPORT_QUERY: &str = "SELECT r.id, r.accession_number, r.filing_date, r.fiscal_period, r.fiscal_year, \
+    r.entity_id, e.name, e.cik, r.taxonomy, r.report_type, r.filing_url \
+    FROM reports r JOIN entities e ON e.id = r.entity_id";
+
+fn taxonomy_from_row(row: &Row) -> rusqlite::Result<Taxonomy> {
+    Ok(Taxonomy {
+        name: row.get(0)?,
+        description: row.get(1)?,
+        version: row.get(2)?,
+        documentation_url: row.get(3)?,
+    })
+}
+
+fn report_from_row(row: &Row) -> rusqlite::Result<Report> {
+    Ok(Report {
+        id: row.get(0)?,
+        accession_number: row.get(1)?,
+        filing_date: row.get(2)?,
+        fiscal_period: row.get(3)?,
+        fiscal_year: row.get(4)?,
+        entity_id: row.get(5)?,
+        entity_name: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+        cik: row.get(7)?,
+        taxonomy: row.get(8)?,
+        report_type: row.get(9)?,
+        filing_url: row.get(10)?,
+    })
+}
+
+fn save_entity(tx: &Transaction, id: &str, name: Option<&str>, cik: Option<&str>) -> rusqlite::Result<()> {
+    tx.prepare_cached(
+        "INSERT INTO entities (id, name, cik) VALUES (?1, ?2, ?3)
+         ON CONFLICT (id) DO UPDATE SET name = COALESCE(excluded.name, name), cik = COALESCE(excluded.cik, cik)",
+    )?
+    .execute(params![id, name, cik])?;
+    Ok(())
+}
+
+fn save_report(tx: &Transaction, report: &Report) -> rusqlite::Result<()> {
+    save_entity(tx, &report.entity_id, Some(&report.entity_name), report.cik.as_deref())?;
+    tx.prepare_cached(
+        "INSERT OR REPLACE INTO reports (id, accession_number, filing_date, fiscal_period, fiscal_year, entity_id,
+         taxonomy, report_type, filing_url) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+    )?
+    .execute(params![
+        report.id,
+        report.accession_number,
+        report.filing_date,
+        report.fiscal_period,
+        report.fiscal_year,
+        report.entity_id,
+        report.taxonomy,
+        report.report_type,
+        report.filing_url
+    ])?;
+    Ok(())
+}
+
+fn save_facts(tx: &Transaction, facts: &[Fact]) -> rusqlite::Result<()> {
+    let mut delete = tx.prepare_cached("DELETE FROM facts WHERE report_id = ?1 AND id = ?2")?;
+    let mut insert = tx.prepare_cached(
+        "INSERT INTO facts (report_id, id, concept_name, concept_label, entity_id, period_start, period_end,
+         value_type, value, unit, decimals, precision, filing_url)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+    )?;
+    let mut insert_dimension = tx.prepare_cached(
+        "INSERT INTO fact_dimensions (report_id, fact_id, dimension, member) VALUES (?1, ?2, ?3, ?4)",
+    )?;
+
+    for fact in facts {
+        save_entity(tx, &fact.entity_id, fact.entity_name.as_deref(), None)?;
+        let (value_type, value) = match &fact.value {
+            FactValue::Decimal(value) => (Some("decimal"), Some(value.to_string())),
+            FactValue::Number(value) => (Some("number"), Some(value.to_string())),
+            FactValue::String(value) => (Some("string"), Some(value.clone())),
+            FactValue::Boolean(value) => (Some("boolean"), Some(value.to_string())),
+            FactValue::Nil => (None, None),
+        };
+        delete.execute(params![fact.report_id, fact.id])?;
+        insert.execute(params![
+            fact.report_id,
+            fact.id,
+            fact.concept_name,
+            fact.concept_label,
+            fact.entity_id,
+            fact.period.start_date().map(|d| d.to_string()),
+            fact.period.end_date().map(|d| d.to_string()),
+            value_type,
+            value,
+            fact.unit.as_ref().map(ToString::to_string),
+            fact.decimals.map(|d| match d {
+                Decimals::Finite(d) => d.to_string(),
+                Decimals::Infinite => "INF".to_string(),
+            }),
+            fact.precision.map(|p| match p {
+                Precision::Finite(p) => p.to_string(),
+                Precision::Infinite => "INF".to_string(),
+            }),
+            fact.filing_url
+        ])?;
+        for (dimension, member) in fact.dimensions.iter().flatten() {
+            insert_dimension.execute(params![fact.report_id, fact.id, dimension, member])?;
+        }
+    }
+    Ok(())
+}
+
+/// Save a node and its descendants depth-first, numbering them in save order
+fn save_node(
+    tx: &Transaction,
+    network_id: &str,
+    node: &NetworkNode,
+    parent_position: Option<i64>,
+    position: &mut i64,
+) -> rusqlite::Result<()> {
+    let own_position = *position;
+    *position += 1;
+    tx.prepare_cached(
+        "INSERT INTO network_nodes (network_id, position, parent_position, concept_name, concept_label, parent,
+         sort_order, level, preferred_label) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+    )?
+    .execute(params![
+        network_id,
+        own_position,
+        parent_position,
+        node.concept_name,
+        node.concept_label,
+        node.parent,
+        node.order,
+        node.level,
+        node.preferred_label
+    ])?;
+    for child in node.children.iter().flatten() {
+        save_node(tx, network_id, child, Some(own_position), position)?;
+    }
+    Ok(())
+}
+
+/// Rebuild node trees from `(position, parent position, node)` rows in save order
+fn build_tree(rows: Vec<(i64, Option<i64>, NetworkNode)>) -> Vec<NetworkNode> {
+    let mut children: HashMap<Option<i64>, Vec<(i64, NetworkNode)>> = HashMap::new();
+    for (position, parent, node) in rows {
+        children.entry(parent).or_default().push((position, node));
+    }
+
+    fn attach(
+        parent: Option<i64>,
+        children: &mut HashMap<Option<i64>, Vec<(i64, NetworkNode)>>,
+    ) -> Vec<NetworkNode> {
+        let nodes = children.remove(&parent).unwrap_or_default();
+        nodes
+            .into_iter()
+            .map(|(position, mut node)| {
+                let grandchildren = attach(Some(position), children);
+                node.children = (!grandchildren.is_empty()).then_some(grandchildren);
+                node
+            })
+            .collect()
+    }
+    attach(None, &mut children)
+}
+
+/// Raw `facts` row joined with one of its dimensions
+struct FactRow {
+    report_id: String,
+    id: String,
+    concept_name: String,
+    concept_label: Option<String>,
+    entity_id: String,
+    entity_name: Option<String>,
+    period_start: Option<String>,
+    period_end: Option<String>,
+    value_type: Option<String>,
+    value: Option<String>,
+    unit: Option<String>,
+    decimals: Option<String>,
+    precision: Option<String>,
+    filing_url: Option<String>,
+    dimension: Option<String>,
+    member: Option<String>,
+}
+
+impl FactRow {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            report_id: row.get(0)?,
+            id: row.get(1)?,
+            concept_name: row.get(2)?,
+            concept_label: row.get(3)?,
+            entity_id: row.get(4)?,
+            entity_name: row.get(5)?,
+            period_start: row.get(6)?,
+            period_end: row.get(7)?,
+            value_type: row.get(8)?,
+            value: row.get(9)?,
+            unit: row.get(10)?,
+            decimals: row.get(11)?,
+            precision: row.get(12)?,
+            filing_url: row.get(13)?,
+            dimension: row.get(14)?,
+            member: row.get(15)?,
+        })
+    }
+
+    fn into_fact(self) -> XbrlResult<Fact> {
+        let corrupt = |what: &str| XbrlApiError::ParseError(format!("fact {} has an invalid stored {}", self.id, what));
+        let period = match (&self.period_start, &self.period_end) {
+            (Some(start), Some(end)) => Period::duration(parse_date(start)?, parse_date(end)?)?,
+            (None, Some(end)) => Period::Instant(parse_date(end)?),
+            _ => Period::Forever,
+        };
+        let value = match (self.value_type.as_deref(), self.value.as_deref()) {
+            (Some("decimal"), Some(value)) => FactValue::Decimal(Decimal::from_str(value).map_err(|_| corrupt("value"))?),
+            (Some("number"), Some(value)) => FactValue::Number(value.parse().map_err(|_| corrupt("value"))?),
+            (Some("boolean"), Some(value)) => FactValue::Boolean(value == "true"),
+            (Some(_), Some(value)) => FactValue::String(value.to_string()),
+            _ => FactValue::Nil,
+        };
+        let decimals = match self.decimals.as_deref() {
+            None => None,
+            Some("INF") => Some(Decimals::Infinite),
+            Some(value) => Some(Decimals::Finite(value.parse().map_err(|_| corrupt("decimals"))?)),
+        };
+        let precision = match self.precision.as_deref() {
+            None => None,
+            Some("INF") => Some(Precision::Infinite),
+            Some(value) => Some(Precision::Finite(value.parse().map_err(|_| corrupt("precision"))?)),
+        };
+
+        Ok(Fact {
+            unit: self.unit.as_deref().map(Unit::parse_lenient),
+            id: self.id,
+            concept_name: self.concept_name,
+            concept_label: self.concept_label,
+            entity_id: self.entity_id,
+            entity_name: self.entity_name,
+            period,
+            value,
+            decimals,
+            precision,
+            dimensions: None,
+            report_id: self.report_id,
+            filing_url: self.filing_url,
+        })
+    }
+}
+
+fn sqlite_error(error: rusqlite::Error) -> XbrlApiError {
+    XbrlApiError::Storage {
+        backend: "SQLite",
+        source: Box::new(error),
+    }
+}
@@ -0,0 +1,150 @@
+use super::{save_facts, save_report, sqlite_error, Store};
+use crate::api::client::XbrlClient;
+use crate::data::period::parse_date;
+use crate::utils::errors::XbrlResult;
+use chrono::{NaiveDate, Utc};
+use futures::TryStreamExt;
+use rusqlite::{params, OptionalExtension};
+
+/// Page size used when listing reports and facts
+const DEFAULT_PAGE_SIZE: u32 = 500;
+
+/// What [`Store::sync`] fetches
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    taxonomies: Vec<String>,
+    page_size: u32,
+    definitions: bool,
+}
+
+impl SyncOptions {
+    /// Sync the reports and facts of one taxonomy
+    pub fn new(taxonomy: &str) -> Self {
+        Self {
+            taxonomies: vec![taxonomy.to_string()],
+            page_size: DEFAULT_PAGE_SIZE,
+            definitions: true,
+        }
+    }
+
+    /// Also sync another taxonomy
+    pub fn taxonomy(mut self, taxonomy: &str) -> Self {
+        self.taxonomies.push(taxonomy.to_string());
+        self
+    }
+
+    /// Page size for report and fact listings
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Whether to fetch taxonomy metadata, concepts and networks the store does
+    /// not have yet (default `true`)
+    pub fn definitions(mut self, definitions: bool) -> Self {
+        self.definitions = definitions;
+        self
+    }
+}
+
+/// Outcome of [`Store::sync`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    /// Reports whose facts were downloaded
+    pub reports: usize,
+    /// Facts saved
+    pub facts: usize,
+    /// Listed reports skipped because they were already stored or filed before the last sync
+    pub skipped_reports: usize,
+    /// Concepts saved
+    pub concepts: usize,
+    /// Networks saved
+    pub networks: usize,
+}
+
+impl Store {
+    /// Fetch reports filed since the last sync, with their facts
+    ///
+    /// The API has neither a filing-date filter nor a guaranteed listing order,
+    /// so every sync pages through each taxonomy's full report listing: about
+    /// `reports / page_size` requests, however few reports are new. Facts,
+    /// which make up almost all of the traffic, are downloaded only for
+    /// reports filed on or after the latest filing date seen by the previous
+    /// sync and not stored yet. The listing is processed page by page rather
+    /// than held in memory, and each report is saved together with its facts,
+    /// so an interrupted sync resumes where it stopped.
+    pub async fn sync(&mut self, client: &XbrlClient, options: &SyncOptions) -> XbrlResult<SyncSummary> {
+        let mut summary = SyncSummary::default();
+
+        if options.definitions && self.taxonomies()?.is_empty() {
+            self.save_taxonomies(&client.get_taxonomies().await?)?;
+        }
+
+        for taxonomy in &options.taxonomies {
+            if options.definitions && self.concepts(taxonomy)?.is_empty() {
+                let concepts = client.get_concepts(taxonomy).await?;
+                self.save_concepts(&concepts)?;
+                summary.concepts += concepts.len();
+
+                let networks = client.get_networks(taxonomy).await?;
+                self.save_networks(&networks)?;
+                summary.networks += networks.len();
+            }
+
+            let watermark = self.last_filing_date(taxonomy)?;
+            let mut listed = std::pin::pin!(client.stream_reports(taxonomy, options.page_size));
+            while let Some(report) = listed.try_next().await? {
+                let filed_before = watermark.is_some_and(|watermark| {
+                    parse_date(&report.filing_date).is_ok_and(|filing_date| filing_date < watermark)
+                });
+                if filed_before || self.report(&report.id)?.is_some() {
+                    summary.skipped_reports += 1;
+                    continue;
+                }
+
+                let mut facts: Vec<_> = client.stream_facts(&report.id, options.page_size).try_collect().await?;
+                for fact in &mut facts {
+                    fact.report_id.clone_from(&report.id);
+                }
+                self.write(|tx| {
+                    save_facts(tx, &facts)?;
+                    save_report(tx, &report)
+                })?;
+                summary.reports += 1;
+                summary.facts += facts.len();
+            }
+
+            self.record_sync(taxonomy)?;
+        }
+        Ok(summary)
+    }
+
+    /// Latest filing date among the reports stored by the last sync of a taxonomy
+    pub fn last_filing_date(&self, taxonomy: &str) -> XbrlResult<Option<NaiveDate>> {
+        let date: Option<Option<String>> = self
+            .connection
+            .query_row(
+                "SELECT last_filing_date FROM sync_state WHERE taxonomy = ?1",
+                params![taxonomy],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(sqlite_error)?;
+        date.flatten().map(|date| parse_date(&date)).transpose()
+    }
+
+    fn record_sync(&mut self, taxonomy: &str) -> XbrlResult<()> {
+        let latest = self
+            .reports(taxonomy)?
+            .iter()
+            .filter_map(|report| parse_date(&report.filing_date).ok())
+            .max();
+        self.write(|tx| {
+            tx.execute(
+                "INSERT OR REPLACE INTO sync_state (taxonomy, last_filing_date, last_synced_at) VALUES (?1, ?2, ?3)",
+                params![taxonomy, latest.map(|d| d.to_string()), Utc::now().to_rfc3339()],
+            )?;
+            Ok(())
+        })
+    }
+}
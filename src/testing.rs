@@ -0,0 +1,423 @@
+//! Mock XBRL US API server for testing integrations (`testing` feature)
+//!
+//! [`MockXbrlServer`] serves taxonomies, reports, facts, concepts, dimensions
+//! and networks registered as ordinary Rust values, and can inject failures,
+//! latency and pagination:
+//!
+//! ```no_run
+//! # async fn example(taxonomy: xbrl_api_client::Taxonomy, facts: Vec<xbrl_api_client::Fact>) {
+//! use std::time::Duration;
+//! use xbrl_api_client::testing::{MockXbrlServer, Route};
+//!
+//! let server = MockXbrlServer::builder()
+//!     .taxonomies(&[taxonomy])
+//!     .facts("rpt-1", &facts)
+//!     .fail_times(Route::Facts, 503, 2)
+//!     .latency(Duration::from_millis(20))
+//!     .max_page_size(100)
+//!     .start()
+//!     .await;
+//!
+//! let client = server.client();
+//! # }
+//! ```
+
+use crate::api::client::XbrlClient;
+use crate::api::models::{PageInfo, SearchParams};
+use crate::data::facts::Fact;
+use crate::data::reports::Report;
+use crate::data::taxonomy::{Concept, Dimension, Network, Taxonomy};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::any;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+/// API key accepted by [`MockXbrlServer::client`]
+pub const TEST_API_KEY: &str = "test_api_key";
+
+/// Endpoint served by the mock, for failure injection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Route {
+    Taxonomies,
+    Reports,
+    Facts,
+    Concepts,
+    Dimensions,
+    Networks,
+    Search,
+}
+
+impl Route {
+    fn from_segment(segment: &str) -> Option<Self> {
+        match segment {
+            "taxonomies" => Some(Self::Taxonomies),
+            "reports" => Some(Self::Reports),
+            "facts" => Some(Self::Facts),
+            "concepts" => Some(Self::Concepts),
+            "dimensions" => Some(Self::Dimensions),
+            "networks" => Some(Self::Networks),
+            "search" => Some(Self::Search),
+            _ => None,
+        }
+    }
+}
+
+/// Injected failure
+#[derive(Debug)]
+struct Failure {
+    route: Route,
+    status: u16,
+    /// Remaining failing requests, or `None` to fail every request
+    remaining: Option<AtomicUsize>,
+    retry_after: Option<u64>,
+}
+
+impl Failure {
+    /// Whether this request fails, consuming one of the remaining failures
+    fn applies(&self, route: Route) -> bool {
+        if self.route != route {
+            return false;
+        }
+        match &self.remaining {
+            None => true,
+            Some(remaining) => remaining
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok(),
+        }
+    }
+}
+
+/// Data and behaviour of a mock server
+#[derive(Debug, Default)]
+struct MockState {
+    taxonomies: Vec<Value>,
+    reports: HashMap<String, Vec<Value>>,
+    facts: HashMap<String, Vec<Value>>,
+    concepts: HashMap<String, Vec<Value>>,
+    dimensions: HashMap<String, Vec<Value>>,
+    networks: HashMap<String, Vec<Value>>,
+    search_results: Option<Vec<Value>>,
+    failures: Vec<Failure>,
+    latency: Option<Duration>,
+    max_page_size: Option<usize>,
+    api_key: Option<String>,
+}
+
+/// Builder registering the contents of a [`MockXbrlServer`]
+///
+/// Registering the same taxonomy, report or endpoint again appends to what
+/// is already there.
+#[derive(Debug, Default)]
+pub struct MockXbrlServerBuilder {
+    state: MockState,
+}
+
+impl MockXbrlServerBuilder {
+    /// Serve `taxonomies` from `/taxonomies`
+    pub fn taxonomies(mut self, taxonomies: &[Taxonomy]) -> Self {
+        self.state.taxonomies.extend(to_values(taxonomies));
+        self
+    }
+
+    /// Serve `reports` from `/reports?taxonomy=...`
+    pub fn reports(mut self, taxonomy: &str, reports: &[Report]) -> Self {
+        extend(&mut self.state.reports, taxonomy, reports);
+        self
+    }
+
+    /// Serve `facts` from `/facts?reportId=...`
+    pub fn facts(mut self, report_id: &str, facts: &[Fact]) -> Self {
+        extend(&mut self.state.facts, report_id, facts);
+        self
+    }
+
+    /// Serve `concepts` from `/concepts?taxonomy=...` and each one from `/concepts/{name}`
+    pub fn concepts(mut self, taxonomy: &str, concepts: &[Concept]) -> Self {
+        extend(&mut self.state.concepts, taxonomy, concepts);
+        self
+    }
+
+    /// Serve `dimensions` from `/dimensions?taxonomy=...`
+    pub fn dimensions(mut self, taxonomy: &str, dimensions: &[Dimension]) -> Self {
+        extend(&mut self.state.dimensions, taxonomy, dimensions);
+        self
+    }
+
+    /// Serve `networks` from `/networks?taxonomy=...` and each one from `/networks/{id}`
+    pub fn networks(mut self, taxonomy: &str, networks: &[Network]) -> Self {
+        extend(&mut self.state.networks, taxonomy, networks);
+        self
+    }
+
+    /// Answer every search with `facts`
+    ///
+    /// Without this, searches filter all registered facts by concept, entity,
+    /// dimension, member, text and value bounds.
+    pub fn search_results(mut self, facts: &[Fact]) -> Self {
+        self.state.search_results.get_or_insert_with(Vec::new).extend(to_values(facts));
+        self
+    }
+
+    /// Answer every request to `route` with `status`
+    pub fn fail(mut self, route: Route, status: u16) -> Self {
+        self.state.failures.push(Failure {
+            route,
+            status,
+            remaining: None,
+            retry_after: None,
+        });
+        self
+    }
+
+    /// Answer the next `times` requests to `route` with `status`, then recover
+    pub fn fail_times(mut self, route: Route, status: u16, times: usize) -> Self {
+        self.state.failures.push(Failure {
+            route,
+            status,
+            remaining: Some(AtomicUsize::new(times)),
+            retry_after: None,
+        });
+        self
+    }
+
+    /// Answer the next `times` requests to `route` with `429` and a `Retry-After` header
+    pub fn rate_limit(mut self, route: Route, times: usize, retry_after: Duration) -> Self {
+        self.state.failures.push(Failure {
+            route,
+            status: 429,
+            remaining: Some(AtomicUsize::new(times)),
+            retry_after: Some(retry_after.as_secs()),
+        });
+        self
+    }
+
+    /// Delay every response by `latency`
+    pub fn latency(mut self, latency: Duration) -> Self {
+        self.state.latency = Some(latency);
+        self
+    }
+
+    /// Split listings into pages of at most `page_size` items, even when the
+    /// client asks for more or for no paging at all
+    pub fn max_page_size(mut self, page_size: usize) -> Self {
+        self.state.max_page_size = Some(page_size.max(1));
+        self
+    }
+
+    /// Answer `401` unless the `X-API-KEY` header is `api_key`
+    pub fn require_api_key(mut self, api_key: &str) -> Self {
+        self.state.api_key = Some(api_key.to_string());
+        self
+    }
+
+    /// Start the server
+    pub async fn start(self) -> MockXbrlServer {
+        let server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(Responder {
+                state: Arc::new(self.state),
+            })
+            .mount(&server)
+            .await;
+        MockXbrlServer { server }
+    }
+}
+
+/// Mock XBRL US API server
+///
+/// The server stops when dropped.
+pub struct MockXbrlServer {
+    server: MockServer,
+}
+
+impl MockXbrlServer {
+    /// Create a builder for the server contents
+    pub fn builder() -> MockXbrlServerBuilder {
+        MockXbrlServerBuilder::default()
+    }
+
+    /// Base URL of the server
+    pub fn url(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Client using [`TEST_API_KEY`] against this server
+    pub fn client(&self) -> XbrlClient {
+        XbrlClient::with_base_url(TEST_API_KEY, &self.url()).expect("mock server URL is valid")
+    }
+
+    /// Requests received so far
+    pub async fn received_requests(&self) -> Vec<Request> {
+        self.server.received_requests().await.unwrap_or_default()
+    }
+
+    /// The underlying wiremock server, for mounting additional mocks
+    pub fn inner(&self) -> &MockServer {
+        &self.server
+    }
+}
+
+struct Responder {
+    state: Arc<MockState>,
+}
+
+impl Respond for Responder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let response = self.route(request);
+        match self.state.latency {
+            Some(latency) => response.set_delay(latency),
+            None => response,
+        }
+    }
+}
+
+impl Responder {
+    fn route(&self, request: &Request) -> ResponseTemplate {
+        let state = &self.state;
+        if let Some(api_key) = &state.api_key {
+            let given = request.headers.get("x-api-key").and_then(|value| value.to_str().ok());
+            if given != Some(api_key.as_str()) {
+                return error(401, "Unauthorized. Invalid API key.");
+            }
+        }
+
+        let segments: Vec<&str> = request.url.path_segments().into_iter().flatten().collect();
+        let Some(route) = segments.first().and_then(|segment| Route::from_segment(segment)) else {
+            return error(404, "Unknown endpoint");
+        };
+        if let Some(failure) = state.failures.iter().find(|failure| failure.applies(route)) {
+            let response = error(failure.status, "Injected failure");
+            return match failure.retry_after {
+                Some(seconds) => response.insert_header("Retry-After", seconds.to_string()),
+                None => response,
+            };
+        }
+
+        let query: HashMap<String, String> = request.url.query_pairs().into_owned().collect();
+        let param = |name: &str| query.get(name).map(String::as_str).unwrap_or_default();
+        let detail = segments.get(1).copied();
+
+        match (route, detail) {
+            (Route::Taxonomies, None) => self.page(&state.taxonomies, &query),
+            (Route::Reports, None) => self.listing(&state.reports, param("taxonomy"), &query),
+            (Route::Facts, None) => self.listing(&state.facts, param("reportId"), &query),
+            (Route::Concepts, None) => self.listing(&state.concepts, param("taxonomy"), &query),
+            (Route::Dimensions, None) => self.listing(&state.dimensions, param("taxonomy"), &query),
+            (Route::Networks, None) => self.listing(&state.networks, param("taxonomy"), &query),
+            (Route::Concepts, Some(name)) => {
+                let mut concepts = state.concepts.get(param("taxonomy")).into_iter().flatten();
+                item(concepts.find(|concept| concept["name"] == name), "Concept not found")
+            }
+            (Route::Networks, Some(id)) => {
+                let mut networks = state.networks.values().flatten();
+                item(networks.find(|network| network["id"] == id), "Network not found")
+            }
+            (Route::Search, None) => match serde_json::from_slice::<SearchParams>(&request.body) {
+                Ok(params) => {
+                    let results: Vec<Value> = match &state.search_results {
+                        Some(results) => results.clone(),
+                        None => state.facts.values().flatten().filter(|fact| matches(fact, &params)).cloned().collect(),
+                    };
+                    self.page(&results, &query)
+                }
+                Err(e) => error(400, &format!("Invalid search parameters: {}", e)),
+            },
+            _ => error(404, "Unknown endpoint"),
+        }
+    }
+
+    fn listing(&self, items: &HashMap<String, Vec<Value>>, key: &str, query: &HashMap<String, String>) -> ResponseTemplate {
+        self.page(items.get(key).map(Vec::as_slice).unwrap_or_default(), query)
+    }
+
+    /// One page of `items` with its pagination metadata
+    fn page(&self, items: &[Value], query: &HashMap<String, String>) -> ResponseTemplate {
+        let requested_size = query.get("pageSize").and_then(|size| size.parse::<usize>().ok());
+        let page_size = match (requested_size, self.state.max_page_size) {
+            (Some(requested), Some(max)) => Some(requested.clamp(1, max)),
+            (requested, max) => requested.or(max).map(|size| size.max(1)),
+        };
+        let Some(page_size) = page_size else {
+            return success(Value::Array(items.to_vec()), None);
+        };
+
+        let page = query.get("page").and_then(|page| page.parse::<usize>().ok()).unwrap_or(1).max(1);
+        let data: Vec<Value> = items.iter().skip((page - 1) * page_size).take(page_size).cloned().collect();
+        let info = PageInfo {
+            page: Some(page as u32),
+            page_size: Some(page_size as u32),
+            total_count: Some(items.len() as u64),
+            total_pages: Some(items.len().div_ceil(page_size) as u32),
+        };
+        success(Value::Array(data), Some(info))
+    }
+}
+
+/// Whether a fact matches the search filters the mock understands
+fn matches(fact: &Value, params: &SearchParams) -> bool {
+    let text = |field: &str| fact[field].as_str().unwrap_or_default();
+    let dimensions = fact["dimensions"].as_object();
+    let value = match &fact["value"] {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    };
+
+    params.concept_name.as_deref().is_none_or(|name| text("concept_name") == name)
+        && params.entity_id.as_deref().is_none_or(|id| text("entity_id") == id)
+        && params
+            .dimension_name
+            .as_deref()
+            .is_none_or(|axis| dimensions.is_some_and(|dims| dims.contains_key(axis)))
+        && params
+            .member_name
+            .as_deref()
+            .is_none_or(|member| dimensions.is_some_and(|dims| dims.values().any(|value| value == member)))
+        && params
+            .text_search
+            .as_deref()
+            .is_none_or(|search| fact["value"].as_str().is_some_and(|value| value.contains(search)))
+        && params.value_greater_than.is_none_or(|bound| value.is_some_and(|value| value > bound))
+        && params.value_less_than.is_none_or(|bound| value.is_some_and(|value| value < bound))
+}
+
+fn to_values<T: Serialize>(items: &[T]) -> Vec<Value> {
+    items
+        .iter()
+        .map(|item| serde_json::to_value(item).expect("XBRL data types serialize to JSON"))
+        .collect()
+}
+
+fn extend<T: Serialize>(map: &mut HashMap<String, Vec<Value>>, key: &str, items: &[T]) {
+    map.entry(key.to_string()).or_default().extend(to_values(items));
+}
+
+fn item(value: Option<&Value>, missing: &str) -> ResponseTemplate {
+    match value {
+        Some(value) => success(value.clone(), None),
+        None => error(404, missing),
+    }
+}
+
+fn success(data: Value, pagination: Option<PageInfo>) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({
+        "status": "success",
+        "message": null,
+        "data": data,
+        "errors": null,
+        "pagination": pagination,
+    }))
+}
+
+fn error(status: u16, message: &str) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_json(json!({
+        "status": "error",
+        "message": message,
+        "data": null,
+        "errors": [message],
+    }))
+}
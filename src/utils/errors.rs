@@ -1,31 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
+/// Request, response and local failures
+///
+/// Failed API responses are decoded into a variant per status class. Each
+/// carries the method and URL of the request, and the message the API gave
+/// in its JSON error body (or the raw body when it was not JSON). Local data
+/// sources report missing items as [`XbrlApiError::NotFound`] with the
+/// endpoint path they stand in for.
 #[derive(Error, Debug)]
 #[allow(dead_code)]
 pub enum XbrlApiError {
     #[error("HTTP error: {0}")]
     HttpError(#[from] reqwest::Error),
-    
-    #[error("API error: {status_code} - {message}")]
+
+    /// Any other unsuccessful status, or a successful one without data
+    #[error("API error: {status_code} - {message} ({method} {url})")]
     ApiError {
         status_code: u16,
         message: String,
+        method: String,
+        url: String,
+    },
+
+    /// 429 Too Many Requests
+    #[error("Rate limited: {message} ({method} {url}{})", retry_hint(.retry_after))]
+    RateLimited {
+        /// Delay asked for by the `Retry-After` header
+        retry_after: Option<Duration>,
+        message: String,
+        method: String,
+        url: String,
+    },
+
+    /// 404 Not Found
+    #[error("Not found: {message} ({method} {url})")]
+    NotFound {
+        message: String,
+        method: String,
+        url: String,
+    },
+
+    /// 401 Unauthorized
+    #[error("Unauthorized: {message} ({method} {url})")]
+    Unauthorized {
+        message: String,
+        method: String,
+        url: String,
+    },
+
+    /// 403 Forbidden
+    #[error("Forbidden: {message} ({method} {url})")]
+    Forbidden {
+        message: String,
+        method: String,
+        url: String,
+    },
+
+    /// 400 Bad Request or 422 Unprocessable Entity
+    #[error("Bad request: {status_code} - {message} ({method} {url})")]
+    BadRequest {
+        status_code: u16,
+        /// Entries of the response's `errors` array
+        field_errors: Vec<FieldError>,
+        message: String,
+        method: String,
+        url: String,
     },
-    
+
+    /// 5xx status
+    #[error("Server error: {status_code} - {message} ({method} {url})")]
+    ServerError {
+        status_code: u16,
+        message: String,
+        method: String,
+        url: String,
+    },
+
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
-    
+
     #[error("URL error: {0}")]
     UrlError(#[from] url::ParseError),
-    
+
     #[error("Authentication error: {0}")]
     AuthError(String),
-    
+
     #[error("Validation error: {0}")]
     ValidationError(String),
-    
+
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    /// Cassette file could not be read or written
+    #[error("Cassette error at {}: {source}", path.display())]
+    Cassette {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// Local file or directory could not be read or written
+    #[error("I/O error at {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// Local store or export format failed, such as SQLite or Parquet
+    #[error("{backend} error: {source}")]
+    Storage {
+        /// Name of the failing library, such as `SQLite`
+        backend: &'static str,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// Command output could not be written
+    #[error("Output error: {0}")]
+    Output(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// Replayed request that the cassette has no interaction for
+    #[error("No cassette interaction for {method} {url}")]
+    CassetteMiss {
+        method: String,
+        url: String,
+    },
+
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
 
-pub type XbrlResult<T> = Result<T, XbrlApiError>;
\ No newline at end of file
+impl XbrlApiError {
+    /// HTTP status the error was decoded from
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            Self::HttpError(error) => error.status().map(|status| status.as_u16()),
+            Self::ApiError { status_code, .. }
+            | Self::BadRequest { status_code, .. }
+            | Self::ServerError { status_code, .. } => Some(*status_code),
+            Self::RateLimited { .. } => Some(429),
+            Self::NotFound { .. } => Some(404),
+            Self::Unauthorized { .. } => Some(401),
+            Self::Forbidden { .. } => Some(403),
+            _ => None,
+        }
+    }
+
+    /// Method and URL of the request that failed
+    pub fn request(&self) -> Option<(&str, &str)> {
+        match self {
+            Self::ApiError { method, url, .. }
+            | Self::RateLimited { method, url, .. }
+            | Self::NotFound { method, url, .. }
+            | Self::Unauthorized { method, url, .. }
+            | Self::Forbidden { method, url, .. }
+            | Self::BadRequest { method, url, .. }
+            | Self::ServerError { method, url, .. }
+            | Self::CassetteMiss { method, url } => Some((method, url)),
+            _ => None,
+        }
+    }
+}
+
+/// Entry of the `errors` array in an API response
+///
+/// The API sends plain messages; validation errors may instead be objects
+/// naming the offending `field` (or `param`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "RawFieldError")]
+pub struct FieldError {
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "{}: {}", field, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawFieldError {
+    Message(String),
+    Field {
+        #[serde(alias = "param")]
+        field: Option<String>,
+        #[serde(alias = "error")]
+        message: String,
+    },
+}
+
+impl From<RawFieldError> for FieldError {
+    fn from(raw: RawFieldError) -> Self {
+        match raw {
+            RawFieldError::Message(message) => Self { field: None, message },
+            RawFieldError::Field { field, message } => Self { field, message },
+        }
+    }
+}
+
+fn retry_hint(retry_after: &Option<Duration>) -> String {
+    retry_after.map(|delay| format!(", retry after {}s", delay.as_secs())).unwrap_or_default()
+}
+
+pub type XbrlResult<T> = Result<T, XbrlApiError>;
@@ -0,0 +1,202 @@
+use wiremock::matchers::{body_string_contains, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::auth::{OAuth2Authenticator, OAuth2Credentials};
+use xbrl_api_client::{XbrlApiError, XbrlClient};
+
+fn taxonomies_body() -> serde_json::Value {
+    serde_json::json!({
+        "status": "success",
+        "message": null,
+        "data": [{ "name": "us-gaap", "description": "US GAAP Taxonomy", "version": "2022" }],
+        "errors": null
+    })
+}
+
+fn token_body(access_token: &str, refresh_token: &str, expires_in: u64) -> serde_json::Value {
+    serde_json::json!({
+        "access_token": access_token,
+        "refresh_token": refresh_token,
+        "expires_in": expires_in,
+        "token_type": "Bearer"
+    })
+}
+
+fn credentials() -> OAuth2Credentials {
+    OAuth2Credentials {
+        client_id: "client-id".to_string(),
+        client_secret: "client-secret".to_string(),
+        username: "analyst@example.com".to_string(),
+        password: "hunter2".to_string(),
+        platform: Some("pc".to_string()),
+    }
+}
+
+fn oauth_client(server: &MockServer) -> XbrlClient {
+    let authenticator =
+        OAuth2Authenticator::new(credentials()).with_token_url(&format!("{}/oauth2/token", server.uri()));
+    XbrlClient::builder()
+        .authenticator(authenticator)
+        .base_url(&server.uri())
+        .build()
+        .unwrap()
+}
+
+async fn mount_token(server: &MockServer, grant_type: &str, body: serde_json::Value, expected_calls: u64) {
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .and(body_string_contains(format!("grant_type={}", grant_type)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .expect(expected_calls)
+        .mount(server)
+        .await;
+}
+
+async fn mount_taxonomies_for_token(server: &MockServer, token: &str, status: u16) {
+    let response = if status == 200 {
+        ResponseTemplate::new(200).set_body_json(taxonomies_body())
+    } else {
+        ResponseTemplate::new(status)
+    };
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .and(header("Authorization", format!("Bearer {}", token).as_str()))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_api_key_header_is_sent() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .and(header("X-API-KEY", "test_api_key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(taxonomies_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = XbrlClient::with_base_url("test_api_key", &server.uri()).unwrap();
+    assert_eq!(client.get_taxonomies().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_oauth2_token_is_fetched_once_and_reused() {
+    let server = MockServer::start().await;
+    mount_token(&server, "password", token_body("token-1", "refresh-1", 3600), 1).await;
+    mount_taxonomies_for_token(&server, "token-1", 200).await;
+
+    let client = oauth_client(&server);
+    client.get_taxonomies().await.unwrap();
+    client.clone().get_taxonomies().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_oauth2_expired_token_is_refreshed() {
+    let server = MockServer::start().await;
+    // A zero lifetime means the token is already stale on the next request
+    mount_token(&server, "password", token_body("token-1", "refresh-1", 0), 1).await;
+    mount_token(&server, "refresh_token", token_body("token-2", "refresh-2", 3600), 1).await;
+    mount_taxonomies_for_token(&server, "token-1", 200).await;
+    mount_taxonomies_for_token(&server, "token-2", 200).await;
+
+    let client = oauth_client(&server);
+    client.get_taxonomies().await.unwrap();
+    client.get_taxonomies().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_oauth2_unauthorized_response_triggers_refresh() {
+    let server = MockServer::start().await;
+    mount_token(&server, "password", token_body("token-1", "refresh-1", 3600), 1).await;
+    mount_token(&server, "refresh_token", token_body("token-2", "refresh-2", 3600), 1).await;
+    mount_taxonomies_for_token(&server, "token-1", 401).await;
+    mount_taxonomies_for_token(&server, "token-2", 200).await;
+
+    let client = oauth_client(&server);
+    let taxonomies = client.get_taxonomies().await.unwrap();
+    assert_eq!(taxonomies[0].name, "us-gaap");
+}
+
+#[tokio::test]
+async fn test_oauth2_rejected_refresh_falls_back_to_login() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .and(body_string_contains("grant_type=refresh_token"))
+        .respond_with(ResponseTemplate::new(400))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .and(body_string_contains("grant_type=password"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(token_body("token-1", "refresh-1", 0)))
+        .expect(2)
+        .mount(&server)
+        .await;
+    mount_taxonomies_for_token(&server, "token-1", 200).await;
+
+    let client = oauth_client(&server);
+    client.get_taxonomies().await.unwrap();
+    client.get_taxonomies().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_oauth2_login_failure_is_an_auth_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("invalid_client"))
+        .mount(&server)
+        .await;
+
+    let client = oauth_client(&server);
+    match client.get_taxonomies().await {
+        Err(XbrlApiError::AuthError(message)) => {
+            assert!(message.contains("401"));
+            assert!(message.contains("invalid_client"));
+        }
+        other => panic!("Expected an authentication error but got: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_token_requests_use_the_configured_http_client() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/oauth2/token"))
+        .and(header("user-agent", "filings-bot/1.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "access_token": "token-1" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mount_taxonomies_for_token(&server, "token-1", 200).await;
+
+    let authenticator =
+        OAuth2Authenticator::new(credentials()).with_token_url(&format!("{}/oauth2/token", server.uri()));
+    let client = XbrlClient::builder()
+        .authenticator(authenticator)
+        .base_url(&server.uri())
+        .user_agent("filings-bot/1.0")
+        .build()
+        .unwrap();
+    client.get_taxonomies().await.unwrap();
+}
+
+#[test]
+fn test_credentials_are_redacted_in_debug_output() {
+    let debug = format!("{:?}", OAuth2Authenticator::new(credentials()));
+    assert!(!debug.contains("hunter2"));
+    assert!(!debug.contains("client-secret"));
+    assert!(debug.contains("client-id"));
+}
+
+#[test]
+fn test_api_key_and_authenticator_are_exclusive() {
+    let result = XbrlClient::builder()
+        .api_key("test_api_key")
+        .authenticator(OAuth2Authenticator::new(credentials()))
+        .build();
+    assert!(matches!(result, Err(XbrlApiError::ValidationError(_))));
+}
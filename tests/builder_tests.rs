@@ -0,0 +1,91 @@
+mod fixtures;
+
+use fixtures::fixture;
+use std::time::Duration;
+use xbrl_api_client::testing::MockXbrlServer;
+use xbrl_api_client::{api::client::XbrlClientBuilder, utils::errors::XbrlApiError, Taxonomy, XbrlClient};
+
+fn assert_validation_error(result: Result<XbrlClient, XbrlApiError>, expected: &str) {
+    match result {
+        Err(XbrlApiError::ValidationError(message)) => {
+            assert!(message.contains(expected), "unexpected message: {}", message);
+        }
+        Err(e) => panic!("Expected a validation error but got: {:?}", e),
+        Ok(_) => panic!("Expected a validation error but the client was built"),
+    }
+}
+
+#[test]
+fn test_builder_defaults() {
+    let client = XbrlClient::builder().api_key("test_api_key").build().unwrap();
+    assert_eq!(client.base_url(), "https://api.xbrl.us/api/v1");
+}
+
+#[test]
+fn test_builder_with_transport_settings() {
+    let client = XbrlClientBuilder::new()
+        .api_key("test_api_key")
+        .base_url("http://localhost:8080/api/")
+        .timeout(Duration::from_secs(30))
+        .connect_timeout(Duration::from_secs(5))
+        .user_agent("xbrl-tests/1.0")
+        .proxy("http://proxy.example.com:3128")
+        .pool_max_idle_per_host(4)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .build()
+        .unwrap();
+
+    // Trailing slashes are trimmed so endpoint paths join cleanly
+    assert_eq!(client.base_url(), "http://localhost:8080/api");
+}
+
+#[test]
+fn test_builder_requires_api_key() {
+    assert_validation_error(XbrlClient::builder().build(), "API key");
+    assert_validation_error(XbrlClient::builder().api_key("   ").build(), "API key");
+}
+
+#[test]
+fn test_builder_rejects_invalid_settings() {
+    let builder = || XbrlClient::builder().api_key("test_api_key");
+
+    assert_validation_error(builder().base_url("not a url").build(), "base URL");
+    assert_validation_error(builder().base_url("ftp://example.com").build(), "scheme 'ftp'");
+    assert_validation_error(builder().timeout(Duration::ZERO).build(), "timeout");
+    assert_validation_error(builder().connect_timeout(Duration::ZERO).build(), "connect timeout");
+    assert_validation_error(builder().user_agent("bad\nagent").build(), "user agent");
+    assert_validation_error(builder().proxy("::not-a-proxy").build(), "proxy URL");
+}
+
+#[test]
+fn test_builder_rejects_custom_client_with_transport_settings() {
+    let result = XbrlClient::builder()
+        .api_key("test_api_key")
+        .http_client(reqwest::Client::new())
+        .timeout(Duration::from_secs(10))
+        .build();
+
+    assert_validation_error(result, "custom reqwest::Client");
+}
+
+#[tokio::test]
+async fn test_builder_with_custom_http_client() {
+    let mock_server = MockXbrlServer::builder()
+        .taxonomies(&fixture::<Vec<Taxonomy>>("taxonomies.json"))
+        .start()
+        .await;
+
+    let http_client = reqwest::Client::builder()
+        .user_agent("custom-client")
+        .build()
+        .unwrap();
+    let client = XbrlClient::builder()
+        .api_key("test_api_key")
+        .base_url(&mock_server.url())
+        .http_client(http_client)
+        .build()
+        .unwrap();
+
+    let taxonomies = client.get_taxonomies().await.unwrap();
+    assert_eq!(taxonomies.len(), 3);
+}
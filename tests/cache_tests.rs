@@ -0,0 +1,223 @@
+use std::time::Duration;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::cache::{
+    CacheBackend, CacheMode, CachePolicy, CachedEndpoint, CachedResponse, DiskCache, MemoryCache, ResponseCache,
+};
+use xbrl_api_client::{XbrlApiError, XbrlClient};
+
+fn envelope(data: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "status": "success",
+        "message": null,
+        "data": data,
+        "errors": null
+    })
+}
+
+fn taxonomies() -> serde_json::Value {
+    envelope(serde_json::json!([
+        { "name": "us-gaap", "description": "US GAAP Taxonomy", "version": "2023" }
+    ]))
+}
+
+fn client(server: &MockServer, cache: ResponseCache) -> XbrlClient {
+    XbrlClient::builder()
+        .api_key("test_api_key")
+        .base_url(&server.uri())
+        .cache(cache)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_fresh_entries_are_served_from_cache() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(taxonomies()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server, ResponseCache::memory(16).unwrap());
+    let first = client.get_taxonomies().await.unwrap();
+    // Clones share the cache
+    let second = client.clone().get_taxonomies().await.unwrap();
+
+    assert_eq!(first.len(), 1);
+    assert_eq!(second[0].name, "us-gaap");
+}
+
+#[tokio::test]
+async fn test_stale_entries_are_revalidated_with_etag() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .and(header("If-None-Match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .with_priority(1)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "\"v1\"")
+                .set_body_json(taxonomies()),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let policy = CachePolicy::none().ttl(CachedEndpoint::Taxonomies, Duration::ZERO);
+    let cache = ResponseCache::memory(16).unwrap().with_policy(policy);
+    let client = client(&server, cache);
+
+    for _ in 0..3 {
+        let taxonomies = client.get_taxonomies().await.unwrap();
+        assert_eq!(taxonomies[0].version, "2023");
+    }
+}
+
+#[tokio::test]
+async fn test_cache_modes_refresh_and_bypass() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(taxonomies()))
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    let client = client(&server, ResponseCache::memory(16).unwrap());
+    client.get_taxonomies().await.unwrap();
+    client.get_taxonomies().await.unwrap();
+    client.with_cache_mode(CacheMode::Refresh).get_taxonomies().await.unwrap();
+    client.with_cache_mode(CacheMode::Bypass).get_taxonomies().await.unwrap();
+
+    // The bypassing call neither read nor replaced the refreshed entry
+    client.get_taxonomies().await.unwrap();
+    client.invalidate_cache(&client.endpoints().taxonomies()).await.unwrap();
+    client.get_taxonomies().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_entries_are_not_shared_between_credentials() {
+    let server = MockServer::start().await;
+    for api_key in ["key-a", "key-b"] {
+        Mock::given(method("GET"))
+            .and(path("/taxonomies"))
+            .and(header("X-API-KEY", api_key))
+            .respond_with(ResponseTemplate::new(200).set_body_json(taxonomies()))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let cache = ResponseCache::memory(16).unwrap();
+    let with_key = |api_key: &str| {
+        XbrlClient::builder()
+            .api_key(api_key)
+            .base_url(&server.uri())
+            .cache(cache.clone())
+            .build()
+            .unwrap()
+    };
+    with_key("key-a").get_taxonomies().await.unwrap();
+    with_key("key-b").get_taxonomies().await.unwrap();
+
+    // Clients with the same credentials do share entries
+    with_key("key-a").get_taxonomies().await.unwrap();
+    let mut client = XbrlClient::with_base_url("key-b", &server.uri()).unwrap();
+    client.set_cache(Some(cache));
+    client.get_taxonomies().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_uncached_endpoints_and_errors_reach_the_server() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/reports"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(envelope(serde_json::json!([]))))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/dimensions"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = XbrlClient::builder()
+        .api_key("test_api_key")
+        .base_url(&server.uri())
+        .retry_policy(xbrl_api_client::api::retry::RetryPolicy::none())
+        .cache(ResponseCache::memory(16).unwrap())
+        .build()
+        .unwrap();
+
+    client.get_reports("us-gaap").await.unwrap();
+    client.get_reports("us-gaap").await.unwrap();
+    assert!(client.get_dimensions("us-gaap").await.is_err());
+    assert!(client.get_dimensions("us-gaap").await.is_err());
+}
+
+#[tokio::test]
+async fn test_memory_cache_evicts_least_recently_used() {
+    let cache = MemoryCache::new(2).unwrap();
+    let entry = |body: &str| CachedResponse::new(body.as_bytes().to_vec(), None, None);
+
+    cache.put("a", entry("a")).await.unwrap();
+    cache.put("b", entry("b")).await.unwrap();
+    assert!(cache.get("a").await.unwrap().is_some());
+    cache.put("c", entry("c")).await.unwrap();
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("b").await.unwrap().is_none());
+    assert_eq!(cache.get("a").await.unwrap().unwrap().body, b"a");
+    assert!(MemoryCache::new(0).is_err());
+}
+
+#[tokio::test]
+async fn test_disk_cache_persists_entries() {
+    let directory = std::env::temp_dir().join(format!("xbrl-cache-{}", std::process::id()));
+    let entry = CachedResponse::new(b"{}".to_vec(), Some("\"v1\"".into()), Some("Tue, 01 Oct 2024 00:00:00 GMT".into()));
+
+    DiskCache::new(&directory).unwrap().put("key", entry.clone()).await.unwrap();
+    // Temporary files are renamed into place, not left behind
+    let mut names: Vec<String> = std::fs::read_dir(&directory)
+        .unwrap()
+        .map(|file| file.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names.len(), 2);
+    assert!(names[0].ends_with(".body") && names[1].ends_with(".json"), "{:?}", names);
+
+    let reopened = DiskCache::new(&directory).unwrap();
+    assert_eq!(reopened.get("key").await.unwrap(), Some(entry));
+    assert!(reopened.get("other").await.unwrap().is_none());
+
+    reopened.remove("key").await.unwrap();
+    assert!(reopened.get("key").await.unwrap().is_none());
+    reopened.clear().await.unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_disk_cache_io_errors_keep_the_path_and_source() {
+    let file = std::env::temp_dir().join(format!("xbrl-cache-file-{}", std::process::id()));
+    std::fs::write(&file, b"not a directory").unwrap();
+
+    let error = DiskCache::new(&file).unwrap_err();
+    std::fs::remove_file(&file).unwrap();
+    match error {
+        XbrlApiError::Io { path, source } => {
+            assert_eq!(path, file);
+            assert_eq!(source.kind(), std::io::ErrorKind::AlreadyExists);
+        }
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+}
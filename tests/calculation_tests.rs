@@ -0,0 +1,151 @@
+mod fixtures;
+
+use fixtures::{dec, fact_json, to_fact};
+use serde_json::json;
+use xbrl_api_client::data::calculation::check_calculations;
+use xbrl_api_client::data::facts::Decimals;
+use xbrl_api_client::data::taxonomy::{CalculationArc, CalculationNetwork};
+use xbrl_api_client::Fact;
+
+fn arc(parent: &str, child: &str, weight: f64) -> CalculationArc {
+    CalculationArc {
+        parent: parent.to_string(),
+        child: child.to_string(),
+        weight,
+        order: None,
+    }
+}
+
+fn gross_profit_network() -> CalculationNetwork {
+    CalculationNetwork {
+        role: "http://example.com/role/StatementsOfOperations".to_string(),
+        arcs: vec![
+            arc("GrossProfit", "Revenues", 1.0),
+            arc("GrossProfit", "CostOfRevenue", -1.0),
+        ],
+    }
+}
+
+fn fact(concept: &str, value: &str, decimals: i32) -> Fact {
+    let mut fact = fact_json(&format!("{}-{}", concept, value), concept, value);
+    fact["period_start"] = json!("2022-01-01");
+    fact["decimals"] = json!(decimals);
+    to_fact(fact)
+}
+
+fn with_segment(mut fact: Fact, member: &str) -> Fact {
+    fact.dimensions = Some([("srt:SegmentAxis".to_string(), member.to_string())].into());
+    fact
+}
+
+#[test]
+fn test_consistent_calculation_has_no_findings() {
+    let facts = vec![
+        fact("Revenues", "1000", 0),
+        fact("CostOfRevenue", "400", 0),
+        fact("GrossProfit", "600", 0),
+    ];
+    assert!(check_calculations(&gross_profit_network(), &facts).is_empty());
+}
+
+#[test]
+fn test_inconsistency_reports_expected_and_difference() {
+    let facts = vec![
+        fact("Revenues", "1000", 0),
+        fact("CostOfRevenue", "400", 0),
+        fact("GrossProfit", "650", 0),
+    ];
+    let findings = check_calculations(&gross_profit_network(), &facts);
+
+    assert_eq!(findings.len(), 1);
+    let finding = &findings[0];
+    assert_eq!(finding.parent, "GrossProfit");
+    assert_eq!(finding.expected, dec("600"));
+    assert_eq!(finding.reported, dec("650"));
+    assert_eq!(finding.difference, dec("50"));
+    assert_eq!(finding.decimals, Decimals::Finite(0));
+    assert_eq!(finding.contributors, ["Revenues", "CostOfRevenue"]);
+    assert_eq!(finding.unit.as_ref().unwrap().to_string(), "iso4217:USD");
+}
+
+#[test]
+fn test_rounding_to_the_totals_decimals() {
+    // Reported in thousands: 1,234,567 - 400,100 = 834,467 rounds to 834,000
+    let facts = vec![
+        fact("Revenues", "1234567", 0),
+        fact("CostOfRevenue", "400100", 0),
+        fact("GrossProfit", "834000", -3),
+    ];
+    assert!(check_calculations(&gross_profit_network(), &facts).is_empty());
+
+    let facts = vec![
+        fact("Revenues", "1234567", 0),
+        fact("CostOfRevenue", "400100", 0),
+        fact("GrossProfit", "835000", -3),
+    ];
+    let findings = check_calculations(&gross_profit_network(), &facts);
+    assert_eq!(findings[0].expected, dec("834000"));
+    assert_eq!(findings[0].difference, dec("1000"));
+}
+
+#[test]
+fn test_contexts_are_grouped_by_dimensions() {
+    let facts = vec![
+        fact("Revenues", "1000", 0),
+        fact("CostOfRevenue", "400", 0),
+        fact("GrossProfit", "600", 0),
+        with_segment(fact("Revenues", "300", 0), "Retail"),
+        with_segment(fact("CostOfRevenue", "100", 0), "Retail"),
+        with_segment(fact("GrossProfit", "250", 0), "Retail"),
+    ];
+    let findings = check_calculations(&gross_profit_network(), &facts);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].dimensions["srt:SegmentAxis"], "Retail");
+    assert_eq!(findings[0].expected, dec("200"));
+}
+
+#[test]
+fn test_unbound_and_duplicate_facts_are_skipped() {
+    // No contributing items reported: nothing to check
+    let facts = vec![fact("GrossProfit", "600", 0)];
+    assert!(check_calculations(&gross_profit_network(), &facts).is_empty());
+
+    // Inconsistent duplicates make the relationship unbound
+    let facts = vec![
+        fact("Revenues", "1000", 0),
+        fact("Revenues", "1100", 0),
+        fact("CostOfRevenue", "400", 0),
+        fact("GrossProfit", "650", 0),
+    ];
+    assert!(check_calculations(&gross_profit_network(), &facts).is_empty());
+
+    // Consistent duplicates use the more accurate fact
+    let facts = vec![
+        fact("Revenues", "1000000", -3),
+        fact("Revenues", "1000400", 0),
+        fact("CostOfRevenue", "400", 0),
+        fact("GrossProfit", "999000", 0),
+    ];
+    let findings = check_calculations(&gross_profit_network(), &facts);
+    assert_eq!(findings[0].expected, dec("1000000"));
+    assert_eq!(findings[0].reported, dec("999000"));
+}
+
+#[test]
+fn test_unusable_weight_skips_the_summation() {
+    let network = CalculationNetwork {
+        role: "http://example.com/role/StatementsOfOperations".to_string(),
+        arcs: vec![
+            arc("GrossProfit", "Revenues", 1.0),
+            arc("GrossProfit", "CostOfRevenue", f64::NAN),
+        ],
+    };
+    let facts = vec![
+        fact("Revenues", "1000", 0),
+        fact("CostOfRevenue", "400", 0),
+        fact("GrossProfit", "600", 0),
+    ];
+    // Treating the weight as +1 would report 1400 as the expected total
+    assert!(check_calculations(&network, &facts).is_empty());
+}
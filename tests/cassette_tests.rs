@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::cassette::{Cassette, CassetteMode, REDACTED};
+use xbrl_api_client::{SearchParams, XbrlApiError, XbrlClient};
+
+const API_KEY: &str = "secret_key_123";
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("xbrl-cassette-{}-{}.json", name, std::process::id()))
+}
+
+fn envelope(data: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "status": "success",
+        "message": null,
+        "data": data,
+        "errors": null
+    })
+}
+
+fn client(base_url: &str, cassette: Cassette) -> XbrlClient {
+    XbrlClient::builder()
+        .api_key(API_KEY)
+        .base_url(base_url)
+        .cassette(cassette)
+        .build()
+        .unwrap()
+}
+
+fn search_params() -> SearchParams {
+    SearchParams {
+        taxonomy: "us-gaap".to_string(),
+        concept_name: Some("Assets".to_string()),
+        entity_id: None,
+        fiscal_year: Some(2023),
+        fiscal_period: None,
+        dimension_name: None,
+        member_name: None,
+        text_search: None,
+        value_greater_than: None,
+        value_less_than: None,
+    }
+}
+
+#[tokio::test]
+async fn test_recorded_traffic_replays_without_network() {
+    let file_path = cassette_path("roundtrip");
+    let server = MockServer::start().await;
+    let base_url = server.uri();
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("X-Api-Key", API_KEY)
+                .insert_header("X-Echo", format!("key={}", API_KEY))
+                .set_body_json(envelope(serde_json::json!([
+                    { "name": "us-gaap", "description": "US GAAP Taxonomy", "version": "2023" }
+                ]))),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(envelope(serde_json::json!([]))))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let recorder = client(&base_url, Cassette::record(&file_path));
+    let recorded = recorder.get_taxonomies().await.unwrap();
+    assert!(recorder.search(search_params()).await.unwrap().is_empty());
+    drop(server);
+
+    let saved = std::fs::read_to_string(&file_path).unwrap();
+    assert!(!saved.contains(API_KEY));
+    assert!(saved.contains(REDACTED));
+    assert!(!saved.to_lowercase().contains("x-api-key"));
+
+    let cassette = Cassette::replay(&file_path).unwrap();
+    assert_eq!(cassette.mode(), CassetteMode::Replay);
+    assert_eq!(cassette.interactions().len(), 2);
+
+    let player = client(&base_url, cassette);
+    let replayed = player.get_taxonomies().await.unwrap();
+    assert_eq!(replayed[0].name, recorded[0].name);
+    assert!(player.search(search_params()).await.unwrap().is_empty());
+
+    // Requests that were never recorded fail instead of reaching the network
+    let missing = player.get_reports("us-gaap").await;
+    assert!(matches!(
+        missing,
+        Err(XbrlApiError::CassetteMiss { method, url }) if method == "GET" && url.contains("/reports")
+    ));
+
+    let mut other_search = search_params();
+    other_search.fiscal_year = Some(2022);
+    assert!(player.search(other_search).await.is_err());
+    std::fs::remove_file(&file_path).unwrap();
+    assert!(matches!(Cassette::replay(&file_path), Err(XbrlApiError::Cassette { .. })));
+}
+
+#[tokio::test]
+async fn test_cassette_set_on_client_scrubs_its_key() {
+    let file_path = cassette_path("set");
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/{}/reports", API_KEY)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(envelope(serde_json::json!([]))))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut client = XbrlClient::new(API_KEY);
+    client.set_base_url(&format!("{}/{}", server.uri(), API_KEY)).unwrap();
+    client.set_cassette(Some(Cassette::record(&file_path)));
+    // The key is echoed in the URL, as a misconfigured proxy or query parameter would
+    assert!(client.get_reports("us-gaap").await.unwrap().is_empty());
+
+    let saved = std::fs::read_to_string(&file_path).unwrap();
+    assert!(!saved.contains(API_KEY));
+    assert!(saved.contains(REDACTED));
+    std::fs::remove_file(&file_path).unwrap();
+}
+
+#[tokio::test]
+async fn test_repeated_requests_replay_in_order() {
+    let file_path = cassette_path("order");
+    let interaction = |status: u16, version: &str| {
+        serde_json::json!({
+            "request": { "method": "GET", "url": "https://api.example.com/v1/taxonomies" },
+            "response": {
+                "status": status,
+                "headers": { "content-type": "application/json" },
+                "json": envelope(serde_json::json!([
+                    { "name": "us-gaap", "description": "US GAAP", "version": version }
+                ]))
+            }
+        })
+    };
+    let file = serde_json::json!({ "interactions": [interaction(200, "2022"), interaction(200, "2023")] });
+    std::fs::write(&file_path, file.to_string()).unwrap();
+
+    let client = client("https://api.example.com/v1", Cassette::replay(&file_path).unwrap());
+    let versions: Vec<String> = [
+        client.get_taxonomies().await.unwrap(),
+        client.get_taxonomies().await.unwrap(),
+        client.get_taxonomies().await.unwrap(),
+    ]
+    .into_iter()
+    .map(|taxonomies| taxonomies[0].version.clone())
+    .collect();
+
+    assert_eq!(versions, ["2022", "2023", "2023"]);
+    std::fs::remove_file(&file_path).unwrap();
+}
+
+#[tokio::test]
+async fn test_auto_records_once_then_replays() {
+    let file_path = cassette_path("auto");
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/dimensions"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("no such taxonomy"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let first = client(&server.uri(), Cassette::auto(&file_path).unwrap());
+    assert_eq!(first.cassette().unwrap().mode(), CassetteMode::Record);
+    assert!(first.get_dimensions("nope").await.is_err());
+
+    // Error responses replay too, non-JSON bodies verbatim
+    let second = client(&server.uri(), Cassette::auto(&file_path).unwrap());
+    assert_eq!(second.cassette().unwrap().mode(), CassetteMode::Replay);
+    let error = second.get_dimensions("nope").await.unwrap_err();
+    assert!(matches!(error, XbrlApiError::NotFound { message, .. } if message == "no such taxonomy"));
+    std::fs::remove_file(&file_path).unwrap();
+}
@@ -0,0 +1,174 @@
+mod fixtures;
+
+use clap::Parser;
+use fixtures::fixture;
+use xbrl_api_client::cli::output::OutputFormat;
+use xbrl_api_client::cli::{self, Cli, Command};
+use xbrl_api_client::testing::MockXbrlServer;
+use xbrl_api_client::data::taxonomy::{Concept, Dimension, Network};
+use xbrl_api_client::{Fact, Report, SearchParams, Taxonomy, XbrlApiError};
+
+async fn run(server: &MockXbrlServer, args: &[&str]) -> String {
+    let mut argv = vec!["xbrl", "--api-key", "test_api_key", "--base-url"];
+    let url = server.url();
+    argv.push(&url);
+    argv.extend_from_slice(args);
+
+    let mut out = Vec::new();
+    cli::run(Cli::try_parse_from(argv).unwrap(), &mut out).await.unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_search_flags_map_to_search_params() {
+    let cli = Cli::try_parse_from([
+        "xbrl",
+        "--api-key",
+        "key",
+        "search",
+        "--taxonomy",
+        "us-gaap",
+        "--concept-name",
+        "Assets",
+        "--entity-id",
+        "0000320193",
+        "--fiscal-year",
+        "2023",
+        "--fiscal-period",
+        "FY",
+        "--dimension-name",
+        "srt:SegmentAxis",
+        "--member-name",
+        "ProductMember",
+        "--text-search",
+        "total",
+        "--value-greater-than",
+        "1000",
+        "--value-less-than",
+        "5e6",
+        "--format",
+        "ndjson",
+    ])
+    .unwrap();
+
+    assert_eq!(cli.format, OutputFormat::Ndjson);
+    let Command::Search(args) = cli.command else { panic!("expected search") };
+    let params = SearchParams::from(args);
+    assert_eq!(params.taxonomy, "us-gaap");
+    assert_eq!(params.concept_name.as_deref(), Some("Assets"));
+    assert_eq!(params.entity_id.as_deref(), Some("0000320193"));
+    assert_eq!(params.fiscal_year, Some(2023));
+    assert_eq!(params.fiscal_period.as_deref(), Some("FY"));
+    assert_eq!(params.dimension_name.as_deref(), Some("srt:SegmentAxis"));
+    assert_eq!(params.member_name.as_deref(), Some("ProductMember"));
+    assert_eq!(params.text_search.as_deref(), Some("total"));
+    assert_eq!(params.value_greater_than, Some(1000.0));
+    assert_eq!(params.value_less_than, Some(5_000_000.0));
+
+    assert!(Cli::try_parse_from(["xbrl", "search", "--concept-name", "Assets"]).is_err());
+    assert!(Cli::try_parse_from(["xbrl", "facts", "rpt-1", "--format", "xml"]).is_err());
+}
+
+#[tokio::test]
+async fn test_table_output() {
+    let server = MockXbrlServer::builder()
+        .taxonomies(&fixture::<Vec<Taxonomy>>("taxonomies.json"))
+        .start()
+        .await;
+
+    let output = run(&server, &["taxonomies"]).await;
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert!(lines[0].starts_with("NAME"));
+    assert!(lines[0].contains("VERSION"));
+    assert!(lines[1].starts_with("us-gaap"));
+    assert_eq!(lines.len(), 4);
+}
+
+#[tokio::test]
+async fn test_listing_commands() {
+    let server = MockXbrlServer::builder()
+        .reports("us-gaap", &fixture::<Vec<Report>>("reports.json"))
+        .concepts("us-gaap", &fixture::<Vec<Concept>>("concepts.json"))
+        .dimensions("us-gaap", &fixture::<Vec<Dimension>>("dimensions.json"))
+        .networks("us-gaap", &fixture::<Vec<Network>>("networks.json"))
+        .start()
+        .await;
+
+    for (command, header) in [
+        ("reports", "ID"),
+        ("concepts", "NAME"),
+        ("dimensions", "NAME"),
+        ("networks", "ID"),
+    ] {
+        let output = run(&server, &[command, "us-gaap"]).await;
+        assert!(output.starts_with(header), "{}: {}", command, output);
+        assert!(output.lines().count() > 1, "{}: {}", command, output);
+    }
+}
+
+#[tokio::test]
+async fn test_json_and_ndjson_output() {
+    let server = MockXbrlServer::builder()
+        .facts("rpt-123456", &fixture::<Vec<Fact>>("facts.json"))
+        .concepts("us-gaap", &[fixture::<Concept>("concept_details.json")])
+        .start()
+        .await;
+
+    let json = run(&server, &["--format", "json", "facts", "rpt-123456"]).await;
+    let facts: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(facts.as_array().unwrap().len(), 3);
+
+    let ndjson = run(&server, &["facts", "rpt-123456", "-f", "ndjson"]).await;
+    let lines: Vec<serde_json::Value> = ndjson.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["concept_name"], "Assets");
+
+    let concept = run(&server, &["-f", "json", "concept", "us-gaap", "Assets"]).await;
+    let concept: serde_json::Value = serde_json::from_str(&concept).unwrap();
+    assert_eq!(concept["name"], "Assets");
+}
+
+#[tokio::test]
+async fn test_csv_output() {
+    let server = MockXbrlServer::builder()
+        .search_results(&fixture::<Vec<Fact>>("search_results.json"))
+        .networks("us-gaap", &[fixture::<Network>("network_details.json")])
+        .start()
+        .await;
+
+    let csv = run(&server, &["-f", "csv", "search", "--taxonomy", "us-gaap"]).await;
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    let headers = reader.headers().unwrap().clone();
+    assert_eq!(&headers[0], "concept_name");
+    assert!(reader.records().count() > 0);
+
+    let network = run(&server, &["-f", "csv", "network", "net-123"]).await;
+    let mut reader = csv::Reader::from_reader(network.as_bytes());
+    assert_eq!(&reader.headers().unwrap()[0], "depth");
+    let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+    // Depth has its own column and names are not padded
+    assert_eq!((&rows[0][0], &rows[0][1]), ("0", "Assets"));
+    assert_eq!((&rows[1][0], &rows[1][1]), ("1", "CurrentAssets"));
+
+    // Tables indent child nodes under their parent instead
+    let table = run(&server, &["network", "net-123"]).await;
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].starts_with("CONCEPT_NAME"));
+    assert!(lines[1].starts_with("Assets"));
+    assert!(lines[2].starts_with("  CurrentAssets"));
+}
+
+#[tokio::test]
+async fn test_missing_api_key_is_an_error() {
+    let cli = Cli {
+        api_key: None,
+        base_url: None,
+        cache_dir: None,
+        format: OutputFormat::Table,
+        command: Command::Taxonomies,
+    };
+
+    let result = cli::run(cli, &mut Vec::new()).await;
+    assert!(matches!(result, Err(XbrlApiError::ValidationError(_))));
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "arrow")]
+
+use arrow::array::{Array, AsArray};
+use arrow::datatypes::{DataType, Date32Type, Decimal128Type, Int32Type};
+use futures::stream;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use xbrl_api_client::api::models::ApiResponse;
+use xbrl_api_client::columnar::{
+    facts_to_record_batch, reports_to_record_batch, write_parquet_stream, ArrowRecord, ParquetStreamWriter,
+};
+use xbrl_api_client::parser::instance::parse_instance_file;
+use xbrl_api_client::{Fact, Report, XbrlApiError};
+
+fn instance_facts() -> Vec<Fact> {
+    parse_instance_file("tests/mock_data/instance.xml").unwrap().facts
+}
+
+fn mock_reports() -> Vec<Report> {
+    let json = std::fs::read_to_string("tests/mock_data/reports.json").unwrap();
+    let response: ApiResponse<Vec<Report>> = serde_json::from_str(&json).unwrap();
+    response.data.unwrap()
+}
+
+fn temp_file(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("{}-{}.parquet", name, std::process::id()))
+}
+
+#[test]
+fn test_fact_batch() {
+    let facts = instance_facts();
+    let batch = facts_to_record_batch(&facts).unwrap();
+    assert_eq!(batch.num_rows(), facts.len());
+    assert_eq!(batch.schema(), Fact::schema());
+    assert_eq!(batch.schema().field_with_name("value").unwrap().data_type(), &DataType::Decimal128(38, 10));
+
+    let row = facts.iter().position(|f| f.id == "f-rev-retail").unwrap();
+    let value = batch.column_by_name("value").unwrap().as_primitive::<Decimal128Type>();
+    assert_eq!(value.value_as_string(row), "400000.0000000000");
+    let start = batch.column_by_name("period_start").unwrap().as_primitive::<Date32Type>();
+    assert_eq!(start.value_as_date(row).unwrap().to_string(), "2022-01-01");
+    let decimals = batch.column_by_name("decimals").unwrap().as_primitive::<Int32Type>();
+    assert_eq!(decimals.value(row), -3);
+
+    let dimensions = batch.column_by_name("dimensions").unwrap().as_map();
+    let entries = dimensions.value(row);
+    let keys = entries.column(0).as_string::<i32>();
+    let values = entries.column(1).as_string::<i32>();
+    assert_eq!(keys.value(0), "ex:StoreAxis");
+    assert_eq!(values.value(0), "042");
+    assert_eq!(keys.value(1), "srt:SegmentAxis");
+    assert_eq!(values.value(1), "ex:RetailMember");
+
+    // Text facts go to text_value, nil facts to neither column
+    let text = facts.iter().position(|f| f.concept_name == "LeaseTerm").unwrap();
+    assert!(value.is_null(text));
+    assert_eq!(batch.column_by_name("text_value").unwrap().as_string::<i32>().value(text), "Five years");
+    let nil = facts.iter().position(|f| f.concept_name == "Goodwill").unwrap();
+    assert!(value.is_null(nil));
+    assert!(batch.column_by_name("text_value").unwrap().is_null(nil));
+    assert!(batch.column_by_name("is_nil").unwrap().as_boolean().value(nil));
+    assert!(dimensions.is_null(nil));
+}
+
+#[test]
+fn test_report_batch() {
+    let reports = mock_reports();
+    let batch = reports_to_record_batch(&reports).unwrap();
+    assert_eq!(batch.num_rows(), reports.len());
+    assert_eq!(batch.schema(), Report::schema());
+
+    let filing_date = batch.column_by_name("filing_date").unwrap().as_primitive::<Date32Type>();
+    assert_eq!(filing_date.value_as_date(0).unwrap().to_string(), reports[0].filing_date);
+}
+
+#[test]
+fn test_value_out_of_range() {
+    let mut facts = instance_facts();
+    facts[0].value = xbrl_api_client::FactValue::Decimal(xbrl_api_client::Decimal::MAX);
+    assert!(matches!(facts_to_record_batch(&facts), Err(XbrlApiError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_stream_to_parquet() {
+    let facts = instance_facts();
+    let path = temp_file("facts");
+    let file = std::fs::File::create(&path).unwrap();
+    let rows = write_parquet_stream(stream::iter(instance_facts().into_iter().map(Ok)), file, 4)
+        .await
+        .unwrap();
+    assert_eq!(rows, facts.len());
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap()).unwrap();
+    // One row group per batch
+    assert_eq!(reader.metadata().num_row_groups(), facts.len().div_ceil(4));
+    let batches: Vec<_> = reader.build().unwrap().collect::<Result<_, _>>().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), facts.len());
+    assert_eq!(batches[0].schema(), Fact::schema());
+    let ids = batches[0].column_by_name("id").unwrap().as_string::<i32>();
+    assert_eq!(ids.value(0), facts[0].id);
+}
+
+#[tokio::test]
+async fn test_stream_error_stops_export() {
+    let items = vec![Ok(instance_facts().remove(0)), Err(XbrlApiError::Unknown("page failed".to_string()))];
+    let result = write_parquet_stream(stream::iter(items), Vec::new(), 10).await;
+    assert!(matches!(result, Err(XbrlApiError::Unknown(message)) if message == "page failed"));
+}
+
+#[tokio::test]
+async fn test_encoding_error_stops_export() {
+    let mut facts = instance_facts();
+    facts[0].value = xbrl_api_client::FactValue::Decimal(xbrl_api_client::Decimal::MAX);
+    let result = write_parquet_stream(stream::iter(facts.into_iter().map(Ok)), Vec::new(), 1).await;
+    assert!(matches!(result, Err(XbrlApiError::ValidationError(_))));
+}
+
+#[test]
+fn test_push_writer_for_reports() {
+    let mut writer = ParquetStreamWriter::<Report, _>::new(Vec::new()).unwrap();
+    for report in mock_reports() {
+        writer.push(report).unwrap();
+    }
+    assert_eq!(writer.finish().unwrap(), mock_reports().len());
+}
@@ -0,0 +1,225 @@
+use std::io::{Cursor, Write};
+use xbrl_api_client::parser::dts::{load_dts, load_dts_from, load_taxonomy_directory, Dts};
+use xbrl_api_client::parser::package::{TaxonomyPackage, MAX_ENTRY_SIZE};
+use xbrl_api_client::XbrlApiError;
+
+const TAXONOMY_DIR: &str = "tests/mock_data/taxonomy";
+const INCOME_STATEMENT: &str = "http://example.com/role/IncomeStatement";
+
+/// Zip the fixture taxonomy inside a top-level folder, as published packages are
+fn zipped_taxonomy() -> Vec<u8> {
+    zipped_taxonomy_with(|_, content| content)
+}
+
+/// Zip the fixture taxonomy after passing each file's content through `edit`
+fn zipped_taxonomy_with(edit: impl Fn(&str, String) -> String) -> Vec<u8> {
+    let package = TaxonomyPackage::open_directory(TAXONOMY_DIR).unwrap();
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for file in package.files() {
+        writer
+            .start_file(format!("example-2022/{}", file), zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(edit(&file, package.read(&file).unwrap()).as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+fn assert_example_dts(dts: &Dts) {
+    assert_eq!(dts.taxonomy.name, "Example Taxonomy");
+    assert_eq!(dts.taxonomy.version, "2022");
+    assert_eq!(dts.concepts.len(), 9);
+    assert_eq!(dts.networks.len(), 1);
+    assert_eq!(dts.calculation_networks.len(), 1);
+    assert_eq!(dts.dimensions.len(), 1);
+}
+
+#[test]
+fn test_package_metadata_and_catalog() {
+    let package = TaxonomyPackage::open_directory(TAXONOMY_DIR).unwrap();
+    let metadata = package.metadata().unwrap();
+    assert_eq!(metadata.name.as_deref(), Some("Example Taxonomy"));
+    assert_eq!(metadata.entry_points, ["http://example.com/taxonomy/2022/ex-2022.xsd"]);
+
+    assert_eq!(package.entry_points().unwrap(), ["ex/ex-2022.xsd"]);
+    assert_eq!(
+        package.resolve("", "http://example.com/taxonomy/2022/ex-2022_lab.xml").as_deref(),
+        Some("ex/ex-2022_lab.xml")
+    );
+    assert_eq!(package.resolve("ex/ex-2022.xsd", "../ex/ex-2022_pre.xml").as_deref(), Some("ex/ex-2022_pre.xml"));
+    assert_eq!(package.resolve("ex/ex-2022.xsd", "https://xbrl.fasb.org/us-gaap/2022/elts/us-gaap-2022.xsd"), None);
+}
+
+#[test]
+fn test_load_directory() {
+    let dts = load_taxonomy_directory(TAXONOMY_DIR).unwrap();
+    assert_example_dts(&dts);
+    assert_eq!(
+        dts.missing,
+        [
+            "http://www.xbrl.org/2003/xbrl-instance-2003-12-31.xsd",
+            "https://xbrl.fasb.org/us-gaap/2022/elts/us-gaap-2022.xsd"
+        ]
+    );
+}
+
+#[test]
+fn test_load_zip_package() {
+    let package = TaxonomyPackage::from_zip_bytes(zipped_taxonomy()).unwrap();
+    assert_eq!(package.entry_points().unwrap(), ["example-2022/ex/ex-2022.xsd"]);
+
+    let dts = load_dts(&package).unwrap();
+    assert_example_dts(&dts);
+    assert_eq!(dts.concept("Revenues").unwrap().label, "Revenues");
+}
+
+#[test]
+fn test_concepts_labels_and_references() {
+    let dts = load_taxonomy_directory(TAXONOMY_DIR).unwrap();
+
+    let revenues = dts.concept("Revenues").unwrap();
+    assert_eq!(revenues.taxonomy, "ex");
+    assert_eq!(revenues.type_name, "xbrli:monetaryItemType");
+    assert_eq!(revenues.period_type.as_deref(), Some("duration"));
+    assert_eq!(revenues.balance.as_deref(), Some("credit"));
+    assert_eq!(revenues.is_abstract, Some(false));
+    // English wins over the German label declared first
+    assert_eq!(revenues.label, "Revenues");
+    assert!(revenues.documentation.as_deref().unwrap().starts_with("Amount of revenue"));
+
+    let references = revenues.references.as_ref().unwrap();
+    assert_eq!(references.len(), 3);
+    assert_eq!(references[2].name, "Topic");
+    assert_eq!(references[2].value, "606");
+
+    let abstract_item = dts.concept("IncomeStatementAbstract").unwrap();
+    assert_eq!(abstract_item.is_abstract, Some(true));
+    assert_eq!(dts.concept("ServiceMember").unwrap().label, "ServiceMember");
+    assert_eq!(
+        dts.label("CostOfRevenue", "http://www.xbrl.org/2003/role/negatedLabel"),
+        Some("Less: cost of revenue")
+    );
+}
+
+#[test]
+fn test_presentation_network() {
+    let dts = load_taxonomy_directory(TAXONOMY_DIR).unwrap();
+    let network = dts.network(INCOME_STATEMENT).unwrap();
+    assert_eq!(network.id, "IncomeStatement");
+    assert_eq!(network.name, "1001 - Statement - Income Statement");
+    assert_eq!(network.taxonomy, "Example Taxonomy");
+
+    let roots = network.nodes.as_ref().unwrap();
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].concept_name, "IncomeStatementAbstract");
+    assert_eq!(roots[0].level, Some(1));
+
+    // The prohibited NetIncomeLoss arc overrides the one it repeats
+    let children = roots[0].children.as_ref().unwrap();
+    let names: Vec<_> = children.iter().map(|n| n.concept_name.as_str()).collect();
+    assert_eq!(names, ["Revenues", "CostOfRevenue", "GrossProfit"]);
+    assert_eq!(children[2].concept_label, "Total gross profit");
+    assert_eq!(children[2].parent.as_deref(), Some("IncomeStatementAbstract"));
+    assert_eq!(children[2].level, Some(2));
+    assert_eq!(children[1].concept_label, "Less: cost of revenue");
+}
+
+#[test]
+fn test_calculation_network() {
+    let dts = load_taxonomy_directory(TAXONOMY_DIR).unwrap();
+    let network = dts.calculation_network(INCOME_STATEMENT).unwrap();
+    assert_eq!(network.parents(), ["GrossProfit"]);
+
+    let arcs: Vec<_> = network.children_of("GrossProfit").map(|a| (a.child.as_str(), a.weight)).collect();
+    assert_eq!(arcs, [("Revenues", 1.0), ("CostOfRevenue", -1.0)]);
+    assert!(dts.skipped_arcs.is_empty());
+}
+
+#[test]
+fn test_calculation_arcs_without_a_usable_weight_are_skipped() {
+    let zipped = zipped_taxonomy_with(|file, content| {
+        if file.ends_with("_cal.xml") {
+            content.replace(" weight=\"-1\"", "").replace("weight=\"1\"", "weight=\"one\"")
+        } else {
+            content
+        }
+    });
+    let dts = load_dts(&TaxonomyPackage::from_zip_bytes(zipped).unwrap()).unwrap();
+
+    let network = dts.calculation_network(INCOME_STATEMENT).unwrap();
+    assert!(network.arcs.is_empty());
+
+    let mut skipped: Vec<_> = dts
+        .skipped_arcs
+        .iter()
+        .map(|arc| (arc.parent.as_str(), arc.child.as_str(), arc.reason.as_str()))
+        .collect();
+    skipped.sort();
+    assert_eq!(
+        skipped,
+        [
+            ("GrossProfit", "CostOfRevenue", "missing weight"),
+            ("GrossProfit", "Revenues", "invalid weight \"one\""),
+        ]
+    );
+    assert!(dts.skipped_arcs.iter().all(|arc| arc.role == INCOME_STATEMENT));
+}
+
+#[test]
+fn test_dimensions() {
+    let dts = load_taxonomy_directory(TAXONOMY_DIR).unwrap();
+
+    let axis = dts.dimension("SegmentAxis").unwrap();
+    assert_eq!(axis.label, "Segments [Axis]");
+    let members: Vec<_> = axis.members.as_ref().unwrap().iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(members, ["SegmentDomain", "ProductMember", "ServiceMember"]);
+    assert_eq!(axis.members.as_ref().unwrap()[1].label, "Products [Member]");
+
+    // The hypercube applies to the primary item and its domain-member descendants
+    for name in ["IncomeStatementAbstract", "Revenues"] {
+        let dimensions = dts.concept(name).unwrap().dimensions.as_ref().unwrap();
+        assert_eq!(dimensions[0].dimension_name, "SegmentAxis");
+        assert_eq!(dimensions[0].members.len(), 3);
+    }
+    assert!(dts.concept("CostOfRevenue").unwrap().dimensions.is_none());
+}
+
+#[test]
+fn test_invalid_packages() {
+    assert!(matches!(
+        TaxonomyPackage::open_directory("tests/mock_data/no-such-taxonomy"),
+        Err(XbrlApiError::ParseError(_))
+    ));
+    assert!(matches!(
+        TaxonomyPackage::from_zip_bytes(b"not a zip".to_vec()),
+        Err(XbrlApiError::ParseError(_))
+    ));
+
+    let package = TaxonomyPackage::open_directory(TAXONOMY_DIR).unwrap();
+    assert!(load_dts_from(&package, &[]).is_err());
+    assert!(load_dts_from(&package, &["ex/missing.xsd".to_string()]).is_err());
+}
+
+#[test]
+fn test_oversized_zip_entries_are_rejected() {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default().large_file(true);
+    writer.start_file("bomb/ex.xsd", options).unwrap();
+    let chunk = vec![0u8; 1024 * 1024];
+    for _ in 0..=MAX_ENTRY_SIZE / chunk.len() as u64 {
+        writer.write_all(&chunk).unwrap();
+    }
+    let zipped = writer.finish().unwrap().into_inner();
+
+    match TaxonomyPackage::from_zip_bytes(zipped) {
+        Err(XbrlApiError::ParseError(message)) => assert!(message.contains("bomb/ex.xsd"), "{}", message),
+        other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_concept_lookup_after_editing_concepts() {
+    let mut dts = load_taxonomy_directory(TAXONOMY_DIR).unwrap();
+    dts.concepts.reverse();
+    assert_eq!(dts.concept("Revenues").unwrap().name, "Revenues");
+    assert!(dts.concept("NoSuchConcept").is_none());
+}
@@ -0,0 +1,91 @@
+use xbrl_api_client::api::endpoints::Endpoints;
+use xbrl_api_client::api::models::QueryParams;
+use url::Url;
+use xbrl_api_client::{XbrlApiError, XbrlClient};
+
+#[test]
+fn test_default_endpoints() {
+    let endpoints = Endpoints::default();
+    assert_eq!(endpoints.base_url(), "https://api.xbrl.us/api/v1");
+    assert_eq!(endpoints.taxonomies().as_str(), "https://api.xbrl.us/api/v1/taxonomies");
+    assert_eq!(
+        endpoints.reports("us-gaap").as_str(),
+        "https://api.xbrl.us/api/v1/reports?taxonomy=us-gaap"
+    );
+    assert_eq!(
+        endpoints.network_details("net-123").as_str(),
+        "https://api.xbrl.us/api/v1/networks/net-123"
+    );
+}
+
+#[test]
+fn test_base_url_with_and_without_trailing_slash() {
+    for base_url in ["http://localhost:8080/api", "http://localhost:8080/api/"] {
+        let endpoints = Endpoints::new(base_url).unwrap();
+        assert_eq!(endpoints.base_url(), "http://localhost:8080/api");
+        assert_eq!(endpoints.search().as_str(), "http://localhost:8080/api/search");
+    }
+
+    let endpoints = Endpoints::new("http://127.0.0.1:9000").unwrap();
+    assert_eq!(endpoints.facts("rpt-1").as_str(), "http://127.0.0.1:9000/facts?reportId=rpt-1");
+}
+
+#[test]
+fn test_special_characters_are_encoded() {
+    let endpoints = Endpoints::default();
+
+    let url = endpoints.concept_details("us gaap&x=1#frag", "Assets/Current #1");
+    assert_eq!(
+        url.as_str(),
+        "https://api.xbrl.us/api/v1/concepts/Assets%2FCurrent%20%231?taxonomy=us+gaap%26x%3D1%23frag"
+    );
+    assert_eq!(url.path_segments().unwrap().count(), 4);
+    assert_eq!(url.query_pairs().count(), 1);
+    assert_eq!(url.fragment(), None);
+
+    let url = endpoints.network_details("../admin");
+    assert_eq!(url.as_str(), "https://api.xbrl.us/api/v1/networks/..%2Fadmin");
+}
+
+#[test]
+fn test_extra_query_params_are_merged() {
+    let endpoints = Endpoints::default();
+
+    let mut params = QueryParams::new();
+    params.insert("pageSize".to_string(), "50".to_string());
+    params.insert("fields".to_string(), "fact.value,concept.name".to_string());
+    params.insert("taxonomy".to_string(), "ifrs".to_string());
+
+    let url = Endpoints::with_query(endpoints.reports("us-gaap"), &params);
+    assert_eq!(
+        url.as_str(),
+        "https://api.xbrl.us/api/v1/reports?fields=fact.value%2Cconcept.name&pageSize=50&taxonomy=ifrs"
+    );
+
+    let unchanged = Endpoints::with_query(endpoints.taxonomies(), &QueryParams::new());
+    assert_eq!(unchanged, endpoints.taxonomies());
+}
+
+#[test]
+fn test_invalid_base_url_is_a_url_error() {
+    assert!(matches!(Endpoints::new("not a url"), Err(XbrlApiError::UrlError(_))));
+    assert!(matches!(Endpoints::new("mailto:user@example.com"), Err(XbrlApiError::UrlError(_))));
+    assert!(matches!(
+        XbrlClient::with_base_url("test_api_key", "::"),
+        Err(XbrlApiError::UrlError(_))
+    ));
+
+    let mut client = XbrlClient::new("test_api_key");
+    assert!(client.set_base_url("relative/path").is_err());
+    assert_eq!(client.base_url(), "https://api.xbrl.us/api/v1");
+}
+
+#[test]
+fn test_resource_names_the_endpoint_of_a_url() {
+    let endpoints = Endpoints::new("https://api.xbrl.us/api/v1/").unwrap();
+
+    assert_eq!(endpoints.resource(&endpoints.concept_details("us-gaap", "Assets")), Some("concepts"));
+    assert_eq!(endpoints.resource(&endpoints.network_details("n1")), Some("networks"));
+    assert_eq!(endpoints.resource(&endpoints.taxonomies()), Some("taxonomies"));
+    assert_eq!(endpoints.resource(&Url::parse("https://example.com/api/v1/facts").unwrap()), None);
+}
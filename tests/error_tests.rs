@@ -0,0 +1,172 @@
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::retry::RetryPolicy;
+use xbrl_api_client::utils::errors::FieldError;
+use xbrl_api_client::{SearchParams, XbrlApiError, XbrlClient};
+
+async fn server_responding(route: &str, response: ResponseTemplate) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(path(route)).respond_with(response).mount(&server).await;
+    server
+}
+
+fn client(server: &MockServer) -> XbrlClient {
+    XbrlClient::builder()
+        .api_key("test_api_key")
+        .base_url(&server.uri())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn error_body(message: &str, errors: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "status": "error",
+        "message": message,
+        "data": null,
+        "errors": errors
+    })
+}
+
+fn search_params() -> SearchParams {
+    SearchParams {
+        taxonomy: "us-gaap".to_string(),
+        concept_name: None,
+        entity_id: None,
+        fiscal_year: Some(1066),
+        fiscal_period: None,
+        dimension_name: None,
+        member_name: None,
+        text_search: None,
+        value_greater_than: None,
+        value_less_than: None,
+    }
+}
+
+#[tokio::test]
+async fn test_bad_request_carries_field_errors() {
+    let body = error_body(
+        "Invalid search",
+        serde_json::json!([
+            { "field": "fiscal_year", "message": "must be 2009 or later" },
+            { "param": "taxonomy", "error": "unknown taxonomy" },
+            "request rejected"
+        ]),
+    );
+    let server = server_responding("/search", ResponseTemplate::new(400).set_body_json(body)).await;
+
+    match client(&server).search(search_params()).await {
+        Err(XbrlApiError::BadRequest { status_code, field_errors, message, method, url }) => {
+            assert_eq!(status_code, 400);
+            assert_eq!(message, "Invalid search");
+            assert_eq!(method, "POST");
+            assert_eq!(url, format!("{}/search", server.uri()));
+            assert_eq!(
+                field_errors,
+                [
+                    FieldError { field: Some("fiscal_year".to_string()), message: "must be 2009 or later".to_string() },
+                    FieldError { field: Some("taxonomy".to_string()), message: "unknown taxonomy".to_string() },
+                    FieldError { field: None, message: "request rejected".to_string() },
+                ]
+            );
+        }
+        other => panic!("Expected a bad request error but got: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_unprocessable_entity_keeps_its_status() {
+    let body = error_body("Invalid fields", serde_json::json!(["unknown field"]));
+    let server = server_responding("/search", ResponseTemplate::new(422).set_body_json(body)).await;
+
+    let error = client(&server).search(search_params()).await.unwrap_err();
+    assert!(matches!(error, XbrlApiError::BadRequest { status_code: 422, .. }));
+    assert_eq!(error.status_code(), Some(422));
+    assert!(error.to_string().contains("422"));
+}
+
+#[tokio::test]
+async fn test_rate_limited_reads_retry_after() {
+    let response = ResponseTemplate::new(429)
+        .insert_header("Retry-After", "7")
+        .set_body_json(error_body("Slow down", serde_json::json!(["Too many requests"])));
+    let server = server_responding("/taxonomies", response).await;
+
+    let error = client(&server).get_taxonomies().await.unwrap_err();
+    assert!(matches!(
+        &error,
+        XbrlApiError::RateLimited { retry_after: Some(delay), message, .. }
+            if *delay == Duration::from_secs(7) && message == "Slow down"
+    ));
+    assert_eq!(error.status_code(), Some(429));
+    assert!(error.to_string().contains("retry after 7s"));
+}
+
+#[tokio::test]
+async fn test_statuses_map_to_variants() {
+    let server = MockServer::start().await;
+    let routes = [("/taxonomies", 401), ("/reports", 403), ("/facts", 404), ("/concepts", 503), ("/dimensions", 409)];
+    for (route, status) in routes {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(ResponseTemplate::new(status).set_body_json(error_body("nope", serde_json::Value::Null)))
+            .mount(&server)
+            .await;
+    }
+    let client = client(&server);
+
+    assert!(matches!(client.get_taxonomies().await, Err(XbrlApiError::Unauthorized { .. })));
+    assert!(matches!(client.get_reports("us-gaap").await, Err(XbrlApiError::Forbidden { .. })));
+    assert!(matches!(client.get_facts("rpt-1").await, Err(XbrlApiError::NotFound { .. })));
+    assert!(matches!(
+        client.get_concepts("us-gaap").await,
+        Err(XbrlApiError::ServerError { status_code: 503, .. })
+    ));
+
+    let error = client.get_dimensions("us-gaap").await.unwrap_err();
+    assert!(matches!(&error, XbrlApiError::ApiError { status_code: 409, message, .. } if message == "nope"));
+    let dimensions_url = format!("{}/dimensions?taxonomy=us-gaap", server.uri());
+    assert_eq!(error.request(), Some(("GET", dimensions_url.as_str())));
+    assert!(error.to_string().contains(&dimensions_url));
+}
+
+#[tokio::test]
+async fn test_message_falls_back_to_body_and_status() {
+    let server = MockServer::start().await;
+    let responses = [
+        ("/taxonomies", ResponseTemplate::new(404).set_body_string("no such thing\n")),
+        ("/reports", ResponseTemplate::new(404)),
+        (
+            "/facts",
+            ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "error": "invalid_token",
+                "error_description": "The access token expired"
+            })),
+        ),
+    ];
+    for (route, response) in responses {
+        Mock::given(path(route)).respond_with(response).mount(&server).await;
+    }
+    let client = client(&server);
+
+    let message = |error: XbrlApiError| match error {
+        XbrlApiError::NotFound { message, .. } | XbrlApiError::Unauthorized { message, .. } => message,
+        other => panic!("Unexpected error: {:?}", other),
+    };
+    assert_eq!(message(client.get_taxonomies().await.unwrap_err()), "no such thing");
+    assert_eq!(message(client.get_reports("us-gaap").await.unwrap_err()), "Not Found");
+    assert_eq!(message(client.get_facts("rpt-1").await.unwrap_err()), "The access token expired");
+}
+
+#[tokio::test]
+async fn test_successful_status_without_data() {
+    let body = error_body("", serde_json::json!(["taxonomy is required", { "field": "page", "message": "too large" }]));
+    let server = server_responding("/taxonomies", ResponseTemplate::new(200).set_body_json(body)).await;
+
+    let error = client(&server).get_taxonomies().await.unwrap_err();
+    assert!(matches!(
+        error,
+        XbrlApiError::ApiError { status_code: 200, message, .. } if message == "taxonomy is required; page: too large"
+    ));
+}
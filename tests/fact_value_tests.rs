@@ -0,0 +1,122 @@
+mod fixtures;
+
+use fixtures::{dec, fact_json, to_fact};
+use xbrl_api_client::data::facts::{round_to_decimals, Decimals, Precision};
+use xbrl_api_client::{Fact, FactValue};
+
+fn fact(value: serde_json::Value, decimals: Option<serde_json::Value>, precision: Option<serde_json::Value>) -> Fact {
+    let mut fact = fact_json("fact-1", "Assets", value);
+    if let Some(decimals) = decimals {
+        fact["decimals"] = decimals;
+    }
+    if let Some(precision) = precision {
+        fact["precision"] = precision;
+    }
+    to_fact(fact)
+}
+
+#[test]
+fn test_fact_value_deserialization() {
+    let value: FactValue = serde_json::from_str("\"123456789012345678901.25\"").unwrap();
+    assert_eq!(value, FactValue::String("123456789012345678901.25".to_string()));
+    assert_eq!(value.into_numeric(), FactValue::Decimal(dec("123456789012345678901.25")));
+
+    let value: FactValue = serde_json::from_str("9007199254740993").unwrap();
+    assert_eq!(value, FactValue::Decimal(dec("9007199254740993")));
+
+    // JSON numbers keep every digit; only values out of Decimal's range fall back to f64
+    assert_eq!(serde_json::from_str::<FactValue>("1.5").unwrap(), FactValue::Decimal(dec("1.5")));
+    assert_eq!(
+        serde_json::from_str::<FactValue>("12345678901234567.89").unwrap(),
+        FactValue::Decimal(dec("12345678901234567.89"))
+    );
+    assert_eq!(serde_json::from_str::<FactValue>("-2.5E3").unwrap(), FactValue::Decimal(dec("-2500")));
+    assert_eq!(serde_json::from_str::<FactValue>("1e40").unwrap(), FactValue::Number(1e40));
+
+    // Including inside a fact, whose flattened period makes serde buffer the value
+    let exact = fact(serde_json::from_str("12345678901234567.89").unwrap(), Some(serde_json::json!(2)), None);
+    assert_eq!(exact.value, FactValue::Decimal(dec("12345678901234567.89")));
+    assert_eq!(exact.decimals, Some(Decimals::Finite(2)));
+    assert_eq!(serde_json::from_str::<FactValue>("null").unwrap(), FactValue::Nil);
+    assert_eq!(serde_json::from_str::<FactValue>("true").unwrap(), FactValue::Boolean(true));
+    assert_eq!(
+        serde_json::from_str::<FactValue>("\"1e5\"").unwrap(),
+        FactValue::String("1e5".to_string())
+    );
+    assert_eq!(
+        serde_json::from_str::<FactValue>("\"Example Corp\"").unwrap(),
+        FactValue::String("Example Corp".to_string())
+    );
+}
+
+#[test]
+fn test_fact_value_serialization_is_lossless() {
+    let value = FactValue::Decimal(dec("123456789012345678901.25"));
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(json, "\"123456789012345678901.25\"");
+    assert_eq!(serde_json::to_string(&FactValue::Nil).unwrap(), "null");
+
+    let mut numeric = fact(serde_json::json!("123456789012345678901.25"), None, None);
+    assert_eq!(numeric.value, value);
+    numeric = serde_json::from_str(&serde_json::to_string(&numeric).unwrap()).unwrap();
+    assert_eq!(numeric.value, value);
+}
+
+#[test]
+fn test_strings_without_unit_stay_text() {
+    let mut text = serde_json::to_value(fact(serde_json::json!("1"), None, None)).unwrap();
+    text["unit"] = serde_json::Value::Null;
+    text["value"] = serde_json::json!("00123");
+    let text: Fact = serde_json::from_value(text).unwrap();
+    assert_eq!(text.value, FactValue::String("00123".to_string()));
+
+    let json = serde_json::to_string(&text).unwrap();
+    assert!(json.contains("\"value\":\"00123\""));
+    assert_eq!(serde_json::from_str::<Fact>(&json).unwrap().value, text.value);
+}
+
+#[test]
+fn test_nil_and_accuracy_attributes_on_fact() {
+    let nil = fact(serde_json::Value::Null, None, None);
+    assert!(nil.value.is_nil());
+    assert_eq!(nil.rounded_value(), None);
+
+    let reported = fact(serde_json::json!("1234000"), Some(serde_json::json!("-3")), None);
+    assert_eq!(reported.decimals, Some(Decimals::Finite(-3)));
+
+    let exact = fact(serde_json::json!("10"), Some(serde_json::json!("INF")), None);
+    assert_eq!(exact.decimals, Some(Decimals::Infinite));
+
+    let with_precision = fact(serde_json::json!(1234), None, Some(serde_json::json!(2)));
+    assert_eq!(with_precision.precision, Some(Precision::Finite(2)));
+    assert_eq!(with_precision.inferred_decimals(), Some(Decimals::Finite(-2)));
+    assert_eq!(with_precision.rounded_value(), Some(dec("1200")));
+
+    let small = fact(serde_json::json!("0.00123"), None, Some(serde_json::json!(2)));
+    assert_eq!(small.inferred_decimals(), Some(Decimals::Finite(4)));
+}
+
+#[test]
+fn test_round_to_decimals_uses_half_even() {
+    assert_eq!(round_to_decimals(dec("2.5"), Decimals::Finite(0)), dec("2"));
+    assert_eq!(round_to_decimals(dec("3.5"), Decimals::Finite(0)), dec("4"));
+    assert_eq!(round_to_decimals(dec("1234567"), Decimals::Finite(-3)), dec("1235000"));
+    assert_eq!(round_to_decimals(dec("1232500"), Decimals::Finite(-3)), dec("1232000"));
+    assert_eq!(round_to_decimals(dec("1.005"), Decimals::Infinite), dec("1.005"));
+}
+
+#[test]
+fn test_value_equals_rounds_to_lower_accuracy() {
+    let thousands = fact(serde_json::json!("1235000"), Some(serde_json::json!(-3)), None);
+    let units = fact(serde_json::json!("1234567"), Some(serde_json::json!(0)), None);
+    let millions = fact(serde_json::json!("1000000"), Some(serde_json::json!(-6)), None);
+
+    assert!(thousands.value_equals(&units));
+    assert!(units.value_equals(&thousands));
+    assert!(units.value_equals(&millions));
+    assert!(!units.value_equals(&fact(serde_json::json!("1236000"), Some(serde_json::json!(-3)), None)));
+
+    let nil = fact(serde_json::Value::Null, None, None);
+    assert!(nil.value_equals(&fact(serde_json::Value::Null, None, None)));
+    assert!(!nil.value_equals(&units));
+}
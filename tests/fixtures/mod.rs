@@ -0,0 +1,47 @@
+// Each test crate including this module uses a different subset of it
+#![allow(dead_code)]
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::str::FromStr;
+use xbrl_api_client::{Decimal, Fact};
+
+/// Load `tests/mock_data/{file}`, unwrapping the API response envelope if it has one
+pub fn fixture<T: DeserializeOwned>(file: &str) -> T {
+    let path = Path::new("tests/mock_data").join(file);
+    let json = std::fs::read_to_string(&path).unwrap_or_else(|_| panic!("Failed to read mock data file: {}", file));
+    let mut value: serde_json::Value =
+        serde_json::from_str(&json).unwrap_or_else(|_| panic!("Failed to parse mock data file: {}", file));
+    if let Some(data) = value.get_mut("data") {
+        value = data.take();
+    }
+    serde_json::from_value(value).unwrap_or_else(|e| panic!("Unexpected data in mock data file {}: {}", file, e))
+}
+
+/// USD fact for 2022-12-31 in the API's JSON shape, for tests to adjust
+pub fn fact_json(id: &str, concept: &str, value: impl Serialize) -> Value {
+    json!({
+        "id": id,
+        "concept_name": concept,
+        "concept_label": null,
+        "entity_id": "entity-123",
+        "entity_name": null,
+        "period_end": "2022-12-31",
+        "value": value,
+        "unit": "USD",
+        "report_id": "rpt-1",
+        "filing_url": null
+    })
+}
+
+/// Deserialize a fact built with [`fact_json`]
+pub fn to_fact(json: Value) -> Fact {
+    serde_json::from_value(json).unwrap_or_else(|e| panic!("Invalid fact JSON: {}", e))
+}
+
+/// Exact decimal from its literal
+pub fn dec(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
@@ -0,0 +1,128 @@
+use std::str::FromStr;
+use xbrl_api_client::data::facts::{Decimals, Precision};
+use xbrl_api_client::parser::instance::{parse_instance, parse_instance_file};
+use xbrl_api_client::{Decimal, FactValue, Period, XbrlApiError};
+
+const INSTANCE: &str = include_str!("mock_data/instance.xml");
+
+fn dec(value: &str) -> FactValue {
+    FactValue::Decimal(Decimal::from_str(value).unwrap())
+}
+
+#[test]
+fn test_report_metadata_from_cover_page() {
+    let instance = parse_instance(INSTANCE, "ex-20221231").unwrap();
+    let report = &instance.report;
+
+    assert_eq!(report.id, "ex-20221231");
+    assert_eq!(report.entity_name, "Example Corp");
+    assert_eq!(report.entity_id, "0000123456");
+    assert_eq!(report.cik.as_deref(), Some("0000123456"));
+    assert_eq!(report.fiscal_year, 2022);
+    assert_eq!(report.fiscal_period, "FY");
+    assert_eq!(report.taxonomy, "us-gaap");
+    assert_eq!(report.report_type.as_deref(), Some("10-K"));
+    assert_eq!(instance.schema_refs, ["ex-20221231.xsd"]);
+}
+
+#[test]
+fn test_contexts_and_units() {
+    let instance = parse_instance(INSTANCE, "rpt").unwrap();
+
+    assert_eq!(instance.contexts.len(), 3);
+    assert!(instance.contexts["I2022"].period.is_instant());
+    let retail = &instance.contexts["FY2022_Retail"];
+    assert_eq!(retail.dimensions["srt:SegmentAxis"], "ex:RetailMember");
+    assert_eq!(retail.dimensions["ex:StoreAxis"], "042");
+    assert_eq!(retail.entity_name.as_deref(), Some("Example Corp"));
+
+    assert!(instance.units["USD"].is_monetary());
+    assert!(instance.units["USDPerShare"].is_per_share());
+}
+
+#[test]
+fn test_midnight_datetimes_end_on_the_previous_day() {
+    let midnight = INSTANCE
+        .replace("<xbrli:endDate>2022-12-31</xbrli:endDate>", "<xbrli:endDate>2023-01-01T00:00:00</xbrli:endDate>")
+        .replace("<xbrli:instant>2022-12-31</xbrli:instant>", "<xbrli:instant>2023-01-01T00:00:00</xbrli:instant>");
+    let instance = parse_instance(&midnight, "rpt").unwrap();
+    let plain = parse_instance(INSTANCE, "rpt").unwrap();
+
+    for id in ["FY2022", "I2022", "FY2022_Retail"] {
+        assert_eq!(instance.contexts[id].period, plain.contexts[id].period, "context {}", id);
+    }
+}
+
+#[test]
+fn test_facts() {
+    let instance = parse_instance(INSTANCE, "rpt").unwrap();
+    let fact = |id: &str| instance.facts.iter().find(|f| f.id == id).unwrap();
+
+    // 5 cover facts, 5 financial facts and one fact inside a tuple
+    assert_eq!(instance.facts.len(), 11);
+
+    let revenues = fact("f-rev");
+    assert_eq!(revenues.concept_name, "Revenues");
+    assert_eq!(revenues.value, dec("123456789012345678000"));
+    assert_eq!(revenues.decimals, Some(Decimals::Finite(-3)));
+    assert_eq!(revenues.entity_name.as_deref(), Some("Example Corp"));
+    assert_eq!(revenues.report_id, "rpt");
+    assert_eq!(revenues.dimensions, None);
+    assert_eq!(
+        revenues.period,
+        Period::duration(
+            chrono::NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2022, 12, 31).unwrap()
+        )
+        .unwrap()
+    );
+
+    let retail = fact("f-rev-retail");
+    assert_eq!(retail.dimensions.as_ref().unwrap()["srt:SegmentAxis"], "ex:RetailMember");
+
+    let assets = fact("f-assets");
+    assert_eq!(assets.precision, Some(Precision::Finite(4)));
+    assert!(assets.period.is_instant());
+
+    let eps = instance.facts.iter().find(|f| f.concept_name == "EarningsPerShareBasic").unwrap();
+    assert_eq!(eps.value, dec("6.11"));
+    assert!(eps.unit.as_ref().unwrap().is_per_share());
+
+    let goodwill = instance.facts.iter().find(|f| f.concept_name == "Goodwill").unwrap();
+    assert!(goodwill.value.is_nil());
+
+    let lease = instance.facts.iter().find(|f| f.concept_name == "LeaseTerm").unwrap();
+    assert_eq!(lease.value, FactValue::String("Five years".to_string()));
+    assert_eq!(lease.unit, None);
+}
+
+#[test]
+fn test_footnotes() {
+    let instance = parse_instance(INSTANCE, "rpt").unwrap();
+
+    assert_eq!(instance.footnotes.len(), 1);
+    let footnote = &instance.footnotes[0];
+    assert_eq!(footnote.id, "fn-1");
+    assert_eq!(footnote.text, "Includes restated amounts.");
+    assert_eq!(footnote.lang.as_deref(), Some("en-US"));
+    assert_eq!(footnote.fact_ids, ["f-rev", "f-assets"]);
+}
+
+#[test]
+fn test_parse_instance_file_uses_file_stem() {
+    let instance = parse_instance_file("tests/mock_data/instance.xml").unwrap();
+    assert_eq!(instance.report.id, "instance");
+    assert!(instance.facts.iter().all(|fact| fact.report_id == "instance"));
+}
+
+#[test]
+fn test_invalid_documents() {
+    assert!(matches!(parse_instance("<not-xml", "rpt"), Err(XbrlApiError::ParseError(_))));
+    assert!(matches!(parse_instance("<html/>", "rpt"), Err(XbrlApiError::ParseError(_))));
+
+    let unknown_context = INSTANCE.replace("contextRef=\"I2022\" unitRef=\"USD\" precision", "contextRef=\"missing\" unitRef=\"USD\" precision");
+    match parse_instance(&unknown_context, "rpt") {
+        Err(XbrlApiError::ParseError(message)) => assert!(message.contains("missing")),
+        other => panic!("Expected a parse error but got: {:?}", other),
+    }
+}
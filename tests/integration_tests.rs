@@ -1,7 +1,6 @@
-mod mocks;
+mod fixtures;
 
-use mocks::server::MockXbrlServer;
-use rstest::rstest;
+use fixtures::fixture;
 use tokio::runtime::Runtime;
 use xbrl_api_client::{
     api::{
@@ -9,9 +8,11 @@ use xbrl_api_client::{
         models::SearchParams,
     },
     data::{
-        facts::FactValue,
-        taxonomy::Taxonomy,
+        facts::{Fact, FactValue},
+        reports::Report,
+        taxonomy::{Concept, Dimension, Network, Taxonomy},
     },
+    testing::{MockXbrlServer, MockXbrlServerBuilder, Route},
     utils::errors::XbrlApiError,
 };
 
@@ -27,15 +28,14 @@ struct TestContext {
     client: XbrlClient,
 }
 
-// Single fixture that creates both mock server and client
-#[rstest::fixture]
-fn test_context() -> TestContext {
-    let mock_server = run_async(MockXbrlServer::start());
+// Start a mock server with the given contents and a client pointed at it
+fn test_context(server: MockXbrlServerBuilder) -> TestContext {
+    let mock_server = run_async(server.start());
     
     // Create client with the mock server's URL
     let api_key = "test_api_key";
     let mut client = XbrlClient::new(api_key);
-    client.set_base_url(&mock_server.url());
+    client.set_base_url(&mock_server.url()).unwrap();
     
     TestContext {
         mock_server,
@@ -43,11 +43,13 @@ fn test_context() -> TestContext {
     }
 }
 
-#[rstest]
-fn test_get_taxonomies(mut test_context: TestContext) {
+#[test]
+fn test_get_taxonomies() {
     // Arrange - Configure mock using our bundled mock server
+    let test_context = test_context(
+        MockXbrlServer::builder().taxonomies(&fixture::<Vec<Taxonomy>>("taxonomies.json")),
+    );
     println!("Mock server URL: {}", test_context.mock_server.url());
-    run_async(test_context.mock_server.mock_taxonomies());
     
     // Act & Assert - Use the client from our bundled context
     run_async(async {
@@ -68,10 +70,12 @@ fn test_get_taxonomies(mut test_context: TestContext) {
     });
 }
 
-#[rstest]
-fn test_get_reports(mut test_context: TestContext) {
+#[test]
+fn test_get_reports() {
     // Arrange - use our bundled mock server
-    run_async(test_context.mock_server.mock_reports("us-gaap"));
+    let test_context = test_context(
+        MockXbrlServer::builder().reports("us-gaap", &fixture::<Vec<Report>>("reports.json")),
+    );
     
     // Act & Assert - use the client from our bundled context
     run_async(async {
@@ -91,10 +95,12 @@ fn test_get_reports(mut test_context: TestContext) {
     });
 }
 
-#[rstest]
-fn test_get_facts(mut test_context: TestContext) {
+#[test]
+fn test_get_facts() {
     // Arrange
-    run_async(test_context.mock_server.mock_facts("rpt-123456"));
+    let test_context = test_context(
+        MockXbrlServer::builder().facts("rpt-123456", &fixture::<Vec<Fact>>("facts.json")),
+    );
     
     // Act & Assert
     run_async(async {
@@ -112,18 +118,20 @@ fn test_get_facts(mut test_context: TestContext) {
         
         // Check if we can match enum values correctly
         match &facts[0].value {
-            FactValue::Number(val) => {
-                assert_eq!(*val, 1000000.0);
+            FactValue::Decimal(val) => {
+                assert_eq!(*val, xbrl_api_client::Decimal::from(1000000));
             },
-            _ => panic!("Expected a number value"),
+            _ => panic!("Expected a decimal value"),
         }
     });
 }
 
-#[rstest]
-fn test_get_concept_details(mut test_context: TestContext) {
+#[test]
+fn test_get_concept_details() {
     // Arrange
-    run_async(test_context.mock_server.mock_concept_details("us-gaap", "Assets"));
+    let test_context = test_context(
+        MockXbrlServer::builder().concepts("us-gaap", &[fixture::<Concept>("concept_details.json")]),
+    );
     
     // Act & Assert
     run_async(async {
@@ -147,10 +155,12 @@ fn test_get_concept_details(mut test_context: TestContext) {
     });
 }
 
-#[rstest]
-fn test_search(mut test_context: TestContext) {
+#[test]
+fn test_search() {
     // Arrange
-    run_async(test_context.mock_server.mock_search());
+    let test_context = test_context(
+        MockXbrlServer::builder().search_results(&fixture::<Vec<Fact>>("search_results.json")),
+    );
     
     // Act & Assert
     run_async(async {
@@ -182,54 +192,57 @@ fn test_search(mut test_context: TestContext) {
     });
 }
 
-#[rstest]
-fn test_unauthorized_error(mut test_context: TestContext) {
+#[test]
+fn test_unauthorized_error() {
     // Arrange
-    run_async(test_context.mock_server.mock_unauthorized());
+    let test_context = test_context(MockXbrlServer::builder().require_api_key("another_api_key"));
     
     // Act & Assert
     run_async(async {
-        // Create request - no /api/v1/ prefix needed as we've removed it from the mock
-        let request = reqwest::Client::new().get(format!("{}/unauthorized", test_context.mock_server.url()));
-        
-        let result = test_context.client.execute_request::<Vec<Taxonomy>>(request).await;
+        // The server only accepts another key
+        let result = test_context.client.get_taxonomies().await;
         
         assert!(result.is_err());
         match result {
-            Err(XbrlApiError::ApiError { status_code, .. }) => {
-                assert_eq!(status_code, 401);
+            Err(XbrlApiError::Unauthorized { message, method, url }) => {
+                assert_eq!(message, "Unauthorized. Invalid API key.");
+                assert_eq!(method, "GET");
+                assert!(url.ends_with("/taxonomies"));
             },
-            _ => panic!("Expected an API error with status code 401"),
+            _ => panic!("Expected an unauthorized error"),
         }
     });
 }
 
-#[rstest]
-fn test_server_error(mut test_context: TestContext) {
+#[test]
+fn test_server_error() {
     // Arrange
-    run_async(test_context.mock_server.mock_server_error());
+    let test_context = test_context(MockXbrlServer::builder().fail(Route::Taxonomies, 500));
     
     // Act & Assert
     run_async(async {
-        // Create request - no /api/v1/ prefix needed
-        let request = reqwest::Client::new().get(format!("{}/error", test_context.mock_server.url()));
-        
-        let result = test_context.client.execute_request::<Vec<Taxonomy>>(request).await;
+        // Server errors are retried, then returned
+        let mut client = test_context.client.clone();
+        client.set_retry_policy(xbrl_api_client::api::retry::RetryPolicy::none()).unwrap();
+        let result = client.get_taxonomies().await;
         
         assert!(result.is_err());
         match result {
-            Err(XbrlApiError::ApiError { status_code, .. }) => {
+            Err(XbrlApiError::ServerError { status_code, message, .. }) => {
                 assert_eq!(status_code, 500);
+                assert_eq!(message, "Injected failure");
             },
-            _ => panic!("Expected an API error with status code 500"),
+            _ => panic!("Expected a server error with status code 500"),
         }
     });
 }
 
-#[rstest]
-fn test_get_concepts(mut test_context: TestContext) {
+#[test]
+fn test_get_concepts() {
     // Arrange
-    run_async(test_context.mock_server.mock_concepts("us-gaap"));
+    let test_context = test_context(
+        MockXbrlServer::builder().concepts("us-gaap", &fixture::<Vec<Concept>>("concepts.json")),
+    );
     
     // Act & Assert
     run_async(async {
@@ -245,10 +258,12 @@ fn test_get_concepts(mut test_context: TestContext) {
     });
 }
 
-#[rstest]
-fn test_get_dimensions(mut test_context: TestContext) {
+#[test]
+fn test_get_dimensions() {
     // Arrange
-    run_async(test_context.mock_server.mock_dimensions("us-gaap"));
+    let test_context = test_context(
+        MockXbrlServer::builder().dimensions("us-gaap", &fixture::<Vec<Dimension>>("dimensions.json")),
+    );
     
     // Act & Assert
     run_async(async {
@@ -268,10 +283,12 @@ fn test_get_dimensions(mut test_context: TestContext) {
     });
 }
 
-#[rstest]
-fn test_get_networks(mut test_context: TestContext) {
+#[test]
+fn test_get_networks() {
     // Arrange
-    run_async(test_context.mock_server.mock_networks("us-gaap"));
+    let test_context = test_context(
+        MockXbrlServer::builder().networks("us-gaap", &fixture::<Vec<Network>>("networks.json")),
+    );
     
     // Act & Assert
     run_async(async {
@@ -288,10 +305,12 @@ fn test_get_networks(mut test_context: TestContext) {
     });
 }
 
-#[rstest]
-fn test_get_network_details(mut test_context: TestContext) {
+#[test]
+fn test_get_network_details() {
     // Arrange
-    run_async(test_context.mock_server.mock_network_details("net-123"));
+    let test_context = test_context(
+        MockXbrlServer::builder().networks("us-gaap", &[fixture::<Network>("network_details.json")]),
+    );
     
     // Act & Assert
     run_async(async {
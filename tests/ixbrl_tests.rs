@@ -0,0 +1,163 @@
+use std::str::FromStr;
+use xbrl_api_client::parser::ixbrl::{parse_inline, parse_inline_file, InlineFact};
+use xbrl_api_client::parser::transform::apply_format;
+use xbrl_api_client::{Decimal, FactValue, XbrlApiError};
+
+const INLINE: &str = include_str!("mock_data/inline.htm");
+
+fn dec(value: &str) -> FactValue {
+    FactValue::Decimal(Decimal::from_str(value).unwrap())
+}
+
+fn find<'a>(facts: &'a [InlineFact], id: &str) -> &'a InlineFact {
+    facts.iter().find(|f| f.fact.id == id).unwrap()
+}
+
+#[test]
+fn test_numeric_transformations() {
+    let document = parse_inline(INLINE, "ex-20221231").unwrap();
+
+    let revenues = find(&document.facts, "f-rev");
+    assert_eq!(revenues.fact.concept_name, "Revenues");
+    assert_eq!(revenues.fact.value, dec("1234500000"));
+    assert!(revenues.fact.unit.as_ref().unwrap().is_monetary());
+
+    assert_eq!(find(&document.facts, "f-loss").fact.value, dec("-12345000"));
+    assert_eq!(find(&document.facts, "f-goodwill").fact.value, dec("0"));
+    assert_eq!(find(&document.facts, "f-eu").fact.value, dec("1234567"));
+}
+
+#[test]
+fn test_non_numeric_text_and_continuations() {
+    let document = parse_inline(INLINE, "ex-20221231").unwrap();
+
+    let policy = &find(&document.facts, "f-policy").fact;
+    assert_eq!(
+        policy.value,
+        FactValue::String("Revenue is recognized when control transfers to the customer.".to_string())
+    );
+
+    let period_end = &find(&document.facts, "f-period-end").fact;
+    assert_eq!(period_end.value, FactValue::String("2022-12-31".to_string()));
+}
+
+#[test]
+fn test_report_and_hidden_facts() {
+    let document = parse_inline(INLINE, "ex-20221231").unwrap();
+
+    assert_eq!(document.report.entity_name, "Example Corp");
+    assert_eq!(document.report.cik.as_deref(), Some("0000123456"));
+    assert_eq!(document.report.fiscal_year, 2022);
+    assert_eq!(document.report.report_type.as_deref(), Some("10-K"));
+    assert_eq!(document.report.taxonomy, "us-gaap");
+    assert_eq!(document.schema_refs, ["ex-20221231.xsd"]);
+
+    let hidden: Vec<_> = document.facts.iter().filter(|f| f.location.hidden).collect();
+    assert_eq!(hidden.len(), 3);
+    assert!(hidden.iter().all(|f| f.fact.concept_name.starts_with("Document") || f.fact.concept_name == "EntityCentralIndexKey"));
+    assert_eq!(find(&document.facts, "f-rev").fact.entity_name.as_deref(), Some("Example Corp"));
+}
+
+#[test]
+fn test_source_locations() {
+    let document = parse_inline(INLINE, "ex-20221231").unwrap();
+    let revenues = find(&document.facts, "f-rev");
+
+    let line = INLINE.lines().position(|line| line.contains("id=\"f-rev\"")).unwrap() as u32 + 1;
+    assert_eq!(revenues.location.line, line);
+    assert!(!revenues.location.hidden);
+    assert!(INLINE[revenues.location.offset..].starts_with("<ix:nonFraction id=\"f-rev\""));
+}
+
+#[test]
+fn test_parse_inline_file_and_into_facts() {
+    let facts = parse_inline_file("tests/mock_data/inline.htm").unwrap().into_facts();
+    assert_eq!(facts.len(), 11);
+    assert!(facts.iter().all(|fact| fact.report_id == "inline"));
+}
+
+#[test]
+fn test_xhtml_doctype() {
+    let with_doctype = INLINE.replacen(
+        "<html ",
+        "<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.1//EN\" \"http://www.w3.org/TR/xhtml11/DTD/xhtml11.dtd\">\n<html ",
+        1,
+    );
+    let document = parse_inline(&with_doctype, "ex-20221231").unwrap();
+    assert_eq!(document.facts.len(), parse_inline(INLINE, "ex-20221231").unwrap().facts.len());
+}
+
+#[test]
+fn test_transformation_registry() {
+    assert_eq!(apply_format("ixt:num-dot-decimal", "1,234,567.89").unwrap(), "1234567.89");
+    assert_eq!(apply_format("ixt:numcommadecimal", "1 234,5").unwrap(), "1234.5");
+    assert_eq!(apply_format("ixt:fixed-zero", "-").unwrap(), "0");
+    assert_eq!(apply_format("ixt:date-day-monthname-year-en", "31 Dec 2022").unwrap(), "2022-12-31");
+    assert_eq!(apply_format("ixt:date-month-day-year", "12/31/22").unwrap(), "2022-12-31");
+    assert_eq!(apply_format("ixt:date-year-month-day", "2022-12-31").unwrap(), "2022-12-31");
+    assert!(apply_format("ixt:date-month-day-year", "31/12/2022").is_err());
+    assert!(matches!(apply_format("ixt:num-word-en", "five"), Err(XbrlApiError::ParseError(_))));
+
+    // Text that is not a number is rejected rather than reduced to its digits
+    for garbage in ["12 apples", "1.2.3", "$100", "1,234.5.6", "5-10", ",5", "1,234,"] {
+        assert!(apply_format("ixt:num-dot-decimal", garbage).is_err(), "{:?} was accepted", garbage);
+    }
+    assert_eq!(apply_format("ixt:num-dot-decimal", "1'234.50").unwrap(), "1234.50");
+}
+
+#[test]
+fn test_unknown_transformation_skips_the_fact() {
+    let unknown = INLINE.replace(
+        "decimals=\"0\" format=\"ixt:fixed-zero\"",
+        "decimals=\"0\" format=\"ixt-sec:numwordsen\"",
+    );
+    let all = parse_inline(INLINE, "rpt").unwrap();
+    let document = parse_inline(&unknown, "rpt").unwrap();
+
+    assert_eq!(document.facts.len(), all.facts.len() - 1);
+    assert!(document.facts.iter().all(|f| f.fact.id != "f-goodwill"));
+    assert_eq!(find(&document.facts, "f-rev").fact.value, dec("1234500000"));
+    assert!(all.skipped.is_empty());
+
+    let skipped = &document.skipped[..];
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].id, "f-goodwill");
+    assert_eq!(skipped[0].name, "us-gaap:Goodwill");
+    assert_eq!(skipped[0].location.line, 43);
+    assert!(skipped[0].reason.contains("ixt-sec:numwordsen"));
+}
+
+#[test]
+fn test_invalid_inline_documents() {
+    assert!(matches!(parse_inline("<html><body>", "rpt"), Err(XbrlApiError::ParseError(_))));
+    assert!(matches!(
+        parse_inline("<html xmlns=\"http://www.w3.org/1999/xhtml\"><body/></html>", "rpt"),
+        Err(XbrlApiError::ParseError(_))
+    ));
+}
+
+#[test]
+fn test_malformed_facts_are_skipped() {
+    let all = parse_inline(INLINE, "rpt").unwrap();
+
+    let bad_number = INLINE.replace(">12,345<", ">twelve<");
+    let document = parse_inline(&bad_number, "rpt").unwrap();
+    assert_eq!(document.facts.len(), all.facts.len() - 1);
+    assert_eq!(find(&document.facts, "f-rev").fact.value, dec("1234500000"));
+    assert_eq!(find(&document.facts, "f-goodwill").fact.value, dec("0"));
+    let skipped = &document.skipped[..];
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].id, "f-loss");
+    assert_eq!(skipped[0].location.line, 42);
+    assert!(skipped[0].reason.contains("twelve"), "{}", skipped[0].reason);
+
+    let bad_scale = INLINE.replace("scale=\"6\"", "scale=\"six\"");
+    let document = parse_inline(&bad_scale, "rpt").unwrap();
+    assert_eq!(document.facts.len(), all.facts.len() - 1);
+    assert_eq!(document.skipped[0].id, "f-rev");
+
+    let broken_chain = INLINE.replace("id=\"c1\"", "id=\"c2\"");
+    let document = parse_inline(&broken_chain, "rpt").unwrap();
+    assert_eq!(document.facts.len(), all.facts.len() - 1);
+    assert_eq!(document.skipped.len(), 1);
+}
@@ -1,3 +1,6 @@
+// Style lints these tests predate
+#![allow(clippy::assertions_on_constants)]
+
 use dotenv::dotenv;
 use xbrl_api_client::api::client::XbrlClient;
 use xbrl_api_client::api::models::SearchParams;
@@ -62,12 +65,14 @@ async fn test_library_integration() {
                 FactValue::String(s) => format!("String: {}", s),
                 FactValue::Number(n) => format!("Number: {}", n),
                 FactValue::Boolean(b) => format!("Boolean: {}", b),
+                FactValue::Decimal(d) => format!("Decimal: {}", d),
+                FactValue::Nil => "Nil".to_string(),
             };
             println!(
                 "{}. {} ({}): {}",
                 i + 1,
                 fact.concept_name,
-                fact.period_end,
+                fact.period,
                 value_str
             );
         }
@@ -1,3 +1,6 @@
+// Style lints these tests predate
+#![allow(clippy::assertions_on_constants)]
+
 use std::env;
 use std::panic;
 use tokio::runtime::Runtime;
@@ -115,9 +118,8 @@ fn test_invalid_api_key_handling() {
             // This is the expected error
             // (although the real implementation might return a different specific error)
         },
-        Err(XbrlApiError::ApiError { status_code, .. }) => {
-            // API might return 401 Unauthorized or another error code
-            assert!(status_code == 401 || status_code == 403);
+        Err(XbrlApiError::Unauthorized { .. } | XbrlApiError::Forbidden { .. }) => {
+            // API might return 401 Unauthorized or 403 Forbidden
         },
         _ => {
             // Unexpected result
@@ -1,2 +0,0 @@
-// Export the server module
-pub mod server;
\ No newline at end of file
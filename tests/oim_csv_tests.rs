@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use xbrl_api_client::data::facts::{Decimals, Fact};
+use xbrl_api_client::oim::csv::{parse_xbrl_csv_file, to_xbrl_csv, XbrlCsvReport};
+use xbrl_api_client::oim::OimOptions;
+use xbrl_api_client::parser::instance::parse_instance_file;
+use xbrl_api_client::{FactValue, XbrlApiError};
+
+fn instance_facts() -> Vec<Fact> {
+    parse_instance_file("tests/mock_data/instance.xml").unwrap().facts
+}
+
+fn instance_options() -> OimOptions {
+    OimOptions::new().us_gaap(2022).namespace("ex", "http://www.example.com/20221231")
+}
+
+fn assert_same_facts(facts: &[Fact], parsed: &[Fact]) {
+    assert_eq!(parsed.len(), facts.len());
+    for original in facts {
+        let fact = parsed.iter().find(|f| f.id == original.id).unwrap();
+        assert_eq!(fact.concept_name, original.concept_name);
+        assert_eq!(fact.entity_id, original.entity_id);
+        assert_eq!(fact.period, original.period);
+        assert_eq!(fact.unit, original.unit);
+        assert_eq!(fact.dimensions, original.dimensions);
+        assert_eq!(fact.value.to_string(), original.value.to_string());
+        match original.decimals {
+            Some(Decimals::Finite(_)) => assert_eq!(fact.decimals, original.decimals),
+            _ => assert_eq!(fact.decimals, None),
+        }
+    }
+}
+
+#[test]
+fn test_templates_follow_dimensions() {
+    let report = to_xbrl_csv(&instance_facts(), &instance_options()).unwrap();
+    let metadata = &report.metadata;
+    assert_eq!(metadata.document_info.document_type, "https://xbrl.org/2021/xbrl-csv");
+    assert_eq!(
+        metadata.table_templates.keys().collect::<Vec<_>>(),
+        ["facts", "facts_ex_StoreAxis_srt_SegmentAxis"]
+    );
+
+    let template = &metadata.table_templates["facts_ex_StoreAxis_srt_SegmentAxis"];
+    assert_eq!(template.row_id_column.as_deref(), Some("id"));
+    assert_eq!(template.dimensions["srt:SegmentAxis"], "$srt_SegmentAxis");
+    assert_eq!(template.dimensions["concept"], "$concept");
+    assert!(template.columns["value"].dimensions.is_some());
+    assert!(template.columns["ex_StoreAxis"].dimensions.is_none());
+    assert_eq!(metadata.tables["facts"].url, "facts.csv");
+
+    let table = &report.tables["facts_ex_StoreAxis_srt_SegmentAxis.csv"];
+    let mut lines = table.lines();
+    assert_eq!(
+        lines.next(),
+        Some("id,concept,entity,period,unit,decimals,ex_StoreAxis,srt_SegmentAxis,value")
+    );
+    assert_eq!(
+        lines.next(),
+        Some(
+            "f-rev-retail,us-gaap:Revenues,cik:0000123456,2022-01-01T00:00:00/2023-01-01T00:00:00,\
+             iso4217:USD,-3,042,ex:RetailMember,400000"
+        )
+    );
+    assert!(report.tables["facts.csv"].contains(",#nil\n"));
+}
+
+#[test]
+fn test_round_trip_through_files() {
+    let facts = instance_facts();
+    let directory = std::env::temp_dir().join(format!("xbrl-csv-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let report = to_xbrl_csv(&facts, &instance_options()).unwrap();
+    let metadata_path = report.write_to_directory(&directory, "ex-20221231").unwrap();
+    let parsed = parse_xbrl_csv_file(&metadata_path).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    assert_same_facts(&facts, &parsed);
+    assert!(parsed.iter().all(|f| f.report_id == "ex-20221231"));
+    let goodwill = parsed.iter().find(|f| f.concept_name == "Goodwill").unwrap();
+    assert_eq!(goodwill.value, FactValue::Nil);
+
+    // The directory is gone now, so writing names the file that failed
+    match report.write_to_directory(&directory, "ex-20221231") {
+        Err(XbrlApiError::Io { path, source }) => {
+            assert!(path.starts_with(&directory));
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_round_trip_in_memory() {
+    let mut facts = instance_facts();
+    facts[0].value = FactValue::String("#1, \"quoted\"".to_string());
+    let report = to_xbrl_csv(&facts, &instance_options()).unwrap();
+
+    let metadata = report.metadata_json().unwrap();
+    let parsed = XbrlCsvReport::parse(&metadata, |url| Ok(report.tables[url].clone()))
+        .unwrap()
+        .into_facts("rpt")
+        .unwrap();
+    assert_same_facts(&facts, &parsed);
+}
+
+#[test]
+fn test_empty_string_round_trips() {
+    let mut facts = instance_facts();
+    facts[0].value = FactValue::String(String::new());
+    facts[1].value = FactValue::String("#empty".to_string());
+    let report = to_xbrl_csv(&facts, &instance_options()).unwrap();
+    assert!(report.tables.values().any(|table| table.contains(",#empty\n")));
+
+    let metadata = report.metadata_json().unwrap();
+    let parsed = XbrlCsvReport::parse(&metadata, |url| Ok(report.tables[url].clone()))
+        .unwrap()
+        .into_facts("rpt")
+        .unwrap();
+    assert_same_facts(&facts, &parsed);
+    let empty = parsed.iter().find(|f| f.id == facts[0].id).unwrap();
+    assert_eq!(empty.value, FactValue::String(String::new()));
+}
+
+#[test]
+fn test_hand_written_report() {
+    // Several fact columns per row, with fixed dimensions and decimals
+    let metadata = r#"{
+        "documentInfo": {
+            "documentType": "https://xbrl.org/2021/xbrl-csv",
+            "namespaces": {"us-gaap": "http://fasb.org/us-gaap/2024", "cik": "http://www.sec.gov/CIK",
+                           "iso4217": "http://www.xbrl.org/2003/iso4217"}
+        },
+        "tableTemplates": {
+            "balance": {
+                "rowIdColumn": "year",
+                "columns": {
+                    "year": {},
+                    "period": {},
+                    "Assets": {"dimensions": {"concept": "us-gaap:Assets"}, "decimals": -3},
+                    "Liabilities": {"dimensions": {"concept": "us-gaap:Liabilities"}, "decimals": "INF"}
+                },
+                "dimensions": {"entity": "cik:0000123456", "period": "$period", "unit": "iso4217:USD"}
+            }
+        },
+        "tables": {"balances": {"url": "balances.csv", "template": "balance"}}
+    }"#;
+    let tables = BTreeMap::from([(
+        "balances.csv".to_string(),
+        "year,period,Assets,Liabilities\n2022,2023-01-01T00:00:00,1000,\n".to_string(),
+    )]);
+
+    let facts = XbrlCsvReport::parse(metadata, |url| Ok(tables[url].clone()))
+        .unwrap()
+        .into_facts("rpt")
+        .unwrap();
+    // The empty Liabilities cell reports no fact
+    assert_eq!(facts.len(), 1);
+    assert_eq!(facts[0].id, "2022.Assets");
+    assert_eq!(facts[0].concept_name, "Assets");
+    assert_eq!(facts[0].entity_id, "0000123456");
+    assert_eq!(facts[0].decimals, Some(Decimals::Finite(-3)));
+    assert_eq!(facts[0].period.to_string(), "2022-12-31");
+}
+
+#[test]
+fn test_invalid_reports() {
+    assert!(matches!(
+        to_xbrl_csv(&instance_facts(), &OimOptions::new()),
+        Err(XbrlApiError::ValidationError(_))
+    ));
+
+    let wrong_type = r#"{"documentInfo": {"documentType": "https://xbrl.org/2021/xbrl-json"}}"#;
+    assert!(matches!(
+        XbrlCsvReport::parse(wrong_type, |_| Ok(String::new())),
+        Err(XbrlApiError::ParseError(_))
+    ));
+
+    let unknown_template = r#"{
+        "documentInfo": {"documentType": "https://xbrl.org/2021/xbrl-csv"},
+        "tables": {"t": {"url": "t.csv"}}
+    }"#;
+    let report = XbrlCsvReport::parse(unknown_template, |_| Ok("id\n".to_string())).unwrap();
+    assert!(matches!(report.into_facts("rpt"), Err(XbrlApiError::ParseError(_))));
+
+    assert!(matches!(
+        parse_xbrl_csv_file("tests/mock_data/no-such-report.json"),
+        Err(XbrlApiError::ParseError(_))
+    ));
+}
@@ -0,0 +1,142 @@
+use serde_json::Value;
+use std::str::FromStr;
+use xbrl_api_client::data::facts::{Decimals, Fact};
+use xbrl_api_client::oim::json::{parse_xbrl_json, to_xbrl_json, XbrlJsonDocument};
+use xbrl_api_client::oim::OimOptions;
+use xbrl_api_client::parser::instance::parse_instance_file;
+use xbrl_api_client::api::models::ApiResponse;
+use xbrl_api_client::{Decimal, FactValue, XbrlApiError};
+
+fn instance_options() -> OimOptions {
+    OimOptions::new().us_gaap(2022).namespace("ex", "http://www.example.com/20221231")
+}
+
+fn api_facts() -> Vec<Fact> {
+    let json = std::fs::read_to_string("tests/mock_data/facts.json").unwrap();
+    let response: ApiResponse<Vec<Fact>> = serde_json::from_str(&json).unwrap();
+    response.data.unwrap()
+}
+
+#[test]
+fn test_export_document_structure() {
+    let facts = parse_instance_file("tests/mock_data/instance.xml").unwrap().facts;
+    let json: Value = serde_json::from_str(&to_xbrl_json(&facts, &instance_options()).unwrap()).unwrap();
+
+    let info = &json["documentInfo"];
+    assert_eq!(info["documentType"], "https://xbrl.org/2021/xbrl-json");
+    assert_eq!(info["namespaces"]["ex"], "http://www.example.com/20221231");
+    assert_eq!(info["namespaces"]["srt"], "http://fasb.org/srt/2022");
+    // Only prefixes in use are declared
+    assert!(info["namespaces"].get("dei").is_none());
+
+    let revenues = &json["facts"]["f-rev-retail"];
+    assert_eq!(revenues["value"], "400000");
+    assert_eq!(revenues["decimals"], -3);
+    let dimensions = &revenues["dimensions"];
+    assert_eq!(dimensions["concept"], "us-gaap:Revenues");
+    assert_eq!(dimensions["entity"], "cik:0000123456");
+    assert_eq!(dimensions["period"], "2022-01-01T00:00:00/2023-01-01T00:00:00");
+    assert_eq!(dimensions["unit"], "iso4217:USD");
+    assert_eq!(dimensions["srt:SegmentAxis"], "ex:RetailMember");
+    assert_eq!(dimensions["ex:StoreAxis"], "042");
+
+    let goodwill = json["facts"]
+        .as_object()
+        .unwrap()
+        .values()
+        .find(|f| f["dimensions"]["concept"] == "us-gaap:Goodwill")
+        .unwrap();
+    assert_eq!(goodwill["value"], Value::Null);
+    assert_eq!(goodwill["dimensions"]["period"], "2023-01-01T00:00:00");
+}
+
+#[test]
+fn test_round_trip_instance_facts() {
+    let facts = parse_instance_file("tests/mock_data/instance.xml").unwrap().facts;
+    let json = to_xbrl_json(&facts, &instance_options()).unwrap();
+    let parsed = parse_xbrl_json(&json, "ex-20221231").unwrap();
+
+    assert_eq!(parsed.len(), facts.len());
+    for (original, fact) in facts.iter().zip(&parsed) {
+        assert_eq!(fact.concept_name, original.concept_name);
+        assert_eq!(fact.entity_id, original.entity_id);
+        assert_eq!(fact.period, original.period);
+        assert_eq!(fact.unit, original.unit);
+        assert_eq!(fact.dimensions, original.dimensions);
+        assert_eq!(fact.value.to_string(), original.value.to_string());
+        assert_eq!(fact.report_id, "ex-20221231");
+        match original.decimals {
+            Some(Decimals::Finite(_)) => assert_eq!(fact.decimals, original.decimals),
+            _ => assert_eq!(fact.decimals, None),
+        }
+    }
+
+    let revenues = parsed.iter().find(|f| f.id == "f-rev").unwrap();
+    assert_eq!(
+        revenues.value,
+        FactValue::Decimal(Decimal::from_str("123456789012345678000").unwrap())
+    );
+}
+
+#[test]
+fn test_round_trip_api_facts() {
+    let facts = api_facts();
+    let json = to_xbrl_json(&facts, &OimOptions::new().us_gaap(2024)).unwrap();
+    let parsed = parse_xbrl_json(&json, "rpt-123456").unwrap();
+
+    assert_eq!(parsed.len(), facts.len());
+    for (original, fact) in facts.iter().zip(&parsed) {
+        assert_eq!(fact.id, original.id);
+        assert_eq!(fact.concept_name, original.concept_name);
+        assert_eq!(fact.entity_id, original.entity_id);
+        assert_eq!(fact.period, original.period);
+        assert_eq!(fact.unit, original.unit);
+        assert_eq!(fact.value.as_decimal(), original.value.as_decimal());
+    }
+}
+
+#[test]
+fn test_duplicate_ids_are_suffixed() {
+    let mut facts = api_facts();
+    facts[1].id = facts[0].id.clone();
+    let document = XbrlJsonDocument::from_facts(&facts, &OimOptions::new().us_gaap(2024)).unwrap();
+    assert_eq!(document.facts[0].0, "fact-12345");
+    assert_eq!(document.facts[1].0, "fact-12345_2");
+}
+
+#[test]
+fn test_unbound_prefix_is_rejected() {
+    let facts = parse_instance_file("tests/mock_data/instance.xml").unwrap().facts;
+    assert!(matches!(
+        to_xbrl_json(&facts, &OimOptions::new()),
+        Err(XbrlApiError::ValidationError(_))
+    ));
+    // No taxonomy release is assumed for us-gaap
+    assert!(matches!(
+        to_xbrl_json(&api_facts(), &OimOptions::new()),
+        Err(XbrlApiError::ValidationError(message)) if message.contains("us-gaap")
+    ));
+
+    let json = r#"{
+        "documentInfo": {"documentType": "https://xbrl.org/2021/xbrl-json", "namespaces": {}},
+        "facts": {"f1": {"value": "1", "dimensions": {"concept": "us-gaap:Assets"}}}
+    }"#;
+    assert!(matches!(parse_xbrl_json(json, "rpt"), Err(XbrlApiError::ParseError(_))));
+}
+
+#[test]
+fn test_invalid_documents() {
+    let wrong_type = r#"{"documentInfo": {"documentType": "https://xbrl.org/2021/xbrl-csv"}, "facts": {}}"#;
+    assert!(matches!(parse_xbrl_json(wrong_type, "rpt"), Err(XbrlApiError::ParseError(_))));
+
+    let no_concept = r#"{
+        "documentInfo": {"documentType": "https://xbrl.org/2021/xbrl-json"},
+        "facts": {"f1": {"value": "1", "dimensions": {}}}
+    }"#;
+    assert!(matches!(parse_xbrl_json(no_concept, "rpt"), Err(XbrlApiError::ParseError(_))));
+
+    assert!(matches!(
+        parse_xbrl_json("not json", "rpt"),
+        Err(XbrlApiError::SerializationError(_))
+    ));
+}
@@ -0,0 +1,175 @@
+mod fixtures;
+
+use fixtures::fact_json;
+use futures::{StreamExt, TryStreamExt};
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::models::{Page, PageInfo, PaginationParams};
+use xbrl_api_client::{SearchParams, XbrlClient};
+
+fn fact(index: usize) -> Value {
+    fact_json(&format!("fact-{}", index), "Assets", 1000 * index)
+}
+
+fn page_body(indices: std::ops::Range<usize>, page: u32, total_count: u64) -> Value {
+    json!({
+        "status": "success",
+        "message": null,
+        "data": indices.map(fact).collect::<Vec<_>>(),
+        "errors": null,
+        "pagination": { "page": page, "page_size": 2, "total_count": total_count }
+    })
+}
+
+async fn mount_fact_pages(server: &MockServer, endpoint: &str, http_method: &str) {
+    for (page, indices) in [(1, 0..2), (2, 2..4), (3, 4..5)] {
+        Mock::given(method(http_method))
+            .and(path(endpoint))
+            .and(query_param("page", page.to_string()))
+            .and(query_param("pageSize", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page_body(indices, page, 5)))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+}
+
+fn client_for(server: &MockServer) -> XbrlClient {
+    XbrlClient::with_base_url("test_api_key", &server.uri()).unwrap()
+}
+
+#[tokio::test]
+async fn test_get_facts_paged_returns_metadata() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/facts"))
+        .and(query_param("reportId", "rpt-123456"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_body(2..4, 2, 5)))
+        .mount(&server)
+        .await;
+
+    let page = client_for(&server)
+        .get_facts_paged("rpt-123456", PaginationParams::new(2, 2))
+        .await
+        .unwrap();
+
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.items[0].id, "fact-2");
+    assert_eq!(page.page, 2);
+    assert_eq!(page.total_count, Some(5));
+    assert_eq!(page.next_page, Some(3));
+}
+
+#[tokio::test]
+async fn test_paged_request_replaces_existing_page_parameters() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/facts"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_body(2..4, 2, 5)))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server);
+    let mut url = client.endpoints().facts("rpt-123456");
+    url.query_pairs_mut().append_pair("page", "9");
+    let page: Page<Value> = client
+        .execute_paged_request(reqwest::Client::new().get(url), PaginationParams::new(2, 2))
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 2);
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests[0].url.query(), Some("reportId=rpt-123456&page=2&pageSize=2"));
+}
+
+#[tokio::test]
+async fn test_stream_facts_walks_every_page() {
+    let server = MockServer::start().await;
+    mount_fact_pages(&server, "/facts", "GET").await;
+
+    let facts: Vec<_> = client_for(&server)
+        .stream_facts("rpt-123456", 2)
+        .try_collect()
+        .await
+        .unwrap();
+
+    let ids: Vec<_> = facts.iter().map(|f| f.id.as_str()).collect();
+    assert_eq!(ids, ["fact-0", "fact-1", "fact-2", "fact-3", "fact-4"]);
+}
+
+#[tokio::test]
+async fn test_stream_is_lazy() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/facts"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page_body(0..2, 1, 5)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Only the first page may be requested when the consumer stops early
+    let first: Vec<_> = client_for(&server)
+        .stream_facts("rpt-123456", 2)
+        .take(2)
+        .collect()
+        .await;
+    assert_eq!(first.len(), 2);
+}
+
+#[tokio::test]
+async fn test_stream_search_walks_every_page() {
+    let server = MockServer::start().await;
+    mount_fact_pages(&server, "/search", "POST").await;
+
+    let params = SearchParams {
+        taxonomy: "us-gaap".to_string(),
+        concept_name: Some("Assets".to_string()),
+        entity_id: None,
+        fiscal_year: None,
+        fiscal_period: None,
+        dimension_name: None,
+        member_name: None,
+        text_search: None,
+        value_greater_than: None,
+        value_less_than: None,
+    };
+    let count = client_for(&server)
+        .stream_search(params, 2)
+        .try_fold(0, |count, _| async move { Ok(count + 1) })
+        .await
+        .unwrap();
+    assert_eq!(count, 5);
+}
+
+#[tokio::test]
+async fn test_stream_reports_stops_on_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/reports"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let results: Vec<_> = client_for(&server).stream_reports("us-gaap", 10).collect().await;
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+}
+
+#[test]
+fn test_next_page_is_derived_from_metadata() {
+    let requested = PaginationParams::new(1, 2);
+
+    let info = PageInfo {
+        total_pages: Some(1),
+        ..PageInfo::default()
+    };
+    assert_eq!(Page::new(vec![1, 2], requested, Some(info)).next_page, None);
+
+    // Without totals a full page implies more may follow, a short page ends the walk
+    assert_eq!(Page::new(vec![1, 2], requested, None).next_page, Some(2));
+    assert_eq!(Page::new(vec![1], requested, None).next_page, None);
+    assert!(!Page::<i32>::new(vec![], requested, None).has_next());
+}
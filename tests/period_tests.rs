@@ -0,0 +1,150 @@
+use chrono::NaiveDate;
+use xbrl_api_client::data::facts::Fact;
+use xbrl_api_client::data::period::{parse_date, parse_end_date, FiscalYearEnd, PeriodLength};
+use xbrl_api_client::{Period, XbrlApiError};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn fact_json(period: serde_json::Value) -> serde_json::Value {
+    let mut fact = serde_json::json!({
+        "id": "fact-1",
+        "concept_name": "Revenues",
+        "concept_label": "Revenues",
+        "entity_id": "entity-123",
+        "entity_name": "Example Corp",
+        "value": 1000.0,
+        "unit": "USD",
+        "report_id": "rpt-123456",
+        "filing_url": null
+    });
+    fact.as_object_mut().unwrap().extend(period.as_object().unwrap().clone());
+    fact
+}
+
+#[test]
+fn test_fact_period_deserialization() {
+    let fact: Fact = serde_json::from_value(fact_json(serde_json::json!({ "period_end": "2022-12-31" }))).unwrap();
+    assert_eq!(fact.period, Period::Instant(date(2022, 12, 31)));
+
+    let fact: Fact = serde_json::from_value(fact_json(serde_json::json!({
+        "period_start": "2022-01-01",
+        "period_end": "2023-01-01T00:00:00"
+    })))
+    .unwrap();
+    assert_eq!(
+        fact.period,
+        Period::Duration {
+            start: date(2022, 1, 1),
+            end: date(2022, 12, 31)
+        }
+    );
+
+    let fact: Fact = serde_json::from_value(fact_json(serde_json::json!({ "period_start": null }))).unwrap();
+    assert!(fact.period.is_forever());
+
+    let result = serde_json::from_value::<Fact>(fact_json(serde_json::json!({ "period_end": "31/12/2022" })));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_period_serialization_round_trip() {
+    let period = Period::Duration {
+        start: date(2022, 10, 1),
+        end: date(2022, 12, 31),
+    };
+    let fact: Fact = serde_json::from_value(fact_json(serde_json::json!({
+        "period_start": "2022-10-01",
+        "period_end": "2022-12-31"
+    })))
+    .unwrap();
+
+    let json = serde_json::to_value(&fact).unwrap();
+    assert_eq!(json["period_start"], "2022-10-01");
+    assert_eq!(json["period_end"], "2022-12-31");
+    assert_eq!(serde_json::from_value::<Fact>(json).unwrap().period, period);
+}
+
+#[test]
+fn test_midnight_end_is_end_of_previous_day() {
+    assert_eq!(parse_end_date("2023-01-01T00:00:00").unwrap(), date(2022, 12, 31));
+    assert_eq!(parse_end_date("2023-01-01T00:00:00Z").unwrap(), date(2022, 12, 31));
+    assert_eq!(parse_end_date("2022-12-31T17:30:00").unwrap(), date(2022, 12, 31));
+    assert_eq!(parse_end_date("2022-12-31").unwrap(), date(2022, 12, 31));
+    assert_eq!(parse_date("2022-01-01T00:00:00").unwrap(), date(2022, 1, 1));
+
+    assert_eq!(
+        Period::from_raw(Some("2022-01-01T00:00:00"), Some("2023-01-01T00:00:00")).unwrap(),
+        Period::Duration {
+            start: date(2022, 1, 1),
+            end: date(2022, 12, 31)
+        }
+    );
+    assert_eq!(
+        Period::from_raw(None, Some("2022-07-01T00:00:00")).unwrap(),
+        Period::Instant(date(2022, 6, 30))
+    );
+}
+
+#[test]
+fn test_duration_validation() {
+    assert!(Period::duration(date(2022, 1, 1), date(2022, 12, 31)).is_ok());
+    assert!(matches!(
+        Period::duration(date(2022, 12, 31), date(2022, 1, 1)),
+        Err(XbrlApiError::ValidationError(_))
+    ));
+    assert!(Period::from_raw(Some("2022-01-01"), None).is_err());
+    assert_eq!(Period::from_raw(None, Some("forever")).unwrap(), Period::Forever);
+}
+
+#[test]
+fn test_duration_days_and_classification() {
+    let quarter = Period::duration(date(2022, 10, 1), date(2022, 12, 31)).unwrap();
+    assert_eq!(quarter.duration_days(), Some(92));
+    assert!(quarter.is_quarter());
+
+    let year = Period::duration(date(2022, 1, 1), date(2022, 12, 31)).unwrap();
+    assert_eq!(year.duration_days(), Some(365));
+    assert!(year.is_annual());
+
+    // 53-week fiscal year
+    let long_year = Period::duration(date(2021, 9, 26), date(2022, 10, 1)).unwrap();
+    assert!(long_year.is_annual());
+
+    let half = Period::duration(date(2022, 1, 1), date(2022, 6, 30)).unwrap();
+    assert_eq!(half.length(), PeriodLength::HalfYear);
+
+    let instant = Period::Instant(date(2022, 12, 31));
+    assert_eq!(instant.duration_days(), None);
+    assert_eq!(instant.length(), PeriodLength::Instant);
+    assert_eq!(instant.to_string(), "2022-12-31");
+    assert_eq!(year.to_string(), "2022-01-01/2022-12-31");
+}
+
+#[test]
+fn test_fiscal_year_alignment() {
+    // Fiscal year ending on the last Saturday of September
+    let fye = FiscalYearEnd::parse("--09-24").unwrap();
+    assert_eq!(fye, FiscalYearEnd { month: 9, day: 24 });
+
+    let fiscal_2022 = Period::duration(date(2021, 9, 26), date(2022, 9, 24)).unwrap();
+    assert_eq!(fiscal_2022.fiscal_year(fye), Some(2022));
+    assert!(fiscal_2022.is_fiscal_year(fye));
+    assert!(!fiscal_2022.is_fiscal_year(FiscalYearEnd::calendar()));
+
+    let q1 = Period::duration(date(2022, 9, 25), date(2022, 12, 31)).unwrap();
+    assert_eq!(q1.fiscal_year(fye), Some(2023));
+    assert_eq!(q1.fiscal_quarter(fye), Some(1));
+
+    let q3 = Period::duration(date(2023, 4, 2), date(2023, 7, 1)).unwrap();
+    assert_eq!(q3.fiscal_quarter(fye), Some(3));
+
+    let calendar_q4 = Period::duration(date(2022, 10, 1), date(2022, 12, 31)).unwrap();
+    assert_eq!(calendar_q4.fiscal_year(FiscalYearEnd::calendar()), Some(2022));
+    assert_eq!(calendar_q4.fiscal_quarter(FiscalYearEnd::calendar()), Some(4));
+    assert_eq!(Period::Forever.fiscal_year(fye), None);
+
+    assert!(FiscalYearEnd::parse("--13-01").is_err());
+    assert!(FiscalYearEnd::parse("0930").is_err());
+}
@@ -0,0 +1,110 @@
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::rate_limit::RateLimiter;
+use xbrl_api_client::{XbrlApiError, XbrlClient};
+
+#[tokio::test]
+async fn test_burst_is_available_immediately() {
+    let limiter = RateLimiter::new(1.0, 3).unwrap();
+
+    assert!(limiter.try_acquire().is_ok());
+    assert!(limiter.try_acquire().is_ok());
+    assert!(limiter.try_acquire().is_ok());
+
+    // The bucket is empty; the next token arrives after roughly one second
+    let wait = limiter.try_acquire().expect_err("bucket should be empty");
+    assert!(wait > Duration::from_millis(900) && wait <= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_acquire_waits_for_refill() {
+    let limiter = RateLimiter::new(20.0, 1).unwrap();
+
+    let started = Instant::now();
+    for _ in 0..5 {
+        limiter.acquire().await;
+    }
+
+    // One token from the burst, then four more at 50ms each
+    assert!(started.elapsed() >= Duration::from_millis(190));
+}
+
+#[test]
+fn test_invalid_rate_limits_are_rejected() {
+    assert!(matches!(RateLimiter::new(0.0, 1), Err(XbrlApiError::ValidationError(_))));
+    assert!(matches!(RateLimiter::new(f64::NAN, 1), Err(XbrlApiError::ValidationError(_))));
+    assert!(matches!(RateLimiter::new(5.0, 0), Err(XbrlApiError::ValidationError(_))));
+
+    let result = XbrlClient::builder()
+        .api_key("test_api_key")
+        .rate_limit(-1.0, 5)
+        .build();
+    assert!(matches!(result, Err(XbrlApiError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_cloned_clients_share_the_budget() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "success",
+            "message": null,
+            "data": [],
+            "errors": null
+        })))
+        .expect(6)
+        .mount(&server)
+        .await;
+
+    let client = XbrlClient::builder()
+        .api_key("test_api_key")
+        .base_url(&server.uri())
+        .rate_limit(10.0, 2)
+        .build()
+        .unwrap();
+
+    let started = Instant::now();
+    let tasks: Vec<_> = (0..3)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move {
+                client.get_taxonomies().await.unwrap();
+                client.get_taxonomies().await.unwrap();
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    // Six requests against a burst of two leaves four waiting 100ms each
+    assert!(started.elapsed() >= Duration::from_millis(390));
+}
+
+#[test]
+fn test_rate_limiter_can_be_shared_between_builders() {
+    let limiter = RateLimiter::new(5.0, 1).unwrap();
+
+    let first = XbrlClient::builder()
+        .api_key("test_api_key")
+        .rate_limiter(limiter.clone())
+        .build()
+        .unwrap();
+    let second = XbrlClient::builder()
+        .api_key("test_api_key")
+        .rate_limiter(limiter.clone())
+        .build()
+        .unwrap();
+
+    assert!(first.rate_limiter().unwrap().try_acquire().is_ok());
+    assert!(second.rate_limiter().unwrap().try_acquire().is_err());
+
+    let result = XbrlClient::builder()
+        .api_key("test_api_key")
+        .rate_limit(5.0, 1)
+        .rate_limiter(limiter)
+        .build();
+    assert!(matches!(result, Err(XbrlApiError::ValidationError(_))));
+}
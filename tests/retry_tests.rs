@@ -0,0 +1,197 @@
+use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::retry::{parse_retry_after, RetryPolicy};
+use xbrl_api_client::{SearchParams, XbrlApiError, XbrlClient};
+
+fn taxonomies_body() -> serde_json::Value {
+    serde_json::json!({
+        "status": "success",
+        "message": null,
+        "data": [{ "name": "us-gaap", "description": "US GAAP Taxonomy", "version": "2022" }],
+        "errors": null
+    })
+}
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::new()
+        .max_attempts(3)
+        .initial_backoff(Duration::from_millis(1))
+        .max_backoff(Duration::from_secs(2))
+        .jitter(false)
+}
+
+fn client_for(server: &MockServer, policy: RetryPolicy) -> XbrlClient {
+    XbrlClient::builder()
+        .api_key("test_api_key")
+        .base_url(&server.uri())
+        .retry_policy(policy)
+        .build()
+        .unwrap()
+}
+
+fn search_params() -> SearchParams {
+    SearchParams {
+        taxonomy: "us-gaap".to_string(),
+        concept_name: Some("Assets".to_string()),
+        entity_id: None,
+        fiscal_year: None,
+        fiscal_period: None,
+        dimension_name: None,
+        member_name: None,
+        text_search: None,
+        value_greater_than: None,
+        value_less_than: None,
+    }
+}
+
+async fn mount_transient_failures(server: &MockServer, http_method: &str, endpoint: &str, status: u16, times: u64) {
+    Mock::given(method(http_method))
+        .and(path(endpoint))
+        .respond_with(ResponseTemplate::new(status))
+        .up_to_n_times(times)
+        .expect(times)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_retries_transient_status_until_success() {
+    let server = MockServer::start().await;
+    mount_transient_failures(&server, "GET", "/taxonomies", 503, 2).await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(taxonomies_body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server, fast_policy());
+    let taxonomies = client.get_taxonomies().await.unwrap();
+    assert_eq!(taxonomies.len(), 1);
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(502))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server, fast_policy());
+    match client.get_taxonomies().await {
+        Err(XbrlApiError::ServerError { status_code, .. }) => assert_eq!(status_code, 502),
+        other => panic!("Expected a 502 server error but got: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_does_not_retry_non_retryable_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server, fast_policy());
+    assert!(client.get_taxonomies().await.is_err());
+}
+
+#[tokio::test]
+async fn test_honours_retry_after_header() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(taxonomies_body()))
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server, fast_policy());
+    let started = Instant::now();
+    client.get_taxonomies().await.unwrap();
+    assert!(started.elapsed() >= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_search_is_not_retried_without_opt_in() {
+    let server = MockServer::start().await;
+    mount_transient_failures(&server, "POST", "/search", 503, 1).await;
+
+    let client = client_for(&server, fast_policy());
+    match client.search(search_params()).await {
+        Err(XbrlApiError::ServerError { status_code, .. }) => assert_eq!(status_code, 503),
+        other => panic!("Expected a 503 server error but got: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_search_is_retried_with_opt_in() {
+    let server = MockServer::start().await;
+    mount_transient_failures(&server, "POST", "/search", 503, 1).await;
+    Mock::given(method("POST"))
+        .and(path("/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "success",
+            "message": null,
+            "data": [],
+            "errors": null
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client_for(&server, fast_policy().retry_non_idempotent(true));
+    let facts = client.search(search_params()).await.unwrap();
+    assert!(facts.is_empty());
+}
+
+#[test]
+fn test_backoff_grows_exponentially_and_is_capped() {
+    let policy = RetryPolicy::new()
+        .initial_backoff(Duration::from_millis(100))
+        .max_backoff(Duration::from_millis(350))
+        .multiplier(2.0)
+        .jitter(false);
+
+    assert_eq!(policy.delay_for(1, None), Duration::from_millis(100));
+    assert_eq!(policy.delay_for(2, None), Duration::from_millis(200));
+    assert_eq!(policy.delay_for(3, None), Duration::from_millis(350));
+
+    let jittered = policy.clone().jitter(true).delay_for(2, None);
+    assert!(jittered >= Duration::from_millis(100) && jittered <= Duration::from_millis(200));
+}
+
+#[test]
+fn test_parse_retry_after() {
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+    assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+
+    // HTTP dates in the past mean "retry now"
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+    assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+    assert_eq!(parse_retry_after(&headers), None);
+}
+
+#[test]
+fn test_invalid_retry_policy_is_rejected() {
+    let result = XbrlClient::builder()
+        .api_key("test_api_key")
+        .retry_policy(RetryPolicy::new().max_attempts(0))
+        .build();
+    assert!(matches!(result, Err(XbrlApiError::ValidationError(_))));
+}
@@ -0,0 +1,140 @@
+mod fixtures;
+
+use fixtures::{dec, fact_json, to_fact};
+use serde_json::{json, Value};
+use xbrl_api_client::data::statements::Statement;
+use xbrl_api_client::data::taxonomy::Network;
+use xbrl_api_client::{Fact, FactValue};
+
+const NEGATED: &str = "http://www.xbrl.org/2009/role/negatedLabel";
+const TOTAL: &str = "http://www.xbrl.org/2003/role/totalLabel";
+
+fn node(concept: &str, order: f64, level: u32, preferred_label: Option<&str>, children: Option<Vec<Value>>) -> Value {
+    json!({
+        "concept_name": concept,
+        "concept_label": concept,
+        "parent": null,
+        "order": order,
+        "level": level,
+        "preferred_label": preferred_label,
+        "children": children
+    })
+}
+
+fn network(nodes: Vec<Value>) -> Network {
+    serde_json::from_value(json!({
+        "id": "net-1",
+        "name": "Consolidated Statements of Operations",
+        "short_name": "Income Statement",
+        "description": null,
+        "taxonomy": "us-gaap",
+        "role": "http://example.com/role/StatementsOfOperations",
+        "nodes": nodes
+    }))
+    .unwrap()
+}
+
+fn fact(concept: &str, start: Option<&str>, end: &str, value: &str) -> Fact {
+    let mut fact = fact_json(&format!("{}-{}", concept, end), concept, value);
+    fact["period_start"] = json!(start);
+    fact["period_end"] = json!(end);
+    to_fact(fact)
+}
+
+fn cell(value: &str) -> Option<FactValue> {
+    Some(FactValue::Decimal(dec(value)))
+}
+
+#[test]
+fn test_income_statement_rows_and_columns() {
+    let network = network(vec![node(
+        "IncomeStatementAbstract",
+        1.0,
+        1,
+        None,
+        Some(vec![
+            node("OperatingExpenses", 2.0, 2, Some(TOTAL), None),
+            node("Revenues", 1.0, 2, None, None),
+            node("CostOfRevenue", 1.5, 2, Some(NEGATED), None),
+            node("ResearchAndDevelopmentExpense", 1.7, 2, None, None),
+        ]),
+    )]);
+
+    let mut dimensional = fact("Revenues", Some("2022-01-01"), "2022-12-31", "1");
+    dimensional.dimensions = Some([("srt:SegmentAxis".to_string(), "Retail".to_string())].into());
+
+    let facts = vec![
+        fact("Revenues", Some("2021-01-01"), "2021-12-31", "900"),
+        fact("Revenues", Some("2022-01-01"), "2022-12-31", "1000"),
+        fact("CostOfRevenue", Some("2022-01-01"), "2022-12-31", "400"),
+        fact("OperatingExpenses", Some("2022-01-01"), "2022-12-31", "600"),
+        fact("Unrelated", Some("2020-01-01"), "2020-12-31", "5"),
+        dimensional,
+    ];
+
+    let statement = Statement::build(&network, &facts);
+    assert_eq!(statement.title, "Income Statement");
+    assert_eq!(statement.columns.len(), 2);
+    assert_eq!(statement.columns[0].to_string(), "2022-01-01/2022-12-31");
+
+    let concepts: Vec<_> = statement.rows.iter().map(|r| r.concept_name.as_str()).collect();
+    // R&D has no facts and is dropped; order follows `order`, not the input
+    assert_eq!(concepts, ["IncomeStatementAbstract", "Revenues", "CostOfRevenue", "OperatingExpenses"]);
+
+    let header = &statement.rows[0];
+    assert!(header.is_abstract);
+    assert_eq!(header.depth, 0);
+    assert_eq!(header.values, vec![None, None]);
+
+    let revenues = statement.row("Revenues").unwrap();
+    assert_eq!(revenues.depth, 1);
+    assert_eq!(revenues.values, vec![cell("1000"), cell("900")]);
+
+    let cost = statement.row("CostOfRevenue").unwrap();
+    assert!(cost.is_negated);
+    assert_eq!(cost.values, vec![cell("-400"), None]);
+
+    assert!(statement.row("OperatingExpenses").unwrap().is_total);
+}
+
+#[test]
+fn test_cash_flow_period_start_and_end_balances() {
+    let period_start = "http://www.xbrl.org/2003/role/periodStartLabel";
+    let period_end = "http://www.xbrl.org/2003/role/periodEndLabel";
+    let network = network(vec![
+        node("Cash", 1.0, 1, Some(period_start), None),
+        node("CashPeriodIncreaseDecrease", 2.0, 1, None, None),
+        node("Cash", 3.0, 1, Some(period_end), None),
+    ]);
+    let facts = vec![
+        fact("Cash", None, "2021-12-31", "100"),
+        fact("Cash", None, "2022-12-31", "150"),
+        fact("CashPeriodIncreaseDecrease", Some("2022-01-01"), "2022-12-31", "50"),
+    ];
+
+    let statement = Statement::build(&network, &facts);
+    assert_eq!(statement.columns.len(), 1);
+    let values: Vec<_> = statement.rows.iter().map(|row| row.values[0].clone()).collect();
+    assert_eq!(values, vec![cell("100"), cell("50"), cell("150")]);
+}
+
+#[test]
+fn test_balance_sheet_from_mock_network() {
+    let response: Value = serde_json::from_str(include_str!("mock_data/network_details.json")).unwrap();
+    let network: Network = serde_json::from_value(response["data"].clone()).unwrap();
+    let facts = vec![
+        fact("Assets", None, "2022-12-31", "1000"),
+        fact("Assets", None, "2021-12-31", "800"),
+        fact("CurrentAssets", None, "2022-12-31", "400"),
+        fact("Liabilities", None, "2022-12-31", "300"),
+    ];
+
+    let statement = Statement::build(&network, &facts);
+    assert_eq!(statement.title, "Balance Sheet");
+    assert_eq!(statement.columns.iter().map(ToString::to_string).collect::<Vec<_>>(), ["2022-12-31", "2021-12-31"]);
+
+    let rows: Vec<_> = statement.rows.iter().map(|r| (r.concept_name.as_str(), r.depth)).collect();
+    assert_eq!(rows, [("Assets", 0), ("CurrentAssets", 1), ("Liabilities", 0)]);
+    assert!(!statement.rows[0].is_abstract);
+    assert_eq!(statement.rows[0].values, vec![cell("1000"), cell("800")]);
+}
@@ -0,0 +1,266 @@
+#![cfg(feature = "store")]
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::models::ApiResponse;
+use xbrl_api_client::parser::dts::load_taxonomy_directory;
+use xbrl_api_client::parser::instance::parse_instance_file;
+use xbrl_api_client::store::{Store, SyncOptions, SyncSummary};
+use xbrl_api_client::{Report, SearchParams, XbrlApiError, XbrlClient};
+
+fn to_json<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap()
+}
+
+fn mock_reports() -> Vec<Report> {
+    let json = std::fs::read_to_string("tests/mock_data/reports.json").unwrap();
+    let response: ApiResponse<Vec<Report>> = serde_json::from_str(&json).unwrap();
+    response.data.unwrap()
+}
+
+fn search(taxonomy: &str) -> SearchParams {
+    SearchParams {
+        taxonomy: taxonomy.to_string(),
+        concept_name: None,
+        entity_id: None,
+        fiscal_year: None,
+        fiscal_period: None,
+        dimension_name: None,
+        member_name: None,
+        text_search: None,
+        value_greater_than: None,
+        value_less_than: None,
+    }
+}
+
+/// Store holding the parsed instance as report `ex-20221231`
+fn instance_store() -> Store {
+    let instance = parse_instance_file("tests/mock_data/instance.xml").unwrap();
+    let mut store = Store::open_in_memory().unwrap();
+    let mut report = instance.report;
+    report.id = "ex-20221231".to_string();
+    store.save_reports(&[report]).unwrap();
+    let mut facts = instance.facts;
+    for fact in &mut facts {
+        fact.report_id = "ex-20221231".to_string();
+    }
+    store.save_facts(&facts).unwrap();
+    store
+}
+
+#[test]
+fn test_facts_round_trip() {
+    let instance = parse_instance_file("tests/mock_data/instance.xml").unwrap();
+    let store = instance_store();
+
+    let facts = store.facts("ex-20221231").unwrap();
+    assert_eq!(facts.len(), instance.facts.len());
+    for (stored, original) in facts.iter().zip(&instance.facts) {
+        assert_eq!(stored.id, original.id);
+        assert_eq!(stored.value, original.value);
+        assert_eq!(stored.period, original.period);
+        assert_eq!(stored.unit, original.unit);
+        assert_eq!(stored.decimals, original.decimals);
+        assert_eq!(stored.precision, original.precision);
+        assert_eq!(stored.dimensions, original.dimensions);
+        assert_eq!(stored.entity_name, original.entity_name);
+    }
+    assert!(store.facts("missing").unwrap().is_empty());
+}
+
+#[test]
+fn test_saving_twice_replaces_rows() {
+    let mut store = Store::open_in_memory().unwrap();
+    let reports = mock_reports();
+    store.save_reports(&reports).unwrap();
+    store.save_reports(&reports).unwrap();
+
+    let stored = store.reports("us-gaap").unwrap();
+    assert_eq!(stored.len(), 2);
+    assert_eq!(to_json(&stored[0]), to_json(&reports[0]));
+    assert_eq!(store.report("rpt-123457").unwrap().unwrap().report_type.as_deref(), Some("10-Q"));
+    assert!(store.report("missing").unwrap().is_none());
+
+    let instance = parse_instance_file("tests/mock_data/instance.xml").unwrap();
+    store.save_facts(&instance.facts).unwrap();
+    store.save_facts(&instance.facts).unwrap();
+    assert_eq!(store.facts(&instance.report.id).unwrap().len(), instance.facts.len());
+}
+
+#[test]
+fn test_concepts_and_networks_round_trip() {
+    let dts = load_taxonomy_directory("tests/mock_data/taxonomy").unwrap();
+    let mut store = Store::open_in_memory().unwrap();
+    store.save_concepts(&dts.concepts).unwrap();
+    store.save_networks(&dts.networks).unwrap();
+
+    let concepts = store.concepts("ex").unwrap();
+    assert_eq!(concepts.len(), dts.concepts.len());
+    let revenues = store.concept("ex", "Revenues").unwrap().unwrap();
+    assert_eq!(to_json(&revenues), to_json(dts.concept("Revenues").unwrap()));
+    assert!(store.concept("ex", "Missing").unwrap().is_none());
+
+    let networks = store.networks("Example Taxonomy").unwrap();
+    assert_eq!(networks.len(), 1);
+    assert_eq!(to_json(&networks[0]), to_json(&dts.networks[0]));
+}
+
+#[test]
+fn test_search() {
+    let store = instance_store();
+
+    let mut params = search("us-gaap");
+    params.concept_name = Some("Revenues".to_string());
+    assert_eq!(store.search(&params).unwrap().len(), 2);
+
+    params.member_name = Some("RetailMember".to_string());
+    let retail = store.search(&params).unwrap();
+    assert_eq!(retail.len(), 1);
+    assert_eq!(retail[0].id, "f-rev-retail");
+
+    let mut params = search("");
+    params.dimension_name = Some("ex:StoreAxis".to_string());
+    assert_eq!(store.search(&params).unwrap().len(), 1);
+
+    let mut params = search("us-gaap");
+    params.fiscal_year = Some(2022);
+    params.value_greater_than = Some(1_000_000.0);
+    let names: Vec<_> = store.search(&params).unwrap().into_iter().map(|f| f.concept_name).collect();
+    assert_eq!(names, ["Revenues", "Assets"]);
+
+    let mut params = search("us-gaap");
+    params.text_search = Some("Five".to_string());
+    assert_eq!(store.search(&params).unwrap()[0].concept_name, "LeaseTerm");
+
+    assert!(store.search(&search("ifrs")).unwrap().is_empty());
+}
+
+#[test]
+fn test_search_matches_wildcards_literally() {
+    let store = instance_store();
+
+    for text in ["%", "_", "Fi_e", "\\"] {
+        let mut params = search("");
+        params.text_search = Some(text.to_string());
+        assert!(store.search(&params).unwrap().is_empty(), "{:?} matched as a wildcard", text);
+    }
+
+    let mut params = search("");
+    params.member_name = Some("Retail_ember".to_string());
+    assert!(store.search(&params).unwrap().is_empty());
+    params.member_name = None;
+    params.dimension_name = Some("%".to_string());
+    assert!(store.search(&params).unwrap().is_empty());
+}
+
+#[test]
+fn test_open_file() {
+    let path = std::env::temp_dir().join(format!("xbrl-store-{}.db", std::process::id()));
+    {
+        let mut store = Store::open(&path).unwrap();
+        store.save_reports(&mock_reports()).unwrap();
+    }
+    let store = Store::open(&path).unwrap();
+    assert_eq!(store.reports("us-gaap").unwrap().len(), 2);
+    std::fs::remove_file(&path).unwrap();
+
+    // SQLite failures keep the underlying error
+    match Store::open(std::env::temp_dir()) {
+        Err(XbrlApiError::Storage { backend, source }) => {
+            assert_eq!(backend, "SQLite");
+            assert!(source.downcast_ref::<rusqlite::Error>().is_some());
+        }
+        other => panic!("expected a storage error, got {:?}", other.map(|_| ())),
+    }
+}
+
+fn envelope(data: Value) -> Value {
+    json!({ "status": "success", "message": null, "data": data, "errors": null })
+}
+
+fn mock_file(name: &str) -> Value {
+    serde_json::from_str(&std::fs::read_to_string(format!("tests/mock_data/{}", name)).unwrap()).unwrap()
+}
+
+async fn mount_listing(server: &MockServer, reports: &[Value]) {
+    Mock::given(method("GET"))
+        .and(path("/reports"))
+        .and(query_param("taxonomy", "us-gaap"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(envelope(json!(reports))))
+        .mount(server)
+        .await;
+}
+
+async fn mount_facts(server: &MockServer, report_id: &str, expected_calls: u64) {
+    let fact = json!({
+        "id": format!("{}-assets", report_id),
+        "concept_name": "Assets",
+        "entity_id": "entity-123",
+        "period_end": "2022-12-31",
+        "value": 1000000,
+        "unit": "USD",
+        "report_id": report_id
+    });
+    Mock::given(method("GET"))
+        .and(path("/facts"))
+        .and(query_param("reportId", report_id))
+        .respond_with(ResponseTemplate::new(200).set_body_json(envelope(json!([fact]))))
+        .expect(expected_calls)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_incremental_sync() {
+    let server = MockServer::start().await;
+    let client = XbrlClient::with_base_url("test-key", &server.uri()).unwrap();
+    let reports = mock_file("reports.json")["data"].as_array().unwrap().clone();
+    let mut store = Store::open_in_memory().unwrap();
+
+    let definitions = [
+        ("/taxonomies", envelope(mock_file("taxonomies.json"))),
+        ("/concepts", mock_file("concepts.json")),
+        ("/networks", mock_file("networks.json")),
+    ];
+    for (endpoint, body) in definitions {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    mount_listing(&server, &reports[..1]).await;
+    mount_facts(&server, "rpt-123456", 1).await;
+
+    let options = SyncOptions::new("us-gaap").page_size(100);
+    let summary = store.sync(&client, &options).await.unwrap();
+    assert_eq!(
+        summary,
+        SyncSummary { reports: 1, facts: 1, skipped_reports: 0, concepts: 2, networks: 2 }
+    );
+    assert_eq!(store.last_filing_date("us-gaap").unwrap().unwrap().to_string(), "2023-02-28");
+    server.verify().await;
+
+    // The second sync only downloads the newly filed report
+    server.reset().await;
+    let mut older = reports[0].clone();
+    older["id"] = json!("rpt-100000");
+    older["filing_date"] = json!("2022-11-30");
+    mount_listing(&server, &[older, reports[0].clone(), reports[1].clone()]).await;
+    mount_facts(&server, "rpt-123456", 0).await;
+    mount_facts(&server, "rpt-100000", 0).await;
+    mount_facts(&server, "rpt-123457", 1).await;
+
+    let summary = store.sync(&client, &options).await.unwrap();
+    assert_eq!(
+        summary,
+        SyncSummary { reports: 1, facts: 1, skipped_reports: 2, concepts: 0, networks: 0 }
+    );
+    assert_eq!(store.last_filing_date("us-gaap").unwrap().unwrap().to_string(), "2023-05-10");
+    assert_eq!(store.reports("us-gaap").unwrap().len(), 2);
+    assert_eq!(store.facts("rpt-123457").unwrap()[0].id, "rpt-123457-assets");
+    assert_eq!(store.taxonomies().unwrap().len(), mock_file("taxonomies.json").as_array().unwrap().len());
+}
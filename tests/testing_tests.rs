@@ -0,0 +1,225 @@
+#![cfg(feature = "testing")]
+
+mod fixtures;
+
+use fixtures::{fact_json, to_fact};
+use futures::TryStreamExt;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use xbrl_api_client::api::models::PaginationParams;
+use xbrl_api_client::api::retry::RetryPolicy;
+use xbrl_api_client::data::taxonomy::{Concept, Network, NetworkNode};
+use xbrl_api_client::testing::{MockXbrlServer, Route, TEST_API_KEY};
+use xbrl_api_client::{Decimal, Fact, Report, SearchParams, Taxonomy, Unit, XbrlApiError, XbrlClient};
+
+fn taxonomy() -> Taxonomy {
+    Taxonomy {
+        name: "us-gaap".to_string(),
+        description: "US GAAP Taxonomy".to_string(),
+        version: "2024".to_string(),
+        documentation_url: None,
+    }
+}
+
+fn report(id: &str) -> Report {
+    Report {
+        id: id.to_string(),
+        accession_number: None,
+        filing_date: "2024-02-01".to_string(),
+        fiscal_period: "FY".to_string(),
+        fiscal_year: 2023,
+        entity_id: "0000320193".to_string(),
+        entity_name: "Example Corp".to_string(),
+        cik: None,
+        taxonomy: "us-gaap".to_string(),
+        report_type: Some("10-K".to_string()),
+        filing_url: None,
+    }
+}
+
+fn fact(id: usize, concept: &str, value: i64, segment: Option<&str>) -> Fact {
+    let mut fact = fact_json(&format!("fact-{}", id), concept, value);
+    fact["entity_id"] = json!("0000320193");
+    fact["entity_name"] = json!("Example Corp");
+    fact["period_end"] = json!("2023-12-31");
+    fact["dimensions"] = json!(segment.map(|member| json!({ "srt:SegmentAxis": member })));
+    to_fact(fact)
+}
+
+fn facts(count: usize) -> Vec<Fact> {
+    (1..=count).map(|id| fact(id, "Assets", id as i64 * 1000, None)).collect()
+}
+
+fn search(concept: &str) -> SearchParams {
+    SearchParams {
+        taxonomy: "us-gaap".to_string(),
+        concept_name: Some(concept.to_string()),
+        entity_id: None,
+        fiscal_year: None,
+        fiscal_period: None,
+        dimension_name: None,
+        member_name: None,
+        text_search: None,
+        value_greater_than: None,
+        value_less_than: None,
+    }
+}
+
+#[tokio::test]
+async fn test_serves_registered_values() {
+    let concept = Concept {
+        name: "Assets".to_string(),
+        label: "Assets".to_string(),
+        description: None,
+        taxonomy: "us-gaap".to_string(),
+        type_name: "monetaryItemType".to_string(),
+        period_type: Some("instant".to_string()),
+        balance: Some("debit".to_string()),
+        standard_label: None,
+        documentation: None,
+        is_abstract: Some(false),
+        is_nillable: Some(true),
+        references: None,
+        dimensions: None,
+    };
+    let network = Network {
+        id: "net-1".to_string(),
+        name: "Balance Sheet".to_string(),
+        short_name: None,
+        description: None,
+        taxonomy: "us-gaap".to_string(),
+        role: "http://example.com/role/BalanceSheet".to_string(),
+        nodes: Some(vec![NetworkNode {
+            concept_name: "Assets".to_string(),
+            concept_label: "Assets".to_string(),
+            parent: None,
+            order: Some(1.0),
+            level: Some(1),
+            preferred_label: None,
+            children: None,
+        }]),
+    };
+
+    let server = MockXbrlServer::builder()
+        .taxonomies(&[taxonomy()])
+        .reports("us-gaap", &[report("rpt-1"), report("rpt-2")])
+        .facts("rpt-1", &facts(3))
+        .concepts("us-gaap", &[concept])
+        .networks("us-gaap", &[network])
+        .start()
+        .await;
+    let client = server.client();
+
+    assert_eq!(client.get_taxonomies().await.unwrap()[0].version, "2024");
+    assert_eq!(client.get_reports("us-gaap").await.unwrap().len(), 2);
+    assert!(client.get_reports("ifrs").await.unwrap().is_empty());
+
+    let served = client.get_facts("rpt-1").await.unwrap();
+    assert_eq!(served.len(), 3);
+    assert_eq!(served[1].value.as_decimal(), Some(Decimal::from(2000)));
+    assert_eq!(served[1].unit, Some(Unit::monetary("USD")));
+
+    assert_eq!(client.get_concept_details("us-gaap", "Assets").await.unwrap().balance.as_deref(), Some("debit"));
+    assert!(matches!(
+        client.get_concept_details("us-gaap", "Missing").await,
+        Err(XbrlApiError::NotFound { .. })
+    ));
+    assert_eq!(client.get_networks("us-gaap").await.unwrap().len(), 1);
+    assert_eq!(client.get_network_details("net-1").await.unwrap().nodes.unwrap()[0].concept_name, "Assets");
+    assert_eq!(server.received_requests().await.len(), 8);
+}
+
+#[tokio::test]
+async fn test_search_filters_registered_facts() {
+    let server = MockXbrlServer::builder()
+        .facts(
+            "rpt-1",
+            &[
+                fact(1, "Assets", 500, None),
+                fact(2, "Assets", 5000, Some("ex:RetailMember")),
+                fact(3, "Liabilities", 700, None),
+            ],
+        )
+        .start()
+        .await;
+    let client = server.client();
+
+    assert_eq!(client.search(search("Assets")).await.unwrap().len(), 2);
+
+    let mut large = search("Assets");
+    large.value_greater_than = Some(1000.0);
+    assert_eq!(client.search(large).await.unwrap()[0].id, "fact-2");
+
+    let mut segmented = search("Assets");
+    segmented.member_name = Some("ex:RetailMember".to_string());
+    assert_eq!(client.search(segmented).await.unwrap().len(), 1);
+
+    let canned = MockXbrlServer::builder().search_results(&facts(1)).start().await;
+    assert_eq!(canned.client().search(search("Anything")).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_pagination() {
+    let server = MockXbrlServer::builder()
+        .facts("rpt-1", &facts(7))
+        .max_page_size(3)
+        .start()
+        .await;
+    let client = server.client();
+
+    let page = client.get_facts_paged("rpt-1", PaginationParams::new(4, 2)).await.unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.total_count, Some(7));
+    assert_eq!(page.next_page, None);
+
+    // Larger page sizes are capped, so streams take three requests
+    let streamed: Vec<Fact> = client.stream_facts("rpt-1", 50).try_collect().await.unwrap();
+    assert_eq!(streamed.len(), 7);
+    assert_eq!(streamed[6].id, "fact-7");
+
+    // Unpaged requests only see the first page, as against the real API
+    assert_eq!(client.get_facts("rpt-1").await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_error_injection() {
+    let server = MockXbrlServer::builder()
+        .taxonomies(&[taxonomy()])
+        .reports("us-gaap", &[report("rpt-1")])
+        .fail_times(Route::Taxonomies, 503, 2)
+        .fail(Route::Dimensions, 500)
+        .rate_limit(Route::Reports, 1, Duration::from_secs(0))
+        .start()
+        .await;
+
+    let retrying = XbrlClient::builder()
+        .api_key(TEST_API_KEY)
+        .base_url(&server.url())
+        .retry_policy(RetryPolicy::new().max_attempts(3).initial_backoff(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+    assert_eq!(retrying.get_taxonomies().await.unwrap().len(), 1);
+    assert_eq!(retrying.get_reports("us-gaap").await.unwrap().len(), 1);
+
+    let mut client = server.client();
+    client.set_retry_policy(RetryPolicy::none()).unwrap();
+    let result = client.get_dimensions("us-gaap").await;
+    assert!(matches!(result, Err(XbrlApiError::ServerError { status_code: 500, .. })));
+
+    let secured = MockXbrlServer::builder().require_api_key("right").start().await;
+    let unauthorized = XbrlClient::with_base_url("wrong", &secured.url()).unwrap().get_taxonomies().await;
+    assert!(matches!(unauthorized, Err(XbrlApiError::Unauthorized { .. })));
+}
+
+#[tokio::test]
+async fn test_latency_injection() {
+    let server = MockXbrlServer::builder()
+        .taxonomies(&[taxonomy()])
+        .latency(Duration::from_millis(150))
+        .start()
+        .await;
+
+    let started = Instant::now();
+    server.client().get_taxonomies().await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(150));
+}
@@ -0,0 +1,133 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::retry::RetryPolicy;
+use xbrl_api_client::XbrlClient;
+
+const API_KEY: &str = "super-secret-api-key";
+
+/// Collects formatted trace output in memory
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+impl CapturedLogs {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for CapturedLogs {
+    type Writer = CapturedLogs;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn capture_traces() -> (CapturedLogs, tracing::subscriber::DefaultGuard) {
+    let logs = CapturedLogs::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(logs.clone())
+        .with_ansi(false)
+        .with_max_level(tracing::Level::TRACE)
+        .with_span_events(FmtSpan::CLOSE)
+        .finish();
+    let guard = tracing::subscriber::set_default(subscriber);
+    (logs, guard)
+}
+
+#[tokio::test]
+async fn test_request_span_records_fields_without_api_key() {
+    let (logs, _guard) = capture_traces();
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/taxonomies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "success",
+            "message": null,
+            "data": [],
+            "errors": null
+        })))
+        .mount(&server)
+        .await;
+
+    let client = XbrlClient::builder()
+        .api_key(API_KEY)
+        .base_url(&server.uri())
+        .retry_policy(RetryPolicy::new().initial_backoff(Duration::from_millis(1)).jitter(false))
+        .build()
+        .unwrap();
+    client.get_taxonomies().await.unwrap();
+
+    let output = logs.contents();
+    assert!(output.contains("xbrl.request"), "missing request span: {}", output);
+    assert!(output.contains("get_taxonomies"));
+    assert!(output.contains("endpoint=/taxonomies"));
+    assert!(output.contains("status=200"));
+    assert!(output.contains("retries=1"));
+    assert!(output.contains("response_bytes="));
+    assert!(output.contains("latency_ms="));
+    assert!(output.contains("retrying request"));
+    assert!(!output.contains(API_KEY), "API key leaked into traces: {}", output);
+}
+
+#[tokio::test]
+async fn test_failed_request_is_traced_with_status() {
+    let (logs, _guard) = capture_traces();
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/reports"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let client = XbrlClient::with_base_url(API_KEY, &server.uri()).unwrap();
+    assert!(client.get_reports("us-gaap").await.is_err());
+
+    let output = logs.contents();
+    assert!(output.contains("status=404"));
+    assert!(output.contains("taxonomy=\"us-gaap\""));
+    assert!(!output.contains(API_KEY));
+}
+
+#[tokio::test]
+async fn test_transport_failure_records_latency_and_retries() {
+    let (logs, _guard) = capture_traces();
+
+    let client = XbrlClient::builder()
+        .api_key(API_KEY)
+        .base_url("http://127.0.0.1:1")
+        .retry_policy(RetryPolicy::new().initial_backoff(Duration::from_millis(1)).jitter(false))
+        .build()
+        .unwrap();
+    assert!(client.get_taxonomies().await.is_err());
+
+    let output = logs.contents();
+    assert!(output.contains("request failed"), "missing failure event: {}", output);
+    assert!(output.contains("latency_ms="));
+    assert!(output.contains("retries="));
+    assert!(!output.contains("retries=0"), "retries were not counted: {}", output);
+}
@@ -0,0 +1,114 @@
+use xbrl_api_client::data::units::Measure;
+use xbrl_api_client::{Fact, Unit};
+
+fn unit(value: &str) -> Unit {
+    value.parse().unwrap()
+}
+
+#[test]
+fn test_parse_simple_units() {
+    let usd = unit("USD");
+    assert_eq!(usd, unit("iso4217:USD"));
+    assert_eq!(usd, Unit::monetary("USD"));
+    assert!(usd.is_monetary());
+    assert_eq!(usd.currency(), Some("USD"));
+    assert_eq!(usd.to_string(), "iso4217:USD");
+
+    assert_eq!(unit("shares"), Unit::shares());
+    assert!(unit("xbrli:shares").is_shares());
+    assert!(unit("pure").is_pure());
+    assert_eq!(unit("pure").currency(), None);
+
+    let custom = unit("utr:Y");
+    assert_eq!(custom.numerators(), [Measure::new("utr", "Y")]);
+    assert!(!custom.is_monetary());
+}
+
+#[test]
+fn test_bare_measures_are_prefixed_only_when_known() {
+    assert_eq!(unit("EUR"), Unit::simple(Measure::new("iso4217", "EUR")));
+    assert_eq!(unit("XYZ").to_string(), "XYZ");
+    assert!(!unit("XYZ").is_monetary());
+    assert!(matches!(unit("USD/shares"), Unit::Divide { .. }));
+    assert!(matches!(unit("USD"), Unit::Measures(_)));
+}
+
+#[test]
+fn test_parse_divide_units() {
+    let eps = unit("USD/shares");
+    assert_eq!(eps, unit("iso4217:USD/xbrli:shares"));
+    assert!(eps.is_per_share());
+    assert!(!eps.is_monetary());
+    assert_eq!(eps.currency(), Some("USD"));
+    assert_eq!(eps.to_string(), "iso4217:USD/xbrli:shares");
+
+    let rate = unit("iso4217:EUR/iso4217:USD");
+    assert!(rate.is_divide());
+    assert!(!rate.is_per_share());
+    assert_eq!(rate.currency(), None);
+}
+
+#[test]
+fn test_measure_order_does_not_matter() {
+    assert_eq!(unit("utr:Y*USD"), unit("USD*utr:Y"));
+    assert_eq!(unit("USD*utr:Y").to_string(), "iso4217:USD*utr:Y");
+}
+
+#[test]
+fn test_invalid_units_are_rejected() {
+    assert!("".parse::<Unit>().is_err());
+    assert!("USD/".parse::<Unit>().is_err());
+    assert!(":USD".parse::<Unit>().is_err());
+    assert!("US Dollars".parse::<Unit>().is_err());
+}
+
+#[test]
+fn test_fact_unit_serde() {
+    let fact: Fact = serde_json::from_value(serde_json::json!({
+        "id": "fact-1",
+        "concept_name": "EarningsPerShareBasic",
+        "concept_label": null,
+        "entity_id": "entity-123",
+        "entity_name": null,
+        "period_start": "2022-01-01",
+        "period_end": "2022-12-31",
+        "value": "6.11",
+        "unit": "USD/shares",
+        "report_id": "rpt-123456",
+        "filing_url": null
+    }))
+    .unwrap();
+
+    assert!(fact.unit.as_ref().unwrap().is_per_share());
+    let json = serde_json::to_value(&fact).unwrap();
+    assert_eq!(json["unit"], "iso4217:USD/xbrli:shares");
+}
+
+#[test]
+fn test_unparseable_unit_does_not_fail_the_fact() {
+    let facts: Vec<Fact> = serde_json::from_value(serde_json::json!([
+        {
+            "id": "fact-1",
+            "concept_name": "Revenues",
+            "concept_label": null,
+            "entity_id": "entity-123",
+            "entity_name": null,
+            "period_end": "2022-12-31",
+            "value": "100",
+            "unit": "US Dollars",
+            "report_id": "rpt-123456",
+            "filing_url": null
+        }
+    ]))
+    .unwrap();
+
+    let unit = facts[0].unit.as_ref().unwrap();
+    assert!(unit.is_unknown());
+    assert_eq!(unit, &Unit::unknown("US Dollars"));
+    assert!(!unit.is_monetary());
+    assert_eq!(unit.currency(), None);
+
+    let json = serde_json::to_value(&facts[0]).unwrap();
+    assert_eq!(json["unit"], "US Dollars");
+    assert_eq!(Unit::parse_lenient("USD"), Unit::monetary("USD"));
+}
@@ -0,0 +1,142 @@
+use mockall::predicate::eq;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xbrl_api_client::api::traits::MockXbrlApi;
+use xbrl_api_client::parser::dts::load_taxonomy_directory;
+use xbrl_api_client::parser::instance::parse_instance_file;
+use xbrl_api_client::parser::offline::OfflineApi;
+use xbrl_api_client::store::{Store, StoreApi};
+use xbrl_api_client::{Report, SearchParams, XbrlApi, XbrlApiError, XbrlClient, XbrlResult};
+
+const TAXONOMY_DIR: &str = "tests/mock_data/taxonomy";
+const INSTANCE: &str = "tests/mock_data/instance.xml";
+
+fn report(id: &str, filing_date: &str) -> Report {
+    Report {
+        id: id.to_string(),
+        accession_number: None,
+        filing_date: filing_date.to_string(),
+        fiscal_period: "FY".to_string(),
+        fiscal_year: 2022,
+        entity_id: "0000123456".to_string(),
+        entity_name: "Example Corp".to_string(),
+        cik: None,
+        taxonomy: "us-gaap".to_string(),
+        report_type: None,
+        filing_url: None,
+    }
+}
+
+fn search(concept: &str) -> SearchParams {
+    SearchParams {
+        taxonomy: String::new(),
+        concept_name: Some(concept.to_string()),
+        entity_id: None,
+        fiscal_year: None,
+        fiscal_period: None,
+        dimension_name: None,
+        member_name: None,
+        text_search: None,
+        value_greater_than: None,
+        value_less_than: None,
+    }
+}
+
+/// Code under test, written against the trait
+async fn latest_report(api: &dyn XbrlApi, taxonomy: &str) -> XbrlResult<Option<Report>> {
+    let reports = api.get_reports(taxonomy).await?;
+    Ok(reports.into_iter().max_by(|a, b| a.filing_date.cmp(&b.filing_date)))
+}
+
+fn offline_api() -> OfflineApi {
+    OfflineApi::new()
+        .open_taxonomy(TAXONOMY_DIR)
+        .unwrap()
+        .open_report(INSTANCE)
+        .unwrap()
+}
+
+fn store_api() -> StoreApi {
+    let dts = load_taxonomy_directory(TAXONOMY_DIR).unwrap();
+    let instance = parse_instance_file(INSTANCE).unwrap();
+    let mut store = Store::open_in_memory().unwrap();
+    store.save_taxonomies(&[dts.taxonomy]).unwrap();
+    store.save_concepts(&dts.concepts).unwrap();
+    store.save_networks(&dts.networks).unwrap();
+    store.save_reports(&[instance.report]).unwrap();
+    store.save_facts(&instance.facts).unwrap();
+    StoreApi::new(store)
+}
+
+#[tokio::test]
+async fn test_mock_stands_in_for_the_client() {
+    let mut api = MockXbrlApi::new();
+    api.expect_get_reports()
+        .with(eq("us-gaap"))
+        .times(1)
+        .returning(|_| Ok(vec![report("old", "2022-02-01"), report("new", "2023-02-01")]));
+    api.expect_get_reports()
+        .with(eq("ifrs"))
+        .returning(|_| Err(XbrlApiError::Unknown("down".to_string())));
+
+    assert_eq!(latest_report(&api, "us-gaap").await.unwrap().unwrap().id, "new");
+    assert!(latest_report(&api, "ifrs").await.is_err());
+}
+
+#[tokio::test]
+async fn test_client_implements_the_trait() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/reports"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "success",
+            "message": null,
+            "data": [report("rpt-1", "2023-02-01")],
+            "errors": null
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = XbrlClient::with_base_url("test_api_key", &server.uri()).unwrap();
+    assert_eq!(latest_report(&client, "us-gaap").await.unwrap().unwrap().id, "rpt-1");
+}
+
+#[tokio::test]
+async fn test_store_and_files_answer_alike() {
+    let apis: [Box<dyn XbrlApi>; 2] = [Box::new(store_api()), Box::new(offline_api())];
+
+    for api in &apis {
+        let taxonomies = api.get_taxonomies().await.unwrap();
+        assert_eq!(taxonomies[0].name, "Example Taxonomy");
+
+        assert_eq!(latest_report(api.as_ref(), "us-gaap").await.unwrap().unwrap().id, "instance");
+        assert_eq!(api.get_facts("instance").await.unwrap().len(), 11);
+        assert!(api.get_facts("other").await.unwrap().is_empty());
+
+        let revenues = api.search(search("Revenues")).await.unwrap();
+        assert_eq!(revenues.len(), 2);
+        let mut retail = search("Revenues");
+        retail.member_name = Some("RetailMember".to_string());
+        assert_eq!(api.search(retail).await.unwrap().len(), 1);
+        let mut other_year = search("Revenues");
+        other_year.fiscal_year = Some(2021);
+        assert!(api.search(other_year).await.unwrap().is_empty());
+
+        let concept = api.get_concept_details("ex", "GrossProfit").await.unwrap();
+        assert_eq!(concept.label, "Gross Profit");
+        assert!(matches!(
+            api.get_concept_details("ex", "Missing").await,
+            Err(XbrlApiError::NotFound { .. })
+        ));
+
+        let dimensions = api.get_dimensions("ex").await.unwrap();
+        assert_eq!(dimensions[0].name, "SegmentAxis");
+        assert_eq!(dimensions[0].members.as_ref().unwrap().len(), 3);
+
+        assert_eq!(api.get_networks("Example Taxonomy").await.unwrap().len(), 1);
+        let network = api.get_network_details("IncomeStatement").await.unwrap();
+        assert_eq!(network.nodes.unwrap()[0].concept_name, "IncomeStatementAbstract");
+        assert!(api.get_network_details("Missing").await.is_err());
+    }
+}